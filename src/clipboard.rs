@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use crate::{imp, Error, Window};
+
+/// An image stored on the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClipboardImage {
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+    /// The pixels of the image.
+    ///
+    /// The pixels are in the RGBA format, with 8 bits per channel, row by row, starting from
+    /// the top-left corner of the image. The color channels are not premultiplied by the alpha
+    /// channel.
+    pub rgba: Vec<u8>,
+}
+
+/// A format in which the contents of the clipboard can be provided.
+///
+/// This is used to offer contents without providing them right away, with
+/// [`Clipboard::offer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClipboardFormat {
+    /// Text, as provided by [`Clipboard::set_text`].
+    Text,
+    /// An image, as provided by [`Clipboard::set_image`].
+    Image,
+    /// A PNG-encoded image, as provided by [`Clipboard::set_png`].
+    Png,
+    /// A list of files, as provided by [`Clipboard::set_files`].
+    Files,
+    /// An HTML fragment, as provided by [`Clipboard::set_html`].
+    Html,
+    /// A custom format identified by its name, as provided by [`Clipboard::set_data`].
+    Custom(String),
+}
+
+/// A function that provides the contents of the clipboard offered by a window.
+///
+/// See [`Window::set_clipboard_renderer`].
+pub type ClipboardRenderer = Box<dyn Send + FnMut(&ClipboardFormat, &mut Clipboard)>;
+
+/// Exclusive access to the clipboard of the system.
+///
+/// Other applications cannot access the clipboard while a [`Clipboard`] instance exists, so it
+/// should be dropped as soon as possible.
+///
+/// # Setting the contents of the clipboard
+///
+/// The first time contents are set through a [`Clipboard`] instance, the previous contents of
+/// the clipboard are discarded. The same contents can then be provided in multiple formats,
+/// letting the application that pastes them pick the one it understands best. For example,
+/// an image can be provided with both [`set_image`](Clipboard::set_image) and
+/// [`set_png`](Clipboard::set_png).
+///
+/// # Delayed rendering
+///
+/// Contents that are expensive to produce can be [offered](Clipboard::offer) instead of being
+/// set. They are then only requested from the owner of the clipboard, through the function
+/// passed to [`Window::set_clipboard_renderer`], if another application actually pastes them.
+#[derive(Debug)]
+pub struct Clipboard(pub(crate) imp::Clipboard);
+
+impl Clipboard {
+    /// Opens the clipboard.
+    ///
+    /// When provided, `owner` becomes the owner of the clipboard once its contents are set.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if another application currently has the clipboard open.
+    pub fn open(owner: Option<&Window>) -> Result<Self, Error> {
+        let owner = owner.map_or(0, |window| window.0.hwnd());
+        imp::Clipboard::open(owner).map(Self).map_err(Error::os)
+    }
+
+    /// Discards the contents of the clipboard.
+    #[inline(always)]
+    pub fn clear(&mut self) -> Result<(), Error> {
+        self.0.clear().map_err(Error::os)
+    }
+
+    /// Returns the text stored on the clipboard, if any.
+    #[inline(always)]
+    pub fn text(&self) -> Option<String> {
+        self.0.text()
+    }
+
+    /// Places the provided text on the clipboard.
+    #[inline(always)]
+    pub fn set_text(&mut self, text: &str) -> Result<(), Error> {
+        self.0.set_text(text).map_err(Error::os)
+    }
+
+    /// Returns the image stored on the clipboard, if any.
+    ///
+    /// `None` is returned if the clipboard holds no image, or if the image is stored in a format
+    /// that is not supported.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** the image is read from the `CF_DIBV5` or `CF_DIB` formats. Only
+    ///   uncompressed images with 24 or 32 bits per pixel are supported.
+    #[inline(always)]
+    pub fn image(&self) -> Option<ClipboardImage> {
+        self.0.image()
+    }
+
+    /// Places the provided image on the clipboard.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of `image.rgba` is not `width * height * 4`.
+    #[inline(always)]
+    pub fn set_image(&mut self, image: &ClipboardImage) -> Result<(), Error> {
+        self.0.set_image(image).map_err(Error::os)
+    }
+
+    /// Returns the PNG-encoded image stored on the clipboard, if any.
+    ///
+    /// Many applications provide images in this format in addition to the native one, as it
+    /// preserves transparency more reliably. The data is returned as-is and must be decoded by
+    /// the application.
+    #[inline(always)]
+    pub fn png(&self) -> Option<Vec<u8>> {
+        self.0.png()
+    }
+
+    /// Places the provided PNG-encoded image on the clipboard.
+    ///
+    /// The data is not validated.
+    #[inline(always)]
+    pub fn set_png(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.0.set_png(data).map_err(Error::os)
+    }
+
+    /// Returns the list of files stored on the clipboard, if any.
+    ///
+    /// This is what file managers place on the clipboard when files are copied.
+    #[inline(always)]
+    pub fn files(&self) -> Option<Vec<PathBuf>> {
+        self.0.files()
+    }
+
+    /// Places the provided list of files on the clipboard.
+    ///
+    /// The files can then be pasted in a file manager. Paths should be absolute.
+    #[inline(always)]
+    pub fn set_files(&mut self, paths: &[&Path]) -> Result<(), Error> {
+        self.0.set_files(paths).map_err(Error::os)
+    }
+
+    /// Returns the HTML fragment stored on the clipboard, if any.
+    ///
+    /// Web browsers and word processors place the rich content that is copied in this format,
+    /// typically in addition to its plain [`text`](Clipboard::text). Only the fragment that was
+    /// copied is returned, without the document that surrounds it.
+    #[inline(always)]
+    pub fn html(&self) -> Option<String> {
+        self.0.html()
+    }
+
+    /// Places the provided HTML fragment on the clipboard.
+    ///
+    /// The fragment is wrapped in a document by this function. Applications that do not
+    /// understand HTML ignore it, so a plain-text version should usually be provided as well
+    /// with [`set_text`](Clipboard::set_text).
+    #[inline(always)]
+    pub fn set_html(&mut self, html: &str) -> Result<(), Error> {
+        self.0.set_html(html).map_err(Error::os)
+    }
+
+    /// Returns the data stored on the clipboard in the custom format with the provided name, if
+    /// any.
+    ///
+    /// Custom formats are identified by name, such that applications can agree on them.
+    #[inline(always)]
+    pub fn data(&self, format: &str) -> Option<Vec<u8>> {
+        self.0.data(format)
+    }
+
+    /// Places the provided data on the clipboard, in the custom format with the provided name.
+    #[inline(always)]
+    pub fn set_data(&mut self, format: &str, data: &[u8]) -> Result<(), Error> {
+        self.0.set_data(format, data).map_err(Error::os)
+    }
+
+    /// Announces that contents are available in the provided format, without providing them.
+    ///
+    /// When another application requests the contents in this format, the function passed to
+    /// [`Window::set_clipboard_renderer`] is called on the owner of the clipboard to provide
+    /// them. If the owner is destroyed while it still owns the clipboard, all the formats it
+    /// has offered are requested at once.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the clipboard has been opened without an owner.
+    #[inline(always)]
+    pub fn offer(&mut self, format: &ClipboardFormat) -> Result<(), Error> {
+        self.0.offer(format).map_err(Error::os)
+    }
+}
@@ -1,56 +1,765 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{
+    imp, Fullscreen, Icon, InputBackend, Monitor, Position, RawInputDevices, Size, Theme, Window,
+    WindowLevel,
+};
+
 /// The configuration of a window.
-#[derive(Debug, Clone)]
+///
+/// With the `serde` feature, the configuration can be loaded from a configuration file. Missing
+/// fields take their default value, the fields of [`attributes`](Config::attributes) appear at
+/// the top level, and the fields that refer to other objects (such as
+/// [`monitor`](Config::monitor) or [`owner`](Config::owner)) are skipped.
+///
+/// `Config` is neither `Send` nor `Sync`: it may refer to windows, monitors, raw handles and
+/// icons that belong to the thread creating the window. Its plain data is held by
+/// [`WindowAttributes`], which can be prepared on another thread and converted into a `Config`
+/// on the thread that creates the window.
+///
+/// Platform-specific options are set through the extension traits of the
+/// [`platform`](crate::platform) module.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Config<'a> {
+    /// The plain data of the configuration.
+    ///
+    /// See [`WindowAttributes`].
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub attributes: WindowAttributes<'a>,
+
+    /// The monitor the window is initially placed on.
+    ///
+    /// When set, [`position`](WindowAttributes::position) is relative to the top-left corner of the
+    /// work area of the monitor, and logical positions are converted using the [scale
+    /// factor](Monitor::scale_factor) of the monitor. If `position` is `None`, the window is
+    /// centered in the work area of the monitor instead.
+    ///
+    /// This cannot be used for child windows.
+    ///
+    /// **Default:** `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub monitor: Option<&'a Monitor>,
+
+    /// The icon of the window.
+    ///
+    /// The icon is set before the window is shown, such that the system never displays its
+    /// default icon in the title bar, the taskbar or the task switcher. See
+    /// [`Window::set_icon`].
+    ///
+    /// **Default:** `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub icon: Option<Icon>,
+
+    /// The fullscreen mode the window is initially in.
+    ///
+    /// The window is only shown once it covers its monitor, such that it never appears in its
+    /// windowed placement. When it leaves fullscreen mode, the window is restored to the
+    /// position and size it has been created with.
+    ///
+    /// This cannot be used for child windows.
+    ///
+    /// **Default:** `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub fullscreen: Option<Fullscreen>,
+
+    /// The owner of the window.
+    ///
+    /// An owned window always stays on top of its owner, is hidden when its owner is minimized,
+    /// and does not appear in the taskbar. It is destroyed along with its owner. This is
+    /// typically used for dialogs and tool palettes.
+    ///
+    /// **Default:** `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub owner: Option<&'a Window>,
+
+    /// The owner of the window, as a handle to a window that may belong to another library or
+    /// application.
+    ///
+    /// This behaves like [`owner`](Config::owner), which it takes precedence over. This is
+    /// typically used for the tool windows of a plugin, which stay on top of the window of
+    /// their host.
+    ///
+    /// **Default:** `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg(feature = "raw-window-handle")]
+    pub owner_handle: Option<rwh::WindowHandle<'a>>,
+
+    /// The parent of the window.
+    ///
+    /// When set, the window is created as a child of the provided window, which may belong to
+    /// another library or application. This is used to embed the window inside existing user
+    /// interfaces, such as the editor of an audio plugin. The position of a child window is
+    /// expressed relative to the client area of its parent, and it has no decorations.
+    ///
+    /// This cannot be used along with [`owner`](Config::owner) or
+    /// [`owner_handle`](Config::owner_handle).
+    ///
+    /// **Default:** `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg(feature = "raw-window-handle")]
+    pub parent: Option<rwh::WindowHandle<'a>>,
+
+    /// The platform-specific options of the window.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) platform: imp::PlatformConfig,
+}
+
+/// The plain data of the configuration of a window, such as its title, size and flags.
+///
+/// Unlike [`Config`], this type never refers to objects that belong to a thread, such that it
+/// can be built on any thread and sent to the one that creates the window, where it is
+/// converted into a [`Config`].
+///
+/// Strings can either be borrowed or owned, such that attributes built from runtime strings can
+/// be stored without borrowing them.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct WindowAttributes<'a> {
     /// The title of the window.
     ///
     /// **Default:** `"My Awesome Window"`
-    pub title: &'a str,
+    pub title: Cow<'a, str>,
+
+    /// The name of the window class registered for the window.
+    ///
+    /// Other applications and tools (such as accessibility software or automation scripts) may
+    /// use it to find the window. Windows created with the same class name share their class,
+    /// which is registered once by the process. On Windows, this also requires the same class
+    /// styles.
+    ///
+    /// **Default:** `"liwin_window_class"`
+    pub class_name: Cow<'a, str>,
+
+    /// The application user model ID of the window.
+    ///
+    /// The taskbar uses it to group windows, to pin them, and to attribute notifications to
+    /// them. If `None`, the ID of the process is used, which the system derives from the path
+    /// of the executable unless it has been set explicitly.
+    ///
+    /// **Default:** `None`
+    pub app_id: Option<Cow<'a, str>>,
+
+    /// The name that identifies the application among the other running applications.
+    ///
+    /// When set, the window receives the arguments that other instances of the application
+    /// forward with [`forward_to_running_instance`](crate::forward_to_running_instance) using
+    /// the same name. It should be unique to the application, such as its reverse domain name
+    /// (e.g. `"com.example.editor"`).
+    ///
+    /// **Default:** `None`
+    pub instance_name: Option<Cow<'a, str>>,
 
     /// The initial position of the window.
     ///
-    /// If `None`, a platform-specific default position will be used instead.
+    /// If `None`, a platform-specific default position will be used instead, unless
+    /// [`monitor`](Config::monitor) is set.
+    ///
+    /// Logical positions are converted using the scale factor of the monitor the window is
+    /// placed on. When [`monitor`](Config::monitor) is not set, this is the monitor that
+    /// contains a physical position, and the primary monitor for a logical one. For child
+    /// windows, the position is relative to the client area of the parent.
     ///
     /// **Default:** `None`
-    pub position: Option<(i32, i32)>,
+    pub position: Option<Position>,
 
-    /// The initial size of the window.
+    /// The initial size of the window's client area.
     ///
     /// If `None`, a platform-specific default size will be used instead.
     ///
+    /// Logical sizes are converted using the scale factor of the monitor the window is placed
+    /// on. See [`position`](WindowAttributes::position) for how that monitor is chosen.
+    ///
     /// **Default:** `None`
-    pub size: Option<(u32, u32)>,
+    pub size: Option<Size>,
+
+    /// The minimum size of the window's client area.
+    ///
+    /// If `None`, the window can be shrunk down to whatever size the system allows. Logical
+    /// sizes follow the scale factor of the window as it changes.
+    ///
+    /// The constraint is known to the system as soon as the window is created, and the initial
+    /// [`size`](WindowAttributes::size) of the window is enlarged to respect it.
+    ///
+    /// **Default:** `None`
+    pub min_size: Option<Size>,
+
+    /// The maximum size of the window's client area.
+    ///
+    /// If `None`, the window can be grown up to whatever size the system allows. Logical sizes
+    /// follow the scale factor of the window as it changes.
+    ///
+    /// The constraint is known to the system as soon as the window is created, and the initial
+    /// [`size`](WindowAttributes::size) of the window is reduced to respect it.
+    ///
+    /// **Default:** `None`
+    pub max_size: Option<Size>,
+
+    /// Whether the window should be modal.
+    ///
+    /// When enabled, the [`owner`] of the window is disabled until the window is dropped,
+    /// preventing the user from interacting with it while the window is open. The window must
+    /// have an owner.
+    ///
+    /// [`owner`]: Config::owner
+    ///
+    /// **Default:** `false`
+    pub modal: bool,
 
     /// The window should be initially visible.
     ///
     /// **Default:** `true`
     pub visible: bool,
 
+    /// Whether the window should be shown without being activated.
+    ///
+    /// When set, the window does not take the keyboard focus away from the window the user is
+    /// currently working with when it is initially shown. This is typically used for popups and
+    /// notifications. See [`Window::set_visible_without_activation`].
+    ///
+    /// Windows that are initially [`maximized`](WindowAttributes::maximized) are always activated.
+    ///
+    /// **Default:** `false`
+    pub no_activate: bool,
+
+    /// Whether the window should be initially maximized.
+    ///
+    /// The window is shown directly in its maximized state, and [`size`](WindowAttributes::size)
+    /// and [`position`](WindowAttributes::position) describe where it goes when restored. This is
+    /// ignored when the window is not initially [`visible`](WindowAttributes::visible), or when it
+    /// is initially in [`fullscreen`](Config::fullscreen) mode.
+    ///
+    /// **Default:** `false`
+    pub maximized: bool,
+
+    /// Whether the window should be initially minimized.
+    ///
+    /// When [`maximized`](WindowAttributes::maximized) is also set, the window is maximized once it
+    /// is restored. This is ignored when the window is not initially
+    /// [`visible`](WindowAttributes::visible), or when it is initially in
+    /// [`fullscreen`](Config::fullscreen) mode.
+    ///
+    /// **Default:** `false`
+    pub start_minimized: bool,
+
     /// Whether the window should be resizable.
     ///
     /// **Default:** `true`
     pub resizable: bool,
 
-    /// Whether the window should always appear on top of other windows.
+    /// The level of the window, which determines how it is ordered relative to other windows.
+    ///
+    /// **Default:** [`WindowLevel::Normal`]
+    pub level: WindowLevel,
+
+    /// Whether the background of the window should be transparent.
+    ///
+    /// When enabled, the alpha channel of the pixels rendered to the window is respected,
+    /// allowing the content behind the window to show through. This is typically used for
+    /// overlays and widgets, and requires [`decorations`](WindowAttributes::decorations) to be
+    /// disabled.
+    ///
+    /// **Default:** `false`
+    pub transparent: bool,
+
+    /// Whether the window should receive mouse input.
+    ///
+    /// When disabled, mouse input goes through the window and reaches whatever is behind it,
+    /// including windows of other applications. See [`Config::overlay`] for a configuration
+    /// suited to click-through overlays.
+    ///
+    /// **Default:** `true`
+    pub cursor_hittest: bool,
+
+    /// Whether the window should be a tool window.
+    ///
+    /// Tool windows do not appear in the taskbar or in the task switcher, and are not activated
+    /// when the user clicks on them. This is typically used for palettes, tooltips and overlays.
+    ///
+    /// **Default:** `false`
+    pub tool_window: bool,
+
+    /// Whether the window should be hidden from the taskbar.
+    ///
+    /// Unlike [`tool_window`](WindowAttributes::tool_window), the window is activated normally. It
+    /// does not appear in the task switcher either, and its title bar is drawn smaller when it has
+    /// [`decorations`](WindowAttributes::decorations). This is typically used for helper windows.
     ///
     /// **Default:** `false`
-    pub always_on_top: bool,
+    pub skip_taskbar: bool,
+
+    /// The theme of the window, which determines how its decorations are drawn.
+    ///
+    /// The theme is applied before the window is shown. If `None`, the window follows the
+    /// preference of the user, including when it changes. See [`Window::set_theme`].
+    ///
+    /// **Default:** `None`
+    pub theme: Option<Theme>,
 
     /// Whether the window should include the system's default decorations.
     ///
     /// **Default:** `true`
     pub decorations: bool,
+
+    /// Whether a window without decorations should keep the native resize borders and drop
+    /// shadow of the system.
+    ///
+    /// The resize borders are invisible and extend over the edges of the client area. This
+    /// only has an effect when [`decorations`] is `false`, and the borders are only present when
+    /// the window is [`resizable`].
+    ///
+    /// [`decorations`]: WindowAttributes::decorations
+    /// [`resizable`]: WindowAttributes::resizable
+    ///
+    /// **Default:** `false`
+    pub undecorated_shadow: bool,
+
+    /// Whether data can be dropped on the window.
+    ///
+    /// When disabled, the window refuses every drag-and-drop operation, such that files and
+    /// other data dragged from other applications can never reach it. No
+    /// [`DragEntered`](crate::Event::DragEntered) or [`Dropped`](crate::Event::Dropped) events
+    /// are sent.
+    ///
+    /// Drag-and-drop is unavailable on threads that use the multithreaded COM apartment, where
+    /// the window refuses every drag-and-drop operation regardless.
+    ///
+    /// **Default:** `true`
+    pub accept_drops: bool,
+
+    /// The device classes from which the window receives raw input events.
+    ///
+    /// See [`Window::set_raw_input_devices`].
+    ///
+    /// **Default:** [`RawInputDevices::all()`]
+    pub raw_input: RawInputDevices,
+
+    /// The source from which the window produces its input events.
+    ///
+    /// **Default:** [`InputBackend::RawInput`]
+    pub input_backend: InputBackend,
 }
 
-impl<'a> Default for Config<'a> {
+impl<'a> Default for WindowAttributes<'a> {
     fn default() -> Self {
         Self {
-            title: "My Awesome Window",
+            title: Cow::Borrowed("My Awesome Window"),
+            class_name: Cow::Borrowed("liwin_window_class"),
+            app_id: None,
+            instance_name: None,
             position: None,
             size: None,
+            min_size: None,
+            max_size: None,
+            modal: false,
             visible: true,
+            no_activate: false,
+            maximized: false,
+            start_minimized: false,
             resizable: true,
-            always_on_top: false,
+            level: WindowLevel::Normal,
+            transparent: false,
+            cursor_hittest: true,
+            tool_window: false,
+            skip_taskbar: false,
+            theme: None,
             decorations: true,
+            undecorated_shadow: false,
+            accept_drops: true,
+            raw_input: RawInputDevices::all(),
+            input_backend: InputBackend::RawInput,
+        }
+    }
+}
+
+impl<'a> From<WindowAttributes<'a>> for Config<'a> {
+    fn from(attributes: WindowAttributes<'a>) -> Self {
+        Self {
+            attributes,
+            ..Self::default()
+        }
+    }
+}
+
+impl<'a> Config<'a> {
+    /// Creates a [`Config`] suited to overlays, such as FPS counters or screen annotation tools.
+    ///
+    /// The window is transparent, has no decorations, lets mouse input go through it, never
+    /// takes the focus, does not appear in the taskbar, and stays on top of other windows.
+    ///
+    /// # Note
+    ///
+    /// Applications in exclusive fullscreen mode are still drawn on top of the overlay.
+    /// Borderless fullscreen applications are not.
+    pub fn overlay() -> Self {
+        Self::from(WindowAttributes {
+            resizable: false,
+            level: WindowLevel::AlwaysOnTop,
+            transparent: true,
+            cursor_hittest: false,
+            tool_window: true,
+            decorations: false,
+            ..WindowAttributes::default()
+        })
+    }
+
+    /// Sets the title of the window.
+    ///
+    /// This accepts both borrowed and owned strings.
+    pub fn with_title(mut self, title: impl Into<Cow<'a, str>>) -> Self {
+        self.attributes.title = title.into();
+        self
+    }
+
+    /// Checks that the configuration makes sense, without creating a window.
+    ///
+    /// This is done by [`Window::new`], but can be used to report invalid configurations early,
+    /// such as ones loaded from a configuration file.
+    ///
+    /// A [`min_size`](WindowAttributes::min_size) and a [`max_size`](WindowAttributes::max_size)
+    /// expressed in different units are only compared once the window exists, as their ratio
+    /// depends on its scale factor.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.attributes.class_name.is_empty() {
+            return Err(ConfigError::EmptyClassName);
+        }
+
+        let strings = [
+            ("title", Some(&*self.attributes.title)),
+            ("class_name", Some(&*self.attributes.class_name)),
+            ("app_id", self.attributes.app_id.as_deref()),
+            ("instance_name", self.attributes.instance_name.as_deref()),
+        ];
+        for (field, string) in strings {
+            if string.is_some_and(|s| s.contains('\0')) {
+                return Err(ConfigError::NulInString { field });
+            }
+        }
+
+        let sizes = [
+            ("size", self.attributes.size, false),
+            ("min_size", self.attributes.min_size, true),
+            ("max_size", self.attributes.max_size, false),
+        ];
+        for (field, size, allow_zero) in sizes {
+            if size.is_some_and(|size| !is_valid_size(size, allow_zero)) {
+                return Err(ConfigError::InvalidSize { field });
+            }
+        }
+
+        let min_exceeds_max = match (self.attributes.min_size, self.attributes.max_size) {
+            (Some(Size::Physical(min)), Some(Size::Physical(max))) => {
+                min.width > max.width || min.height > max.height
+            }
+            (Some(Size::Logical(min)), Some(Size::Logical(max))) => {
+                min.width > max.width || min.height > max.height
+            }
+            _ => false,
+        };
+        if min_exceeds_max {
+            return Err(ConfigError::MinSizeExceedsMaxSize);
+        }
+
+        if self.attributes.decorations && self.attributes.transparent {
+            return Err(ConfigError::Conflict("decorations", "transparent"));
+        }
+
+        #[cfg(feature = "raw-window-handle")]
+        if self.parent.is_some() {
+            if self.fullscreen.is_some() {
+                return Err(ConfigError::Conflict("parent", "fullscreen"));
+            }
+            if self.monitor.is_some() {
+                return Err(ConfigError::Conflict("parent", "monitor"));
+            }
+            if self.owner.is_some() {
+                return Err(ConfigError::Conflict("parent", "owner"));
+            }
+            if self.owner_handle.is_some() {
+                return Err(ConfigError::Conflict("parent", "owner_handle"));
+            }
+        }
+
+        #[cfg(feature = "raw-window-handle")]
+        let has_owner = self.owner.is_some() || self.owner_handle.is_some();
+        #[cfg(not(feature = "raw-window-handle"))]
+        let has_owner = self.owner.is_some();
+
+        if self.attributes.modal && !has_owner {
+            return Err(ConfigError::ModalWithoutOwner);
+        }
+
+        Ok(())
+    }
+
+    /// Places the window at the provided offset from the top-left corner of the work area of
+    /// `monitor`.
+    ///
+    /// Logical offsets are converted using the scale factor of the monitor, as is a logical
+    /// [`size`](WindowAttributes::size), such that the placement of the window matches its size on
+    /// every monitor. See [`monitor`](Config::monitor) for more information.
+    pub fn position_relative_to(
+        mut self,
+        monitor: &'a Monitor,
+        offset: impl Into<Position>,
+    ) -> Self {
+        self.attributes.position = Some(offset.into());
+        Self {
+            monitor: Some(monitor),
+            ..self
         }
     }
+
+    /// Places the window at the center of the work area of `monitor`.
+    ///
+    /// See [`monitor`](Config::monitor) for more information.
+    pub fn centered_on(mut self, monitor: &'a Monitor) -> Self {
+        self.attributes.position = None;
+        Self {
+            monitor: Some(monitor),
+            ..self
+        }
+    }
+}
+
+/// Returns whether the dimensions of `size` are positive and finite.
+///
+/// When `allow_zero` is set, dimensions of zero are accepted as well.
+fn is_valid_size(size: Size, allow_zero: bool) -> bool {
+    match size {
+        Size::Physical(size) => allow_zero || (size.width != 0 && size.height != 0),
+        Size::Logical(size) => [size.width, size.height]
+            .into_iter()
+            .all(|x| x.is_finite() && (x > 0.0 || (allow_zero && x == 0.0))),
+    }
+}
+
+/// A problem with a [`Config`], reported by [`Config::validate`].
+///
+/// Fields are identified by their name in [`Config`] or [`WindowAttributes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// A size has a dimension of zero, or a logical size has a negative or non-finite
+    /// dimension.
+    ///
+    /// A [`min_size`](WindowAttributes::min_size) of zero is accepted.
+    InvalidSize {
+        /// The field that holds the size.
+        field: &'static str,
+    },
+    /// The [`min_size`](WindowAttributes::min_size) is larger than the
+    /// [`max_size`](WindowAttributes::max_size) in at least one dimension.
+    MinSizeExceedsMaxSize,
+    /// A string contains a NUL character, which the system would interpret as its end.
+    NulInString {
+        /// The field that holds the string.
+        field: &'static str,
+    },
+    /// The [`class_name`](WindowAttributes::class_name) is empty.
+    EmptyClassName,
+    /// The window is [`modal`](WindowAttributes::modal), but has no [`owner`](Config::owner).
+    ModalWithoutOwner,
+    /// Two fields that cannot be used together are both set.
+    Conflict(&'static str, &'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSize { field } => write!(f, "`{field}` is not a valid size"),
+            Self::MinSizeExceedsMaxSize => f.write_str("`min_size` is larger than `max_size`"),
+            Self::NulInString { field } => write!(f, "`{field}` contains a NUL character"),
+            Self::EmptyClassName => f.write_str("`class_name` is empty"),
+            Self::ModalWithoutOwner => f.write_str("`modal` is set, but the window has no owner"),
+            Self::Conflict(a, b) => write!(f, "`{a}` and `{b}` cannot be used together"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LogicalSize, PhysicalSize};
+
+    /// Returns a handle to a window that does not exist, which is enough for validation.
+    #[cfg(feature = "raw-window-handle")]
+    fn handle() -> rwh::WindowHandle<'static> {
+        let handle = rwh::Win32WindowHandle::new(std::num::NonZeroIsize::new(1).unwrap());
+        unsafe { rwh::WindowHandle::borrow_raw(handle.into()) }
+    }
+
+    #[test]
+    fn default_and_overlay_are_valid() {
+        assert_eq!(Config::default().validate(), Ok(()));
+        assert_eq!(Config::overlay().validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_size() {
+        let sizes = [
+            ("size", Size::Physical(PhysicalSize::new(0, 100))),
+            ("size", Size::Logical(LogicalSize::new(-1.0, 100.0))),
+            ("size", Size::Logical(LogicalSize::new(100.0, f64::NAN))),
+            ("max_size", Size::Logical(LogicalSize::new(0.0, 100.0))),
+            (
+                "min_size",
+                Size::Logical(LogicalSize::new(f64::INFINITY, 0.0)),
+            ),
+        ];
+
+        for (field, size) in sizes {
+            let mut config = Config::default();
+            match field {
+                "size" => config.attributes.size = Some(size),
+                "min_size" => config.attributes.min_size = Some(size),
+                _ => config.attributes.max_size = Some(size),
+            }
+            assert_eq!(config.validate(), Err(ConfigError::InvalidSize { field }));
+        }
+
+        let config = Config::from(WindowAttributes {
+            min_size: Some(Size::Physical(PhysicalSize::new(0, 0))),
+            ..WindowAttributes::default()
+        });
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn min_size_exceeds_max_size() {
+        let config = Config::from(WindowAttributes {
+            min_size: Some(Size::Physical(PhysicalSize::new(200, 100))),
+            max_size: Some(Size::Physical(PhysicalSize::new(100, 100))),
+            ..WindowAttributes::default()
+        });
+        assert_eq!(config.validate(), Err(ConfigError::MinSizeExceedsMaxSize));
+
+        // Sizes in different units are only compared once the scale factor is known.
+        let config = Config::from(WindowAttributes {
+            min_size: Some(Size::Logical(LogicalSize::new(200.0, 100.0))),
+            max_size: Some(Size::Physical(PhysicalSize::new(100, 100))),
+            ..WindowAttributes::default()
+        });
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn nul_in_string() {
+        let config = Config::default().with_title("a\0b");
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::NulInString { field: "title" })
+        );
+
+        let config = Config::from(WindowAttributes {
+            app_id: Some("app\0id".into()),
+            ..WindowAttributes::default()
+        });
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::NulInString { field: "app_id" })
+        );
+
+        let config = Config::from(WindowAttributes {
+            instance_name: Some("com.example\0".into()),
+            ..WindowAttributes::default()
+        });
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::NulInString {
+                field: "instance_name"
+            })
+        );
+    }
+
+    #[test]
+    fn empty_class_name() {
+        let config = Config::from(WindowAttributes {
+            class_name: "".into(),
+            ..WindowAttributes::default()
+        });
+        assert_eq!(config.validate(), Err(ConfigError::EmptyClassName));
+    }
+
+    #[test]
+    fn decorations_and_transparent_conflict() {
+        let config = Config::from(WindowAttributes {
+            transparent: true,
+            ..WindowAttributes::default()
+        });
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::Conflict("decorations", "transparent"))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "raw-window-handle")]
+    fn parent_conflicts() {
+        let config = Config {
+            parent: Some(handle()),
+            owner_handle: Some(handle()),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::Conflict("parent", "owner_handle"))
+        );
+
+        let config = Config {
+            parent: Some(handle()),
+            fullscreen: Some(Fullscreen::Borderless(None)),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::Conflict("parent", "fullscreen"))
+        );
+    }
+
+    #[test]
+    fn modal_without_owner() {
+        let config = Config::from(WindowAttributes {
+            modal: true,
+            ..WindowAttributes::default()
+        });
+        assert_eq!(config.validate(), Err(ConfigError::ModalWithoutOwner));
+
+        #[cfg(feature = "raw-window-handle")]
+        {
+            let config = Config {
+                attributes: WindowAttributes {
+                    modal: true,
+                    ..WindowAttributes::default()
+                },
+                owner_handle: Some(handle()),
+                ..Config::default()
+            };
+            assert_eq!(config.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn window_attributes_are_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<WindowAttributes<'static>>();
+    }
+
+    #[test]
+    fn is_valid_size_accepts_zero_only_when_allowed() {
+        let zero = Size::Physical(PhysicalSize::new(0, 0));
+        assert!(is_valid_size(zero, true));
+        assert!(!is_valid_size(zero, false));
+        assert!(!is_valid_size(
+            Size::Logical(LogicalSize::new(-0.5, 1.0)),
+            true
+        ));
+    }
 }
@@ -0,0 +1,108 @@
+use std::fmt;
+
+use crate::{imp, Error};
+
+/// A cursor icon provided by the system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorIcon {
+    /// The default arrow.
+    #[default]
+    Arrow,
+    /// A vertical bar, indicating that text can be selected or entered.
+    IBeam,
+    /// A pointing hand, indicating a link.
+    Hand,
+    /// A crosshair, indicating precise selection.
+    Crosshair,
+    /// An hourglass or a spinning wheel, indicating that the application is busy.
+    Wait,
+    /// An arrow with an hourglass or a spinning wheel, indicating that the application is busy
+    /// but can still be interacted with.
+    Progress,
+    /// An arrow with a question mark.
+    Help,
+    /// A slashed circle, indicating that an action is not allowed.
+    NotAllowed,
+    /// A four-pointed arrow, indicating that something can be moved.
+    Move,
+    /// A horizontal double-pointed arrow, indicating horizontal resizing.
+    ResizeHorizontal,
+    /// A vertical double-pointed arrow, indicating vertical resizing.
+    ResizeVertical,
+    /// A diagonal double-pointed arrow pointing north-west and south-east.
+    ResizeNwSe,
+    /// A diagonal double-pointed arrow pointing north-east and south-west.
+    ResizeNeSw,
+}
+
+/// The way the cursor is restricted to a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorGrabMode {
+    /// The cursor can move freely.
+    #[default]
+    None,
+    /// The cursor is confined to the client area of the window.
+    Confined,
+    /// The cursor cannot move at all.
+    ///
+    /// Mouse motion is still reported through [`Event::MouseMoved`](crate::Event::MouseMoved)
+    /// events, making this mode suited to first-person camera controls.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** the cursor is confined to the single point where it was when the mode was
+    ///   applied. If it was outside of the client area, it is moved to the closest point within
+    ///   the client area.
+    Locked,
+}
+
+/// A custom cursor image.
+///
+/// Cloning a [`Cursor`] is cheap, as the underlying image is shared.
+#[derive(Clone)]
+pub struct Cursor(pub(crate) imp::Cursor);
+
+impl Cursor {
+    /// Creates a new [`Cursor`] from raw pixels.
+    ///
+    /// The pixels are expected to be in the RGBA format, with 8 bits per channel, row by row,
+    /// starting from the top-left corner of the image. `hotspot` is the position of the pixel
+    /// that designates the actual position of the cursor, relative to the top-left corner of
+    /// the image.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`ErrorKind::InvalidArgument`](crate::ErrorKind::InvalidArgument)
+    /// if the length of `rgba` is not `width * height * 4`.
+    pub fn from_rgba(
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot: (u32, u32),
+    ) -> Result<Self, Error> {
+        match imp::Cursor::from_rgba(rgba, width, height, hotspot) {
+            Ok(cursor) => Ok(Self(cursor)),
+            Err(error) => Err(Error::os(error)),
+        }
+    }
+
+    /// Creates a new [`Cursor`] from the content of a Windows cursor file.
+    ///
+    /// Both static (`.cur`) and animated (`.ani`) cursors are supported. When a static cursor
+    /// file contains multiple images, the one that best matches the size of the cursors of the
+    /// system is used.
+    pub fn from_cursor_file(data: &[u8]) -> Result<Self, Error> {
+        match imp::Cursor::from_cursor_file(data) {
+            Ok(cursor) => Ok(Self(cursor)),
+            Err(error) => Err(Error::os(error)),
+        }
+    }
+}
+
+impl fmt::Debug for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
@@ -0,0 +1,153 @@
+use std::path::{Path, PathBuf};
+
+use crate::{imp, Error, Window};
+
+/// The buttons displayed by a [`message_box`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MessageBoxButtons {
+    /// A single "OK" button.
+    #[default]
+    Ok,
+    /// An "OK" and a "Cancel" button.
+    OkCancel,
+    /// A "Yes" and a "No" button.
+    YesNo,
+    /// A "Yes", a "No" and a "Cancel" button.
+    YesNoCancel,
+    /// A "Retry" and a "Cancel" button.
+    RetryCancel,
+}
+
+/// A button of a [`message_box`], chosen by the user to close it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageBoxButton {
+    /// The "OK" button.
+    Ok,
+    /// The "Cancel" button.
+    ///
+    /// This is also returned when a message box with a "Cancel" button is closed with the
+    /// ESCAPE key or the close button of its title bar.
+    Cancel,
+    /// The "Yes" button.
+    Yes,
+    /// The "No" button.
+    No,
+    /// The "Retry" button.
+    Retry,
+}
+
+/// Displays a message box, and blocks until the user closes it.
+///
+/// When `parent` is provided, the message box is displayed above it and the window cannot be
+/// interacted with until the message box is closed. The texts of the buttons are localized by
+/// the system.
+///
+/// The button chosen by the user is returned. A message box with a single "OK" button always
+/// returns [`MessageBoxButton::Ok`], even when closed with the ESCAPE key.
+///
+/// # Platform-specific
+///
+/// - **Windows:** the parent window keeps processing messages while the message box is open.
+///   The events it produces are discarded, unless the message box is displayed through
+///   [`Window::run_modal`].
+#[inline(always)]
+pub fn message_box(
+    parent: Option<&Window>,
+    title: &str,
+    text: &str,
+    buttons: MessageBoxButtons,
+) -> Result<MessageBoxButton, Error> {
+    let parent = parent.map_or(0, |window| window.0.hwnd());
+    imp::message_box(parent, title, text, buttons).map_err(Error::os)
+}
+
+/// A file type the user can choose from in a [`FileDialog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileFilter<'a> {
+    /// The name of the file type (e.g. `"Images"`).
+    pub name: &'a str,
+    /// The extensions of the files of this type, without the leading dot (e.g. `["png",
+    /// "jpg"]`).
+    ///
+    /// The `"*"` extension matches all files.
+    pub extensions: &'a [&'a str],
+}
+
+/// A native dialog that lets the user choose files or folders to open, or where to save a file.
+///
+/// The dialog is configured through its fields, then displayed with one of
+/// [`pick_file`](FileDialog::pick_file), [`pick_files`](FileDialog::pick_files),
+/// [`pick_folder`](FileDialog::pick_folder) or [`save_file`](FileDialog::save_file). These
+/// functions block until the user closes the dialog, and return `None` if it has been canceled.
+///
+/// When a parent window is provided, the dialog is displayed above it and the window cannot be
+/// interacted with until the dialog is closed. To keep drawing the window in the meantime, the
+/// dialog can be displayed through [`Window::run_modal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FileDialog<'a> {
+    /// The title of the dialog.
+    ///
+    /// When `None`, the system chooses a title depending on the kind of the dialog.
+    ///
+    /// **Default:** `None`
+    pub title: Option<&'a str>,
+    /// The file types the user can choose from.
+    ///
+    /// The first one is selected when the dialog is opened. When saving a file, the first
+    /// extension of the selected type is appended to file names entered without one. When
+    /// empty, all files are displayed. This is ignored when picking a folder.
+    ///
+    /// **Default:** `&[]`
+    pub filters: &'a [FileFilter<'a>],
+    /// The directory displayed when the dialog is opened.
+    ///
+    /// The system may display the directory the user has last chosen a file from instead.
+    ///
+    /// **Default:** `None`
+    pub directory: Option<&'a Path>,
+    /// The file name initially entered in the dialog.
+    ///
+    /// This is typically used to suggest a name when saving a file.
+    ///
+    /// **Default:** `None`
+    pub file_name: Option<&'a str>,
+}
+
+impl<'a> FileDialog<'a> {
+    /// Lets the user choose an existing file to open.
+    #[inline(always)]
+    pub fn pick_file(&self, parent: Option<&Window>) -> Result<Option<PathBuf>, Error> {
+        let parent = parent.map_or(0, |window| window.0.hwnd());
+        imp::pick_files(self, parent, false)
+            .map(|paths| paths.and_then(|paths| paths.into_iter().next()))
+            .map_err(Error::os)
+    }
+
+    /// Lets the user choose any number of existing files to open.
+    ///
+    /// When the dialog is not canceled, at least one path is returned.
+    #[inline(always)]
+    pub fn pick_files(&self, parent: Option<&Window>) -> Result<Option<Vec<PathBuf>>, Error> {
+        let parent = parent.map_or(0, |window| window.0.hwnd());
+        imp::pick_files(self, parent, true).map_err(Error::os)
+    }
+
+    /// Lets the user choose an existing folder.
+    ///
+    /// This is typically used to choose where an application installs or writes its output.
+    /// The [`filters`](FileDialog::filters) of the dialog are ignored.
+    #[inline(always)]
+    pub fn pick_folder(&self, parent: Option<&Window>) -> Result<Option<PathBuf>, Error> {
+        let parent = parent.map_or(0, |window| window.0.hwnd());
+        imp::pick_folder(self, parent).map_err(Error::os)
+    }
+
+    /// Lets the user choose where to save a file.
+    ///
+    /// The user is asked for confirmation when choosing a file that already exists.
+    #[inline(always)]
+    pub fn save_file(&self, parent: Option<&Window>) -> Result<Option<PathBuf>, Error> {
+        let parent = parent.map_or(0, |window| window.0.hwnd());
+        imp::save_file(self, parent).map_err(Error::os)
+    }
+}
@@ -0,0 +1,308 @@
+/// A size, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicalSize {
+    /// The width, in physical pixels.
+    pub width: u32,
+    /// The height, in physical pixels.
+    pub height: u32,
+}
+
+impl PhysicalSize {
+    /// Creates a new [`PhysicalSize`].
+    #[inline]
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Converts this size into logical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    #[inline]
+    pub fn to_logical(self, scale_factor: f64) -> LogicalSize {
+        LogicalSize {
+            width: self.width as f64 / scale_factor,
+            height: self.height as f64 / scale_factor,
+        }
+    }
+}
+
+impl From<(u32, u32)> for PhysicalSize {
+    #[inline]
+    fn from((width, height): (u32, u32)) -> Self {
+        Self { width, height }
+    }
+}
+
+impl From<PhysicalSize> for (u32, u32) {
+    #[inline]
+    fn from(size: PhysicalSize) -> Self {
+        (size.width, size.height)
+    }
+}
+
+/// A size, in logical pixels.
+///
+/// Logical pixels are scaled by the [scale factor](crate::Window::scale_factor) of the monitor,
+/// such that they keep the same physical size on every monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogicalSize {
+    /// The width, in logical pixels.
+    pub width: f64,
+    /// The height, in logical pixels.
+    pub height: f64,
+}
+
+impl LogicalSize {
+    /// Creates a new [`LogicalSize`].
+    #[inline]
+    pub const fn new(width: f64, height: f64) -> Self {
+        Self { width, height }
+    }
+
+    /// Converts this size into physical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    ///
+    /// The result is rounded to the nearest pixel.
+    #[inline]
+    pub fn to_physical(self, scale_factor: f64) -> PhysicalSize {
+        PhysicalSize {
+            width: (self.width * scale_factor).round() as u32,
+            height: (self.height * scale_factor).round() as u32,
+        }
+    }
+}
+
+impl From<(f64, f64)> for LogicalSize {
+    #[inline]
+    fn from((width, height): (f64, f64)) -> Self {
+        Self { width, height }
+    }
+}
+
+impl From<LogicalSize> for (f64, f64) {
+    #[inline]
+    fn from(size: LogicalSize) -> Self {
+        (size.width, size.height)
+    }
+}
+
+/// A position, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicalPosition {
+    /// The X coordinate, in physical pixels.
+    pub x: i32,
+    /// The Y coordinate, in physical pixels.
+    pub y: i32,
+}
+
+impl PhysicalPosition {
+    /// Creates a new [`PhysicalPosition`].
+    #[inline]
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts this position into logical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    #[inline]
+    pub fn to_logical(self, scale_factor: f64) -> LogicalPosition {
+        LogicalPosition {
+            x: self.x as f64 / scale_factor,
+            y: self.y as f64 / scale_factor,
+        }
+    }
+}
+
+impl From<(i32, i32)> for PhysicalPosition {
+    #[inline]
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<PhysicalPosition> for (i32, i32) {
+    #[inline]
+    fn from(position: PhysicalPosition) -> Self {
+        (position.x, position.y)
+    }
+}
+
+/// A position, in logical pixels.
+///
+/// See [`LogicalSize`] for more information about logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogicalPosition {
+    /// The X coordinate, in logical pixels.
+    pub x: f64,
+    /// The Y coordinate, in logical pixels.
+    pub y: f64,
+}
+
+impl LogicalPosition {
+    /// Creates a new [`LogicalPosition`].
+    #[inline]
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts this position into physical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    ///
+    /// The result is rounded to the nearest pixel.
+    #[inline]
+    pub fn to_physical(self, scale_factor: f64) -> PhysicalPosition {
+        PhysicalPosition {
+            x: (self.x * scale_factor).round() as i32,
+            y: (self.y * scale_factor).round() as i32,
+        }
+    }
+}
+
+impl From<(f64, f64)> for LogicalPosition {
+    #[inline]
+    fn from((x, y): (f64, f64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<LogicalPosition> for (f64, f64) {
+    #[inline]
+    fn from(position: LogicalPosition) -> Self {
+        (position.x, position.y)
+    }
+}
+
+/// A size, in either physical or logical pixels.
+///
+/// This is accepted by the functions that take a size, such that the caller can choose the unit
+/// that suits it best.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Size {
+    /// A size in physical pixels.
+    Physical(PhysicalSize),
+    /// A size in logical pixels.
+    Logical(LogicalSize),
+}
+
+impl Size {
+    /// Converts this size into physical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    #[inline]
+    pub fn to_physical(self, scale_factor: f64) -> PhysicalSize {
+        match self {
+            Self::Physical(size) => size,
+            Self::Logical(size) => size.to_physical(scale_factor),
+        }
+    }
+
+    /// Converts this size into logical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    #[inline]
+    pub fn to_logical(self, scale_factor: f64) -> LogicalSize {
+        match self {
+            Self::Physical(size) => size.to_logical(scale_factor),
+            Self::Logical(size) => size,
+        }
+    }
+}
+
+impl From<PhysicalSize> for Size {
+    #[inline]
+    fn from(size: PhysicalSize) -> Self {
+        Self::Physical(size)
+    }
+}
+
+impl From<LogicalSize> for Size {
+    #[inline]
+    fn from(size: LogicalSize) -> Self {
+        Self::Logical(size)
+    }
+}
+
+/// A position, in either physical or logical pixels.
+///
+/// This is accepted by the functions that take a position, such that the caller can choose the
+/// unit that suits it best.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Position {
+    /// A position in physical pixels.
+    Physical(PhysicalPosition),
+    /// A position in logical pixels.
+    Logical(LogicalPosition),
+}
+
+impl Position {
+    /// Converts this position into physical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    #[inline]
+    pub fn to_physical(self, scale_factor: f64) -> PhysicalPosition {
+        match self {
+            Self::Physical(position) => position,
+            Self::Logical(position) => position.to_physical(scale_factor),
+        }
+    }
+
+    /// Converts this position into logical pixels, given the scale factor of the monitor it is
+    /// displayed on.
+    #[inline]
+    pub fn to_logical(self, scale_factor: f64) -> LogicalPosition {
+        match self {
+            Self::Physical(position) => position.to_logical(scale_factor),
+            Self::Logical(position) => position,
+        }
+    }
+}
+
+impl From<PhysicalPosition> for Position {
+    #[inline]
+    fn from(position: PhysicalPosition) -> Self {
+        Self::Physical(position)
+    }
+}
+
+impl From<LogicalPosition> for Position {
+    #[inline]
+    fn from(position: LogicalPosition) -> Self {
+        Self::Logical(position)
+    }
+}
+
+#[cfg(feature = "mint")]
+mod mint_impls {
+    use super::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+
+    macro_rules! impl_mint {
+        ($ty:ty, $mint:ident<$scalar:ty>, $a:ident, $b:ident) => {
+            impl From<mint::$mint<$scalar>> for $ty {
+                #[inline]
+                fn from(value: mint::$mint<$scalar>) -> Self {
+                    Self {
+                        $a: value.x,
+                        $b: value.y,
+                    }
+                }
+            }
+
+            impl From<$ty> for mint::$mint<$scalar> {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self {
+                        x: value.$a,
+                        y: value.$b,
+                    }
+                }
+            }
+        };
+    }
+
+    impl_mint!(PhysicalSize, Vector2<u32>, width, height);
+    impl_mint!(LogicalSize, Vector2<f64>, width, height);
+    impl_mint!(PhysicalPosition, Point2<i32>, x, y);
+    impl_mint!(LogicalPosition, Point2<f64>, x, y);
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::imp;
+
+/// What happens to the data of a drag-and-drop operation once it is dropped.
+///
+/// The effect is chosen by the window the data is dragged over, with
+/// [`Window::set_drop_effect`](crate::Window::set_drop_effect), and is reflected by the cursor.
+/// The source of the data only allows some effects; the others are treated as
+/// [`DropEffect::None`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DropEffect {
+    /// The data cannot be dropped on the window.
+    None,
+    /// The data is copied into the window.
+    #[default]
+    Copy,
+    /// The data is moved into the window, and removed from its source.
+    Move,
+    /// A link to the original data is created.
+    Link,
+}
+
+/// The data being dragged over a window.
+///
+/// The data is only transferred when it is requested. It can be kept after the event that
+/// provided it, but the source of the data may stop providing it once the operation is over.
+///
+/// Cloning a [`DragData`] does not copy the data, and two instances are equal when they refer
+/// to the data of the same operation.
+#[derive(Clone, PartialEq)]
+pub struct DragData(pub(crate) imp::DragData);
+
+impl DragData {
+    /// Returns whether the data is available as text.
+    #[inline(always)]
+    pub fn has_text(&self) -> bool {
+        self.0.has_text()
+    }
+
+    /// Returns the data as text, if available.
+    #[inline(always)]
+    pub fn text(&self) -> Option<String> {
+        self.0.text()
+    }
+
+    /// Returns whether the data is available as a list of files.
+    #[inline(always)]
+    pub fn has_files(&self) -> bool {
+        self.0.has_files()
+    }
+
+    /// Returns the data as a list of files, if available.
+    #[inline(always)]
+    pub fn files(&self) -> Option<Vec<PathBuf>> {
+        self.0.files()
+    }
+
+    /// Returns whether the data is available in the custom format with the provided name.
+    ///
+    /// Custom formats are identified by name, such that applications can agree on them.
+    #[inline(always)]
+    pub fn has_format(&self, format: &str) -> bool {
+        self.0.has_format(format)
+    }
+
+    /// Returns the data in the custom format with the provided name, if available.
+    #[inline(always)]
+    pub fn data(&self, format: &str) -> Option<Vec<u8>> {
+        self.0.data(format)
+    }
+}
+
+impl fmt::Debug for DragData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
@@ -1,21 +1,127 @@
-use std::fmt;
+use std::{fmt, io};
 
-use crate::imp;
+use crate::{imp, ConfigError};
 
 /// An error that might occur when interacting with the windowing system.
-pub struct Error(pub(crate) imp::Error);
+pub struct Error(Repr);
+
+/// The internal representation of an [`Error`].
+enum Repr {
+    /// An error reported by the platform.
+    Os(imp::Error),
+    /// The configuration of a window is invalid.
+    Config(ConfigError),
+}
+
+impl Error {
+    /// Creates an [`Error`] from an error reported by the platform.
+    #[inline(always)]
+    pub(crate) fn os(error: imp::Error) -> Self {
+        Self(Repr::Os(error))
+    }
+
+    /// Returns the problem with the configuration of a window, if the error has been caused by
+    /// one.
+    ///
+    /// Such errors are returned by [`Window::new`](crate::Window::new) before anything is
+    /// passed to the platform. Their [kind](Error::kind) is [`ErrorKind::InvalidArgument`].
+    #[inline]
+    pub fn config_error(&self) -> Option<ConfigError> {
+        match self.0 {
+            Repr::Config(error) => Some(error),
+            Repr::Os(_) => None,
+        }
+    }
+
+    /// Returns the category of the error.
+    ///
+    /// This can be used to react to some failures without inspecting platform-specific error
+    /// codes.
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        match self.0 {
+            Repr::Os(error) => error.kind(),
+            Repr::Config(_) => ErrorKind::InvalidArgument,
+        }
+    }
+
+    /// Returns the platform-specific error code of the error, if any.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** this is either a Win32 error code, as returned by `GetLastError`, or an
+    ///   `HRESULT` that does not wrap one.
+    #[inline]
+    pub fn raw_os_error(&self) -> Option<i32> {
+        match self.0 {
+            Repr::Os(error) => error.raw_os_error(),
+            Repr::Config(_) => None,
+        }
+    }
+}
+
+impl From<ConfigError> for Error {
+    #[inline]
+    fn from(error: ConfigError) -> Self {
+        Self(Repr::Config(error))
+    }
+}
 
 impl fmt::Debug for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(&self.0, f)
+        match &self.0 {
+            Repr::Os(error) => fmt::Debug::fmt(error, f),
+            Repr::Config(error) => fmt::Debug::fmt(error, f),
+        }
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+        match &self.0 {
+            Repr::Os(error) => fmt::Display::fmt(error, f),
+            Repr::Config(error) => write!(f, "invalid window configuration: {error}"),
+        }
     }
 }
 
 impl std::error::Error for Error {}
+
+/// Converts the error using its [platform-specific code](Error::raw_os_error), such that the
+/// message and kind of the resulting [`io::Error`] are determined by the standard library.
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        match err.raw_os_error() {
+            Some(code) => io::Error::from_raw_os_error(code),
+            None => io::Error::other(err),
+        }
+    }
+}
+
+/// The category of an [`Error`], returned by [`Error::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The operation is not supported by the platform, or by the running version of it.
+    NotSupported,
+    /// The application is not allowed to perform the operation.
+    PermissionDenied,
+    /// An argument of the operation is invalid, such as a handle to an object that no longer
+    /// exists.
+    InvalidArgument,
+    /// The system ran out of memory or of another resource needed by the operation.
+    OutOfResources,
+    /// An object the operation refers to does not exist.
+    NotFound,
+    /// An object the operation would create already exists, such as a global hotkey registered
+    /// by another application.
+    AlreadyExists,
+    /// The operation did not complete in time.
+    TimedOut,
+    /// An error of the operating system that does not belong to any other category.
+    ///
+    /// Errors that belong to this category may be moved to a more specific one in future
+    /// versions.
+    Os,
+}
@@ -1,8 +1,21 @@
 use std::fmt;
+use std::str::FromStr;
 
-use crate::imp;
+use crate::{
+    imp, DragData, KeyboardLayout, PhysicalPosition, PhysicalSize, TrayIconEvent, TrayIconId,
+};
 
 /// An event received from the windowing system.
+///
+/// With the `serde` feature, events can be serialized, for example to record them in a log.
+/// The [`DragData`] of drag-and-drop events is not serialized, and events cannot be
+/// deserialized.
+///
+/// New events may be added in future versions, so matching on an [`Event`] requires a wildcard
+/// arm.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
 pub enum Event {
     /// The user requested the window to close itself.
     ///
@@ -16,39 +29,146 @@ pub enum Event {
 
     /// The window has been resized.
     ///
-    /// If the window is now minimized, the new width and height of the window will be 0.
-    Resized {
-        /// The new width of the window, in pixels.
-        width: u32,
-        /// The new height of the window, in pixels.
-        height: u32,
-    },
+    /// This is the new size of the client area of the window. If the window is now minimized,
+    /// the new width and height of the window will be 0.
+    Resized(PhysicalSize),
 
     /// The window has been moved.
     ///
     /// If the window is now minimized, it is unspecified whether a [`Moved`] event will be
-    /// generated. If it is, the new X and Y position of the window is unspecified as well.
+    /// generated. If it is, the new position of the window is unspecified as well.
     ///
     /// [`Moved`]: Event::Moved
-    Moved {
-        /// The new X position of the window, in pixels.
-        x: i32,
-        /// The new Y position of the window, in pixels.
-        y: i32,
-    },
+    Moved(PhysicalPosition),
+
+    /// The scale factor of the window has changed.
+    ///
+    /// This happens when the window is moved to a monitor with a different DPI, or when the
+    /// user changes the scale of the monitor. This is the new value returned by
+    /// [`Window::scale_factor`].
+    ///
+    /// The system resizes the window to keep its physical size on the new monitor, such that
+    /// this event is followed by a [`Resized`] event.
+    ///
+    /// [`Window::scale_factor`]: crate::Window::scale_factor
+    /// [`Resized`]: Event::Resized
+    ScaleFactorChanged(f64),
 
     /// The cursor has been moved over the window.
     ///
-    /// This event is only generated when the cursor is over the window. It cannot be used to track
-    /// the mouse position outside of the window.
-    CursorMoved {
-        /// The new X position of the cursor, in pixels.
-        x: i32,
-        /// The new Y position of the cursor, in pixels.
-        y: i32,
+    /// This event is only generated when the cursor is over the window, or when the window has
+    /// captured the mouse (see [`Window::set_mouse_capture`]). In the latter case, the
+    /// position may be outside of the client area, including negative values.
+    ///
+    /// The position is relative to the top-left corner of the client area of the window.
+    ///
+    /// [`Window::set_mouse_capture`]: crate::Window::set_mouse_capture
+    CursorMoved(PhysicalPosition),
+
+    /// Some data is being dragged over the window.
+    ///
+    /// This event starts a drag-and-drop operation, which ends with either
+    /// [`DragLeft`](Event::DragLeft) or [`Dropped`](Event::Dropped). The effect of the drop can
+    /// be chosen with [`Window::set_drop_effect`] depending on the data.
+    ///
+    /// [`Window::set_drop_effect`]: crate::Window::set_drop_effect
+    DragEntered {
+        /// The position of the cursor, relative to the client area of the window.
+        position: PhysicalPosition,
+        /// The data being dragged.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        data: DragData,
+    },
+
+    /// The data being dragged over the window has moved.
+    ///
+    /// This is sent repeatedly during the operation, even when the cursor does not move, such
+    /// that the position can be used to choose the effect of the drop.
+    DragMoved {
+        /// The position of the cursor, relative to the client area of the window.
+        position: PhysicalPosition,
+    },
+
+    /// The data being dragged has left the window, or the operation has been canceled.
+    DragLeft,
+
+    /// The data being dragged has been dropped on the window.
+    ///
+    /// This is not sent when the effect of the drop is [`DropEffect::None`].
+    ///
+    /// [`DropEffect::None`]: crate::DropEffect::None
+    Dropped {
+        /// The position of the cursor, relative to the client area of the window.
+        position: PhysicalPosition,
+        /// The data that has been dropped.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        data: DragData,
+        /// The effect of the drop, which the source of the data applies once it knows it.
+        effect: crate::DropEffect,
     },
 
+    /// The window has lost the mouse capture.
+    ///
+    /// This event is sent when a capture started with [`Window::set_mouse_capture`] ends, whether
+    /// it has been released by the application or taken away by the system.
+    ///
+    /// [`Window::set_mouse_capture`]: crate::Window::set_mouse_capture
+    MouseCaptureLost,
+
+    /// A global hotkey registered with [`Window::register_hotkey`] has been pressed.
+    ///
+    /// [`Window::register_hotkey`]: crate::Window::register_hotkey
+    Hotkey(HotkeyId),
+
+    /// A [`TrayIcon`] created with the window has been interacted with.
+    ///
+    /// [`TrayIcon`]: crate::TrayIcon
+    TrayIcon {
+        /// The icon that has been interacted with.
+        id: TrayIconId,
+        /// What happened to the icon.
+        event: TrayIconEvent,
+        /// The position of the icon, in screen coordinates.
+        ///
+        /// This is typically used to position a menu next to the icon.
+        position: PhysicalPosition,
+    },
+
+    /// Another instance of the application has forwarded its command-line arguments to this
+    /// one with [`forward_to_running_instance`].
+    ///
+    /// This happens when an entry of the [`JumpList`] of the application is activated while it
+    /// is already running. The window is allowed to bring itself to the foreground. Only the
+    /// windows created with an [`instance_name`](crate::WindowAttributes::instance_name) receive
+    /// this event.
+    ///
+    /// Any other process of the user may send these arguments, which must therefore be treated
+    /// as untrusted input.
+    ///
+    /// [`forward_to_running_instance`]: crate::forward_to_running_instance
+    /// [`JumpList`]: crate::JumpList
+    InstanceActivated {
+        /// The command-line arguments of the other instance, without the path of the program.
+        arguments: Vec<String>,
+    },
+
+    /// The keyboard layout used to interpret the keyboard input of the window has changed.
+    ///
+    /// Applications may use this to adapt their default keybindings, or to refresh the names of
+    /// keys displayed to the user.
+    KeyboardLayoutChanged(KeyboardLayout),
+
+    /// The set of lock keys that are toggled on has changed.
+    ///
+    /// This event is also sent when the window gains the focus, if the lock keys have been
+    /// toggled while it did not have it.
+    LockKeysChanged(LockKeys),
+
     /// A character has been entered.
+    ///
+    /// Dead keys produce no character on their own. They are composed with the following key
+    /// (e.g. `^` followed by `e` produces a single `ê`), and when the two cannot be composed,
+    /// the character of the dead key is entered before the one of the following key.
     Text(char),
 
     /// A mouse has been moved.
@@ -87,6 +207,9 @@ pub enum Event {
     },
 
     /// A keyboard key has been pressed or released.
+    ///
+    /// On layouts with an **ALTGR** key, pressing it is reported as [`Key::RightAlt`] alone,
+    /// without the [`Key::LeftControl`] key that the system emulates along with it.
     KeyboardKey {
         /// The device that generated the event.
         device: Device,
@@ -96,6 +219,19 @@ pub enum Event {
         key: Option<Key>,
         /// A unique code that identifies the key that has been pressed or released.
         code: KeyCode,
+        /// The platform-specific virtual-key code of the key.
+        ///
+        /// # Platform-specific
+        ///
+        /// - **Windows:** this is one of the `VK_*` constants.
+        virtual_key: u32,
+        /// The character the key produces according to the active keyboard layout, ignoring
+        /// the modifiers currently held.
+        ///
+        /// This is typically used to implement menu accelerators such as **CTRL+S**, for which
+        /// [`Event::Text`] events are not produced. Dead keys produce their own character. If the
+        /// key does not produce any character, this is `None`.
+        key_without_modifiers: Option<char>,
         /// Whether the key is now pressed.
         ///
         /// If `true`, the key is now pressed. If `false`, the key is now released.
@@ -103,8 +239,106 @@ pub enum Event {
     },
 }
 
+bitflags::bitflags! {
+    /// A set of device classes from which raw input events are received.
+    ///
+    /// Raw input is what produces the [`Event::MouseMoved`], [`Event::MouseWheel`],
+    /// [`Event::MouseButton`] and [`Event::KeyboardKey`] events. Applications that only need the
+    /// position of the cursor and text input may disable it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct RawInputDevices: u8 {
+        /// Keyboards, producing [`Event::KeyboardKey`] events.
+        const KEYBOARD = 1 << 0;
+        /// Mice, producing [`Event::MouseMoved`], [`Event::MouseWheel`] and
+        /// [`Event::MouseButton`] events.
+        const MOUSE = 1 << 1;
+    }
+}
+
+impl Default for RawInputDevices {
+    #[inline]
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// The source from which a window produces its input events.
+///
+/// This determines how the [`Event::MouseMoved`], [`Event::MouseWheel`],
+/// [`Event::MouseButton`] and [`Event::KeyboardKey`] events are produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputBackend {
+    /// Input events are produced from raw input, for the device classes selected with
+    /// [`Window::set_raw_input_devices`].
+    ///
+    /// Events are received even when the window does not have the focus, and identify the
+    /// device that generated them.
+    ///
+    /// [`Window::set_raw_input_devices`]: crate::Window::set_raw_input_devices
+    #[default]
+    RawInput,
+    /// Input events are produced from the standard window messages.
+    ///
+    /// Keyboard events are only received while the window has the focus, and mouse events
+    /// while the cursor is over it or the mouse is captured. [`Event::MouseMoved`] events are
+    /// not produced, except in relative mouse mode. Events do not identify the device that
+    /// generated them.
+    ///
+    /// This is typically more reliable under remote desktop sessions, where raw input does not
+    /// always behave as expected.
+    WindowMessages,
+    /// [`Event::MouseMoved`] events are produced from raw input, and the other input events
+    /// from the standard window messages.
+    ///
+    /// Mouse motion is only received when the mouse device class is selected with
+    /// [`Window::set_raw_input_devices`].
+    ///
+    /// [`Window::set_raw_input_devices`]: crate::Window::set_raw_input_devices
+    Both,
+}
+
+bitflags::bitflags! {
+    /// A set of modifier keys.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Modifiers: u8 {
+        /// Either **SHIFT** key.
+        const SHIFT = 1 << 0;
+        /// Either **CONTROL** key.
+        const CONTROL = 1 << 1;
+        /// Either **ALT** key.
+        const ALT = 1 << 2;
+        /// Either **META** key, also known as the Windows key.
+        const META = 1 << 3;
+    }
+}
+
+bitflags::bitflags! {
+    /// A set of lock keys that are toggled on.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct LockKeys: u8 {
+        /// The **CAPS LOCK** key.
+        const CAPS_LOCK = 1 << 0;
+        /// The **NUM LOCK** key.
+        const NUM_LOCK = 1 << 1;
+        /// The **SCROLL LOCK** key.
+        const SCROLL_LOCK = 1 << 2;
+    }
+}
+
+/// Identifies a global hotkey registered with [`Window::register_hotkey`].
+///
+/// [`Window::register_hotkey`]: crate::Window::register_hotkey
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HotkeyId(pub(crate) u16);
+
 /// An external human interface device.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Device(pub(crate) imp::Device);
 
 impl fmt::Debug for Device {
@@ -128,6 +362,44 @@ impl MouseButton {
     pub const RIGHT: Self = Self(2);
 }
 
+/// Formats the button as `Left`, `Middle`, `Right`, or `Button{n}` for other buttons.
+impl fmt::Display for MouseButton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LEFT => f.pad("Left"),
+            Self::MIDDLE => f.pad("Middle"),
+            Self::RIGHT => f.pad("Right"),
+            Self(num) => write!(f, "Button{num}"),
+        }
+    }
+}
+
+impl FromStr for MouseButton {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Left" => Ok(Self::LEFT),
+            "Middle" => Ok(Self::MIDDLE),
+            "Right" => Ok(Self::RIGHT),
+            _ => {
+                let num = s.strip_prefix("Button").ok_or(ParseInputError)?;
+
+                // Leading signs and zeros would make the representation ambiguous.
+                if num.starts_with(['+', '0']) {
+                    return Err(ParseInputError);
+                }
+
+                // The named buttons are only accepted under their name.
+                match num.parse() {
+                    Ok(num) if num > 2 => Ok(Self(num)),
+                    _ => Err(ParseInputError),
+                }
+            }
+        }
+    }
+}
+
 impl fmt::Debug for MouseButton {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
@@ -371,6 +643,121 @@ pub enum Key {
     NumpadEnter,
 }
 
+/// Defines [`Key::ALL`] and [`Key::name`] from the list of the variants of [`Key`].
+///
+/// As the `match` of [`Key::name`] is exhaustive, a variant cannot be added to [`Key`] without
+/// being added to this list, and therefore to [`Key::ALL`].
+macro_rules! key_list {
+    ($($key:ident,)*) => {
+        impl Key {
+            /// All the keys, in the order of their declaration.
+            pub const ALL: [Self; [$(Self::$key),*].len()] = [$(Self::$key),*];
+
+            /// Returns the name of the key.
+            ///
+            /// The name of a key is the name of its variant (e.g. `"LeftShift"`), and is
+            /// guaranteed to remain stable. It is what the [`Display`](fmt::Display) and
+            /// [`FromStr`] implementations of [`Key`] use.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$key => stringify!($key),)*
+                }
+            }
+        }
+    };
+}
+
+key_list! {
+    Backspace, Tab, Enter, Escape, Space, PageUp, PageDown, End, Home, Left, Up, Right, Down,
+    Insert, Delete, Zero, One, Two, Three, Four, Five, Six, Seven, Eight, Nine, A, B, C, D, E, F,
+    G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, F1, F2, F3, F4, F5, F6, F7, F8, F9,
+    F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24, NumLock, ScrollLock,
+    LeftShift, RightShift, LeftControl, RightControl, LeftAlt, RightAlt, LeftMeta, RightMeta, Menu,
+    PrintScreen, Pause, CapsLock, VolumeUp, VolumeDown, VolumeMute, MediaPlayPause, MediaStop,
+    MediaPrevious, MediaNext, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6,
+    Numpad7, Numpad8, Numpad9, NumpadDecimal, NumpadAdd, NumpadSubtract, NumpadMultiply,
+    NumpadDivide, NumpadEnter,
+}
+
+impl Key {
+    /// Returns the [`KeyCode`] of the physical key that currently produces this key, according
+    /// to the active keyboard layout.
+    ///
+    /// If no key of the keyboard produces this key, `None` is returned.
+    #[inline(always)]
+    pub fn to_key_code(self) -> Option<KeyCode> {
+        imp::KeyCode::from_key(self).map(KeyCode)
+    }
+
+    /// Returns the human-readable name of the key, localized according to the active keyboard
+    /// layout.
+    ///
+    /// This is the name of the physical key that produces this key. See
+    /// [`KeyCode::display_name`].
+    #[inline]
+    pub fn display_name(self) -> Option<String> {
+        self.to_key_code()?.display_name()
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Key {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|key| key.name() == s)
+            .ok_or(ParseInputError)
+    }
+}
+
+/// A snapshot of the keys that are currently pressed.
+///
+/// See [`Window::keyboard_state`](crate::Window::keyboard_state).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KeyboardState {
+    /// A bit set indexed by the discriminant of [`Key`].
+    pressed: [u64; 4],
+}
+
+impl KeyboardState {
+    /// Returns whether the provided key is pressed.
+    #[inline]
+    pub fn is_pressed(&self, key: Key) -> bool {
+        let index = key as usize;
+        self.pressed[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Returns whether no key is pressed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pressed == [0; 4]
+    }
+
+    /// Marks the provided key as pressed or released.
+    #[inline]
+    pub(crate) fn set_pressed(&mut self, key: Key, pressed: bool) {
+        let index = key as usize;
+        if pressed {
+            self.pressed[index / 64] |= 1 << (index % 64);
+        } else {
+            self.pressed[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    /// Marks all keys as released.
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.pressed = [0; 4];
+    }
+}
+
 /// A unique code that identifies a keyboard key.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct KeyCode(pub(crate) imp::KeyCode);
@@ -380,6 +767,25 @@ impl KeyCode {
     pub const fn from_code(code: u8) -> Self {
         Self(imp::KeyCode::from_code(code))
     }
+
+    /// Returns the [`Key`] that this physical key currently produces, according to the active
+    /// keyboard layout.
+    ///
+    /// This is typically used to display keybindings that are stored as [`KeyCode`]s.
+    #[inline(always)]
+    pub fn to_key(self) -> Option<Key> {
+        self.0.to_key()
+    }
+
+    /// Returns the human-readable name of the physical key, localized according to the active
+    /// keyboard layout.
+    ///
+    /// For example, the left shift key is named "Shift" on an English layout, and "Maj" on a
+    /// French one. If the system does not know the name of the key, `None` is returned.
+    #[inline(always)]
+    pub fn display_name(self) -> Option<String> {
+        self.0.display_name()
+    }
 }
 
 impl fmt::Debug for KeyCode {
@@ -387,3 +793,124 @@ impl fmt::Debug for KeyCode {
         fmt::Debug::fmt(&self.0, f)
     }
 }
+
+/// Formats the key code as a hexadecimal scan code, such as `0x1E`.
+///
+/// # Platform-specific
+///
+/// - **Windows:** prefixed scan codes include their prefix, such as `0xE01D` for the right
+///   control key. Codes that do not fit in a byte are formatted with six digits: the extended
+///   flags of the key, followed by its 16-bit code.
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for KeyCode {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        imp::KeyCode::parse(s).map(Self).ok_or(ParseInputError)
+    }
+}
+
+/// An error returned when parsing a [`Key`], a [`KeyCode`] or a [`MouseButton`] from a string
+/// fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseInputError;
+
+impl fmt::Display for ParseInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("invalid input identifier")
+    }
+}
+
+impl std::error::Error for ParseInputError {}
+
+/// Implements `Serialize` and `Deserialize` for the provided types using their
+/// [`Display`](fmt::Display) and [`FromStr`] implementations.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_with_str {
+    ($($ty:ty),*) => {
+        $(
+            impl serde::Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    struct Visitor;
+
+                    impl<'de> serde::de::Visitor<'de> for Visitor {
+                        type Value = $ty;
+
+                        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                            f.write_str(concat!("a ", stringify!($ty), " name"))
+                        }
+
+                        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<$ty, E> {
+                            v.parse()
+                                .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+                        }
+                    }
+
+                    deserializer.deserialize_str(Visitor)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_serde_with_str!(Key, KeyCode, MouseButton);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_listed_in_declaration_order() {
+        for (index, key) in Key::ALL.into_iter().enumerate() {
+            assert_eq!(key as usize, index + 1);
+        }
+    }
+
+    #[test]
+    fn key_round_trip() {
+        for key in Key::ALL {
+            assert_eq!(key.to_string().parse(), Ok(key));
+        }
+        assert_eq!("leftshift".parse::<Key>(), Err(ParseInputError));
+    }
+
+    #[test]
+    fn mouse_button_round_trip() {
+        for button in (0..=u8::MAX).map(MouseButton) {
+            assert_eq!(button.to_string().parse(), Ok(button));
+        }
+    }
+
+    #[test]
+    fn mouse_button_aliases() {
+        for s in [
+            "Button0",
+            "Button1",
+            "Button2",
+            "Button03",
+            "Button+3",
+            "Button256",
+            "left",
+        ] {
+            assert_eq!(s.parse::<MouseButton>(), Err(ParseInputError), "{s}");
+        }
+    }
+}
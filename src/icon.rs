@@ -0,0 +1,49 @@
+use std::fmt;
+
+use crate::{imp, Error};
+
+/// An icon that can be displayed by the windowing system.
+///
+/// Cloning an [`Icon`] is cheap, as the underlying image is shared.
+#[derive(Clone)]
+pub struct Icon(pub(crate) imp::Icon);
+
+impl Icon {
+    /// Creates a new [`Icon`] from raw pixels.
+    ///
+    /// The pixels are expected to be in the RGBA format, with 8 bits per channel, row by row,
+    /// starting from the top-left corner of the image.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`ErrorKind::InvalidArgument`](crate::ErrorKind::InvalidArgument)
+    /// if the length of `rgba` is not `width * height * 4`.
+    pub fn from_rgba(rgba: &[u8], width: u32, height: u32) -> Result<Self, Error> {
+        match imp::Icon::from_rgba(rgba, width, height) {
+            Ok(icon) => Ok(Self(icon)),
+            Err(error) => Err(Error::os(error)),
+        }
+    }
+
+    /// Loads an icon embedded as a resource in the executable.
+    ///
+    /// `id` is the numeric identifier of the icon resource, typically chosen when compiling the
+    /// resources of the executable from a build script.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** the icon is loaded at the default icon size of the system. The resource
+    ///   may contain multiple images, in which case the one closest to that size is used.
+    pub fn from_resource(id: u16) -> Result<Self, Error> {
+        match imp::Icon::from_resource(id) {
+            Ok(icon) => Ok(Self(icon)),
+            Err(error) => Err(Error::os(error)),
+        }
+    }
+}
+
+impl fmt::Debug for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
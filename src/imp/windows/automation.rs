@@ -0,0 +1,147 @@
+use std::mem::size_of;
+
+use windows_sys::Win32::Foundation::{ERROR_INVALID_PARAMETER, HWND};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::*;
+use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+use super::{Error, KeyCode, PAUSE_CODE};
+
+/// Synthesizes the provided input events, as if they came from a physical device.
+fn send_input(inputs: &[INPUT]) -> Result<(), Error> {
+    let ret = unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_ptr(),
+            size_of::<INPUT>() as i32,
+        )
+    };
+
+    // The function returns the number of events that were inserted, which is only lower than
+    // expected when the input was blocked.
+    if ret as usize != inputs.len() {
+        Err(Error::last())
+    } else {
+        Ok(())
+    }
+}
+
+/// Synthesizes a mouse event.
+fn send_mouse_input(dx: i32, dy: i32, data: i32, flags: MOUSE_EVENT_FLAGS) -> Result<(), Error> {
+    send_input(&[INPUT {
+        r#type: INPUT_MOUSE,
+        Anonymous: INPUT_0 {
+            mi: MOUSEINPUT {
+                dx,
+                dy,
+                mouseData: data,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }])
+}
+
+/// See [`crate::Window::inject_key`].
+pub fn inject_key(code: KeyCode, pressed: bool) -> Result<(), Error> {
+    // The E1 prefix cannot be injected, and the scan code of the PAUSE key alone is the one of
+    // the left CONTROL key. Its virtual-key code is injected instead, along with the scan code
+    // the system reports for it.
+    let (vkey, scan, mut flags) = if code.extended_flags & 0x02 != 0 {
+        if code.code != PAUSE_CODE {
+            return Err(Error(ERROR_INVALID_PARAMETER));
+        }
+
+        (VK_PAUSE, 0x45, 0)
+    } else if code.extended_flags & 0x01 != 0 {
+        (0, code.code, KEYEVENTF_SCANCODE | KEYEVENTF_EXTENDEDKEY)
+    } else {
+        (0, code.code, KEYEVENTF_SCANCODE)
+    };
+
+    if !pressed {
+        flags |= KEYEVENTF_KEYUP;
+    }
+
+    send_input(&[INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: vkey,
+                wScan: scan,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }])
+}
+
+/// See [`crate::Window::inject_mouse_button`].
+pub fn inject_mouse_button(button: crate::MouseButton, pressed: bool) -> Result<(), Error> {
+    let (flags, data) = match (button, pressed) {
+        (crate::MouseButton::LEFT, true) => (MOUSEEVENTF_LEFTDOWN, 0),
+        (crate::MouseButton::LEFT, false) => (MOUSEEVENTF_LEFTUP, 0),
+        (crate::MouseButton::MIDDLE, true) => (MOUSEEVENTF_MIDDLEDOWN, 0),
+        (crate::MouseButton::MIDDLE, false) => (MOUSEEVENTF_MIDDLEUP, 0),
+        (crate::MouseButton::RIGHT, true) => (MOUSEEVENTF_RIGHTDOWN, 0),
+        (crate::MouseButton::RIGHT, false) => (MOUSEEVENTF_RIGHTUP, 0),
+        (crate::MouseButton(4), true) => (MOUSEEVENTF_XDOWN, XBUTTON1),
+        (crate::MouseButton(4), false) => (MOUSEEVENTF_XUP, XBUTTON1),
+        (crate::MouseButton(5), true) => (MOUSEEVENTF_XDOWN, XBUTTON2),
+        (crate::MouseButton(5), false) => (MOUSEEVENTF_XUP, XBUTTON2),
+        _ => return Err(Error(ERROR_INVALID_PARAMETER)),
+    };
+
+    send_mouse_input(0, 0, data as i32, flags)
+}
+
+/// Moves the cursor to the provided position, in screen coordinates.
+///
+/// Unlike `SetCursorPos`, this goes through the input pipeline of the system.
+pub fn inject_cursor_position(x: i32, y: i32) -> Result<(), Error> {
+    let (left, top, width, height) = unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    };
+
+    // Absolute coordinates are normalized to the range 0..=65535 over the virtual screen.
+    let normalize = |value: i32, origin: i32, size: i32| {
+        ((value - origin) as i64 * 65535 / (size - 1).max(1) as i64) as i32
+    };
+
+    send_mouse_input(
+        normalize(x, left, width),
+        normalize(y, top, height),
+        0,
+        MOUSEEVENTF_MOVE | MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_VIRTUALDESK,
+    )
+}
+
+/// See [`crate::Window::inject_mouse_wheel`].
+pub fn inject_mouse_wheel(dx: f64, dy: f64) -> Result<(), Error> {
+    if dy != 0.0 {
+        send_mouse_input(0, 0, (dy * WHEEL_DELTA as f64) as i32, MOUSEEVENTF_WHEEL)?;
+    }
+
+    if dx != 0.0 {
+        send_mouse_input(0, 0, (dx * WHEEL_DELTA as f64) as i32, MOUSEEVENTF_HWHEEL)?;
+    }
+
+    Ok(())
+}
+
+/// Posts `WM_CHAR` messages to the provided window for each UTF-16 code unit of `text`.
+pub fn post_text(hwnd: HWND, text: &str) -> Result<(), Error> {
+    for unit in text.encode_utf16() {
+        if unsafe { PostMessageW(hwnd, WM_CHAR, unit as usize, 1) } == 0 {
+            return Err(Error::last());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,637 @@
+use std::ffi::OsString;
+use std::mem::size_of;
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use windows_sys::Win32::Foundation::{HANDLE, HWND};
+use windows_sys::Win32::Graphics::Gdi::{BITMAPV5HEADER, BI_BITFIELDS, BI_RGB};
+use windows_sys::Win32::System::DataExchange::*;
+use windows_sys::Win32::System::Memory::*;
+use windows_sys::Win32::System::Ole::{CF_DIB, CF_DIBV5, CF_HDROP, CF_UNICODETEXT};
+
+use super::hwnd::make_utf16;
+use super::Error;
+use crate::{ClipboardFormat, ClipboardImage};
+
+/// The `LCS_sRGB` color space, which is missing from `windows-sys`.
+const LCS_SRGB: u32 = u32::from_be_bytes(*b"sRGB");
+
+/// The formats offered by the process with delayed rendering, since it last emptied the
+/// clipboard.
+///
+/// The clipboard is shared by the whole session, such that a single list is needed.
+static OFFERED_FORMATS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+/// The [`crate::Clipboard`] implementation for Windows.
+#[derive(Debug)]
+pub struct Clipboard {
+    /// The window that owns the clipboard once its contents are set.
+    owner: HWND,
+    /// Whether the previous contents of the clipboard have been discarded.
+    emptied: bool,
+    /// Whether the clipboard has been opened by this instance, and must be closed when it is
+    /// dropped.
+    opened: bool,
+}
+
+impl Clipboard {
+    /// See [`crate::Clipboard::open`].
+    pub fn open(owner: HWND) -> Result<Self, Error> {
+        if unsafe { OpenClipboard(owner) } == 0 {
+            return Err(Error::last());
+        }
+
+        Ok(Self {
+            owner,
+            emptied: false,
+            opened: true,
+        })
+    }
+
+    /// Returns a [`Clipboard`] used to provide contents requested from a window that has offered
+    /// them.
+    ///
+    /// The system expects the data to be set while processing `WM_RENDERFORMAT`, without
+    /// opening the clipboard.
+    pub fn for_rendering(owner: HWND) -> Self {
+        Self {
+            owner,
+            emptied: true,
+            opened: false,
+        }
+    }
+
+    /// See [`crate::Clipboard::clear`].
+    pub fn clear(&mut self) -> Result<(), Error> {
+        if unsafe { EmptyClipboard() } == 0 {
+            return Err(Error::last());
+        }
+
+        forget_offered_formats();
+        self.emptied = true;
+        Ok(())
+    }
+
+    /// Returns whether data is available in the provided format.
+    fn has_format(&self, format: u32) -> bool {
+        unsafe { IsClipboardFormatAvailable(format) != 0 }
+    }
+
+    /// Returns a copy of the data stored in the provided format.
+    fn get(&self, format: u32) -> Option<Vec<u8>> {
+        let handle = unsafe { GetClipboardData(format) };
+        if handle == 0 {
+            return None;
+        }
+
+        unsafe { read_global(handle) }
+    }
+
+    /// Places the provided data on the clipboard, in the provided format.
+    ///
+    /// The previous contents of the clipboard are discarded the first time this is called.
+    fn set(&mut self, format: u32, data: &[u8]) -> Result<(), Error> {
+        if !self.emptied {
+            self.clear()?;
+        }
+
+        let handle = alloc_global(data)?;
+
+        // The system takes ownership of the memory only if the call succeeds.
+        if unsafe { SetClipboardData(format, handle) } == 0 {
+            let err = Error::last();
+            unsafe { GlobalFree(handle) };
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// See [`crate::Clipboard::text`].
+    pub fn text(&self) -> Option<String> {
+        Some(decode_text(&self.get(CF_UNICODETEXT as u32)?))
+    }
+
+    /// See [`crate::Clipboard::set_text`].
+    pub fn set_text(&mut self, text: &str) -> Result<(), Error> {
+        let data: Vec<u8> = make_utf16(text)
+            .into_iter()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+
+        self.set(CF_UNICODETEXT as u32, &data)
+    }
+
+    /// See [`crate::Clipboard::image`].
+    pub fn image(&self) -> Option<ClipboardImage> {
+        // The system converts between the two formats, but the conversion loses the alpha
+        // channel when the image was provided as `CF_DIB`.
+        let format = if self.has_format(CF_DIBV5 as u32) {
+            CF_DIBV5
+        } else {
+            CF_DIB
+        };
+
+        decode_dib(&self.get(format as u32)?)
+    }
+
+    /// See [`crate::Clipboard::set_image`].
+    pub fn set_image(&mut self, image: &ClipboardImage) -> Result<(), Error> {
+        self.set(CF_DIBV5 as u32, &encode_dib(image))
+    }
+
+    /// See [`crate::Clipboard::png`].
+    pub fn png(&self) -> Option<Vec<u8>> {
+        self.get(register_format("PNG").ok()?)
+    }
+
+    /// See [`crate::Clipboard::set_png`].
+    pub fn set_png(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.set(register_format("PNG")?, data)
+    }
+
+    /// See [`crate::Clipboard::files`].
+    pub fn files(&self) -> Option<Vec<PathBuf>> {
+        decode_drop_files(&self.get(CF_HDROP as u32)?)
+    }
+
+    /// See [`crate::Clipboard::set_files`].
+    pub fn set_files(&mut self, paths: &[&Path]) -> Result<(), Error> {
+        self.set(CF_HDROP as u32, &encode_drop_files(paths))
+    }
+
+    /// See [`crate::Clipboard::html`].
+    pub fn html(&self) -> Option<String> {
+        let data = self.get(register_format("HTML Format").ok()?)?;
+        decode_html(&data)
+    }
+
+    /// See [`crate::Clipboard::set_html`].
+    pub fn set_html(&mut self, html: &str) -> Result<(), Error> {
+        self.set(register_format("HTML Format")?, &encode_html(html))
+    }
+
+    /// See [`crate::Clipboard::data`].
+    pub fn data(&self, format: &str) -> Option<Vec<u8>> {
+        self.get(register_format(format).ok()?)
+    }
+
+    /// See [`crate::Clipboard::set_data`].
+    pub fn set_data(&mut self, format: &str, data: &[u8]) -> Result<(), Error> {
+        self.set(register_format(format)?, data)
+    }
+
+    /// See [`crate::Clipboard::offer`].
+    pub fn offer(&mut self, format: &ClipboardFormat) -> Result<(), Error> {
+        use windows_sys::Win32::Foundation::ERROR_INVALID_WINDOW_HANDLE;
+
+        // The data is requested from the owner of the clipboard.
+        if self.owner == 0 {
+            return Err(Error(ERROR_INVALID_WINDOW_HANDLE));
+        }
+
+        if !self.emptied {
+            self.clear()?;
+        }
+
+        let format = format_id(format)?;
+        if unsafe { SetClipboardData(format, 0) } == 0 {
+            return Err(Error::last());
+        }
+
+        let mut offered = OFFERED_FORMATS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        if !offered.contains(&format) {
+            offered.push(format);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Clipboard {
+    fn drop(&mut self) {
+        if self.opened {
+            unsafe { CloseClipboard() };
+        }
+    }
+}
+
+/// Returns the identifier of the provided clipboard format.
+fn format_id(format: &ClipboardFormat) -> Result<u32, Error> {
+    match format {
+        ClipboardFormat::Text => Ok(CF_UNICODETEXT as u32),
+        ClipboardFormat::Image => Ok(CF_DIBV5 as u32),
+        ClipboardFormat::Png => register_format("PNG"),
+        ClipboardFormat::Files => Ok(CF_HDROP as u32),
+        ClipboardFormat::Html => register_format("HTML Format"),
+        ClipboardFormat::Custom(name) => register_format(name),
+    }
+}
+
+/// Returns the clipboard format with the provided identifier.
+///
+/// `None` is returned for the predefined formats that are not exposed.
+pub fn format_from_id(id: u32) -> Option<ClipboardFormat> {
+    match id {
+        _ if id == CF_UNICODETEXT as u32 => return Some(ClipboardFormat::Text),
+        _ if id == CF_DIBV5 as u32 => return Some(ClipboardFormat::Image),
+        _ if id == CF_HDROP as u32 => return Some(ClipboardFormat::Files),
+        _ => (),
+    }
+
+    let mut buf = [0u16; 256];
+    let len = unsafe { GetClipboardFormatNameW(id, buf.as_mut_ptr(), buf.len() as i32) };
+    if len <= 0 {
+        return None;
+    }
+
+    let name = String::from_utf16_lossy(&buf[..len as usize]);
+    Some(match name.as_str() {
+        "PNG" => ClipboardFormat::Png,
+        "HTML Format" => ClipboardFormat::Html,
+        _ => ClipboardFormat::Custom(name),
+    })
+}
+
+/// Returns the formats offered by the process with delayed rendering since it last emptied the
+/// clipboard.
+pub fn offered_formats() -> Vec<u32> {
+    OFFERED_FORMATS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+}
+
+/// Forgets about the formats offered by the process, once the clipboard has been emptied.
+pub fn forget_offered_formats() {
+    OFFERED_FORMATS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clear();
+}
+
+/// Returns the identifier of the clipboard format with the provided name, registering it if
+/// needed.
+pub fn register_format(name: &str) -> Result<u32, Error> {
+    let name = make_utf16(name);
+    let format = unsafe { RegisterClipboardFormatW(name.as_ptr()) };
+
+    if format == 0 {
+        Err(Error::last())
+    } else {
+        Ok(format)
+    }
+}
+
+/// Returns a copy of the contents of a global memory object.
+///
+/// # Safety
+///
+/// `handle` must be a valid global memory object.
+pub unsafe fn read_global(handle: HANDLE) -> Option<Vec<u8>> {
+    unsafe {
+        let size = GlobalSize(handle);
+        let data = GlobalLock(handle) as *const u8;
+        if data.is_null() {
+            return None;
+        }
+
+        let ret = std::slice::from_raw_parts(data, size).to_vec();
+        GlobalUnlock(handle);
+        Some(ret)
+    }
+}
+
+/// Decodes a null-terminated UTF-16 string, as stored in the `CF_UNICODETEXT` format.
+pub fn decode_text(data: &[u8]) -> String {
+    let text: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+
+    String::from_utf16_lossy(&text)
+}
+
+/// Allocates a movable global memory object holding a copy of `data`.
+fn alloc_global(data: &[u8]) -> Result<HANDLE, Error> {
+    unsafe {
+        let handle = GlobalAlloc(GMEM_MOVEABLE, data.len());
+        if handle == 0 {
+            return Err(Error::last());
+        }
+
+        let ptr = GlobalLock(handle) as *mut u8;
+        if ptr.is_null() {
+            let err = Error::last();
+            GlobalFree(handle);
+            return Err(err);
+        }
+
+        std::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+        GlobalUnlock(handle);
+
+        Ok(handle)
+    }
+}
+
+/// Encodes the provided image as a bottom-up, 32-bit `BITMAPV5HEADER` device-independent bitmap.
+///
+/// Bottom-up bitmaps are the most widely supported by other applications.
+fn encode_dib(image: &ClipboardImage) -> Vec<u8> {
+    assert_eq!(
+        image.rgba.len(),
+        image.width as usize * image.height as usize * 4,
+        "the provided pixel buffer does not match the size of the image",
+    );
+
+    let mut header: BITMAPV5HEADER = unsafe { std::mem::zeroed() };
+    header.bV5Size = size_of::<BITMAPV5HEADER>() as u32;
+    header.bV5Width = image.width as i32;
+    header.bV5Height = image.height as i32;
+    header.bV5Planes = 1;
+    header.bV5BitCount = 32;
+    header.bV5Compression = BI_BITFIELDS;
+    header.bV5SizeImage = image.rgba.len() as u32;
+    header.bV5RedMask = 0x00FF_0000;
+    header.bV5GreenMask = 0x0000_FF00;
+    header.bV5BlueMask = 0x0000_00FF;
+    header.bV5AlphaMask = 0xFF00_0000;
+    header.bV5CSType = LCS_SRGB;
+
+    let mut data = Vec::with_capacity(size_of::<BITMAPV5HEADER>() + image.rgba.len());
+    data.extend_from_slice(unsafe {
+        std::slice::from_raw_parts(
+            &header as *const BITMAPV5HEADER as *const u8,
+            size_of::<BITMAPV5HEADER>(),
+        )
+    });
+
+    // Windows expects BGRA pixels rather than RGBA.
+    let stride = image.width as usize * 4;
+    for row in image.rgba.chunks_exact(stride.max(1)).rev() {
+        data.extend(row.chunks_exact(4).flat_map(|p| [p[2], p[1], p[0], p[3]]));
+    }
+
+    data
+}
+
+/// Decodes a device-independent bitmap, as stored in the `CF_DIB` and `CF_DIBV5` formats.
+///
+/// Only uncompressed 24-bit and 32-bit images are supported. The data may come from any
+/// process, such that invalid or truncated images are rejected.
+fn decode_dib(data: &[u8]) -> Option<ClipboardImage> {
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes = data.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+    };
+
+    let header_size = read_u32(0)? as usize;
+    let width = read_u32(4)? as i32;
+    let height = read_u32(8)? as i32;
+    let bit_count = u16::from_le_bytes(data.get(14..16)?.try_into().unwrap());
+    let compression = read_u32(16)? as i32;
+    let colors_used = read_u32(32)? as usize;
+
+    if width <= 0 || height == 0 || header_size < 40 {
+        return None;
+    }
+
+    // The masks of the channels are either part of the header, or follow a `BITMAPINFOHEADER`.
+    let (masks, offset) = match (compression, bit_count) {
+        (BI_RGB, 32) => (
+            [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0xFF00_0000],
+            header_size,
+        ),
+        (BI_RGB, 24) => ([0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0], header_size),
+        (BI_BITFIELDS, 32) if header_size == 40 => {
+            let masks = [read_u32(40)?, read_u32(44)?, read_u32(48)?, 0];
+            (masks, header_size.checked_add(12)?)
+        }
+        (BI_BITFIELDS, 32) => {
+            let alpha = if header_size >= 56 { read_u32(52)? } else { 0 };
+            let masks = [read_u32(40)?, read_u32(44)?, read_u32(48)?, alpha];
+            (masks, header_size)
+        }
+        _ => return None,
+    };
+
+    // Images with more than 8 bits per pixel may still come with a color table, which is only
+    // used to optimize their display on palette-based devices.
+    let offset = colors_used.checked_mul(4)?.checked_add(offset)?;
+
+    let width = width as usize;
+    let rows = height.unsigned_abs() as usize;
+    let bytes_per_pixel = bit_count as usize / 8;
+    let stride = width
+        .checked_mul(bytes_per_pixel)?
+        .checked_next_multiple_of(4)?;
+    let end = stride.checked_mul(rows)?.checked_add(offset)?;
+    let pixels = data.get(offset..end)?;
+
+    let mut rgba = Vec::with_capacity(width * rows * 4);
+    for y in 0..rows {
+        // Bitmaps are stored bottom-up unless their height is negative.
+        let row = if height > 0 { rows - 1 - y } else { y };
+        let row = &pixels[row * stride..][..width * bytes_per_pixel];
+
+        for pixel in row.chunks_exact(bytes_per_pixel) {
+            let mut value = [0u8; 4];
+            value[..bytes_per_pixel].copy_from_slice(pixel);
+            let value = u32::from_le_bytes(value);
+
+            rgba.extend(masks.map(|mask| extract_channel(value, mask)));
+        }
+    }
+
+    // Many applications leave the alpha channel of 32-bit images empty, even though the image
+    // is meant to be opaque.
+    if masks[3] == 0 || rgba.chunks_exact(4).all(|p| p[3] == 0) {
+        rgba.chunks_exact_mut(4).for_each(|p| p[3] = 255);
+    }
+
+    Some(ClipboardImage {
+        width: width as u32,
+        height: rows as u32,
+        rgba,
+    })
+}
+
+/// Extracts the channel selected by `mask` from the provided pixel, scaled to 8 bits.
+fn extract_channel(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+
+    let max = (mask >> mask.trailing_zeros()) as u64;
+    let value = ((pixel & mask) >> mask.trailing_zeros()) as u64;
+    (value * 255 / max) as u8
+}
+
+/// The size of the `DROPFILES` structure that starts the `CF_HDROP` format.
+const DROPFILES_SIZE: usize = 20;
+
+/// Encodes the provided paths in the `CF_HDROP` format.
+///
+/// The format is a `DROPFILES` structure followed by the null-terminated paths, the list
+/// itself being terminated by an empty path.
+fn encode_drop_files(paths: &[&Path]) -> Vec<u8> {
+    let mut data = vec![0u8; DROPFILES_SIZE];
+
+    // `pFiles`, the offset of the list of paths.
+    data[0..4].copy_from_slice(&(DROPFILES_SIZE as u32).to_le_bytes());
+    // `fWide`, whether the paths are UTF-16 strings.
+    data[16..20].copy_from_slice(&1u32.to_le_bytes());
+
+    for path in paths {
+        data.extend(path.as_os_str().encode_wide().flat_map(u16::to_le_bytes));
+        data.extend([0, 0]);
+    }
+    data.extend([0, 0]);
+
+    data
+}
+
+/// Decodes a list of paths stored in the `CF_HDROP` format.
+pub fn decode_drop_files(data: &[u8]) -> Option<Vec<PathBuf>> {
+    let offset = u32::from_le_bytes(data.get(0..4)?.try_into().unwrap()) as usize;
+    let wide = u32::from_le_bytes(data.get(16..20)?.try_into().unwrap()) != 0;
+    let list = data.get(offset..)?;
+
+    let paths = if wide {
+        let list: Vec<u16> = list
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+
+        list.split(|&c| c == 0)
+            .take_while(|path| !path.is_empty())
+            .map(|path| PathBuf::from(OsString::from_wide(path)))
+            .collect()
+    } else {
+        // Paths in the ANSI code page are very unlikely nowadays, and are assumed to be ASCII.
+        list.split(|&c| c == 0)
+            .take_while(|path| !path.is_empty())
+            .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+            .collect()
+    };
+
+    Some(paths)
+}
+
+/// The markers that delimit the fragment of a document in the HTML clipboard format.
+const START_FRAGMENT: &str = "<!--StartFragment-->";
+const END_FRAGMENT: &str = "<!--EndFragment-->";
+
+/// Encodes the provided HTML fragment in the HTML clipboard format.
+///
+/// The format is a header giving the byte offsets of the document and of the fragment,
+/// followed by a UTF-8 document wrapping the fragment.
+fn encode_html(fragment: &str) -> Vec<u8> {
+    // The offsets are padded to a fixed width so that the length of the header does not depend
+    // on them.
+    const HEADER: &str = "Version:0.9\r\n\
+        StartHTML:0000000000\r\n\
+        EndHTML:0000000000\r\n\
+        StartFragment:0000000000\r\n\
+        EndFragment:0000000000\r\n";
+
+    let start_html = HEADER.len();
+    let start_fragment = start_html + "<html><body>\r\n".len() + START_FRAGMENT.len();
+    let end_fragment = start_fragment + fragment.len();
+    let end_html = end_fragment + END_FRAGMENT.len() + "\r\n</body></html>".len();
+
+    let mut data = format!(
+        "Version:0.9\r\n\
+        StartHTML:{start_html:010}\r\n\
+        EndHTML:{end_html:010}\r\n\
+        StartFragment:{start_fragment:010}\r\n\
+        EndFragment:{end_fragment:010}\r\n\
+        <html><body>\r\n{START_FRAGMENT}{fragment}{END_FRAGMENT}\r\n</body></html>"
+    )
+    .into_bytes();
+    data.push(0);
+
+    data
+}
+
+/// Decodes the fragment of a document stored in the HTML clipboard format.
+fn decode_html(data: &[u8]) -> Option<String> {
+    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    let data = &data[..end];
+
+    // The header is made of ASCII `key:value` lines, and ends where the document starts.
+    let header = String::from_utf8_lossy(&data[..data.len().min(1024)]);
+    let offset = |key: &str| -> Option<usize> {
+        header
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|value| value.trim().parse().ok())
+    };
+
+    let (start, end) = match (offset("StartFragment"), offset("EndFragment")) {
+        (Some(start), Some(end)) => (start, end),
+        _ => (offset("StartHTML")?, offset("EndHTML")?),
+    };
+
+    let fragment = data.get(start..end.min(data.len()))?;
+    Some(String::from_utf8_lossy(fragment).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a bottom-up `BI_RGB` bitmap with a `BITMAPINFOHEADER`, followed by `pixels`.
+    fn dib(width: i32, height: i32, bit_count: u16, colors_used: u32, pixels: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(40u32.to_le_bytes());
+        data.extend(width.to_le_bytes());
+        data.extend(height.to_le_bytes());
+        data.extend(1u16.to_le_bytes());
+        data.extend(bit_count.to_le_bytes());
+        data.extend(BI_RGB.to_le_bytes());
+        data.extend([0; 12]);
+        data.extend(colors_used.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(pixels);
+        data
+    }
+
+    #[test]
+    fn decodes_24_bit_image() {
+        // Two rows of a single pixel, padded to 4 bytes, stored bottom-up.
+        let data = dib(1, 2, 24, 0, &[1, 2, 3, 0, 4, 5, 6, 0]);
+        let image = decode_dib(&data).unwrap();
+
+        assert_eq!((image.width, image.height), (1, 2));
+        assert_eq!(image.rgba, [6, 5, 4, 255, 3, 2, 1, 255]);
+    }
+
+    #[test]
+    fn rejects_truncated_header() {
+        let data = dib(1, 1, 32, 0, &[0; 4]);
+        assert!(decode_dib(&data[..20]).is_none());
+        assert!(decode_dib(&[]).is_none());
+    }
+
+    #[test]
+    fn rejects_truncated_pixels() {
+        let data = dib(2, 2, 32, 0, &[0; 12]);
+        assert!(decode_dib(&data).is_none());
+    }
+
+    #[test]
+    fn rejects_oversized_dimensions() {
+        assert!(decode_dib(&dib(i32::MAX, i32::MAX, 32, 0, &[0; 4])).is_none());
+        assert!(decode_dib(&dib(i32::MAX, i32::MIN, 24, 0, &[0; 4])).is_none());
+    }
+
+    #[test]
+    fn rejects_oversized_color_table() {
+        assert!(decode_dib(&dib(1, 1, 32, u32::MAX, &[0; 4])).is_none());
+    }
+}
@@ -0,0 +1,944 @@
+//! Minimal bindings to the COM interfaces used by the Windows implementation.
+//!
+//! The `windows-sys` crate only provides the raw functions and structures of the Windows API, not
+//! the vtables of its COM interfaces. The few interfaces this crate needs are declared here.
+
+use std::ffi::{c_void, OsString};
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+
+use windows_sys::core::{GUID, HRESULT};
+use windows_sys::Win32::Foundation::{HWND, POINTL};
+use windows_sys::Win32::System::Com::StructuredStorage::PROPVARIANT;
+use windows_sys::Win32::System::Com::{
+    DVASPECT_CONTENT, FORMATETC, STGMEDIUM, TYMED_HGLOBAL, VT_LPWSTR,
+};
+use windows_sys::Win32::System::Ole::ReleaseStgMedium;
+use windows_sys::Win32::UI::Shell::Common::COMDLG_FILTERSPEC;
+use windows_sys::Win32::UI::Shell::PropertiesSystem::{SHGetPropertyStoreForWindow, PROPERTYKEY};
+use windows_sys::Win32::UI::WindowsAndMessaging::HICON;
+
+use super::clipboard::read_global;
+use super::hwnd::make_utf16;
+use super::Error;
+
+/// The vtable of the `IPropertyStore` interface.
+#[repr(C)]
+struct IPropertyStoreVtbl {
+    query_interface:
+        unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+    release: unsafe extern "system" fn(*mut c_void) -> u32,
+    get_count: unsafe extern "system" fn(*mut c_void, *mut u32) -> HRESULT,
+    get_at: unsafe extern "system" fn(*mut c_void, u32, *mut PROPERTYKEY) -> HRESULT,
+    get_value:
+        unsafe extern "system" fn(*mut c_void, *const PROPERTYKEY, *mut PROPVARIANT) -> HRESULT,
+    set_value:
+        unsafe extern "system" fn(*mut c_void, *const PROPERTYKEY, *const PROPVARIANT) -> HRESULT,
+    commit: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+}
+
+/// The identifier of the `IPropertyStore` interface.
+const IID_IPROPERTY_STORE: GUID = GUID::from_u128(0x886d8eeb_8cf2_4446_8d02_cdba1dbdcf99);
+
+/// An owned reference to an `IPropertyStore` object, released when dropped.
+pub struct PropertyStore(*mut *const IPropertyStoreVtbl);
+
+impl PropertyStore {
+    /// Returns the property store of the provided window.
+    pub fn for_window(hwnd: HWND) -> Result<Self, Error> {
+        let mut store = std::ptr::null_mut();
+
+        let hr = unsafe { SHGetPropertyStoreForWindow(hwnd, &IID_IPROPERTY_STORE, &mut store) };
+
+        if hr < 0 {
+            Err(Error::from_hresult(hr))
+        } else {
+            Ok(Self(store as *mut *const IPropertyStoreVtbl))
+        }
+    }
+
+    /// Returns the property store of the provided object.
+    ///
+    /// # Safety
+    ///
+    /// `object` must be a valid COM object.
+    pub unsafe fn for_object(object: *mut c_void) -> Result<Self, Error> {
+        let store = unsafe { query_interface(object, &IID_IPROPERTY_STORE)? };
+        Ok(Self(store as *mut *const IPropertyStoreVtbl))
+    }
+
+    /// Returns the vtable of the object.
+    #[inline]
+    fn vtbl(&self) -> &IPropertyStoreVtbl {
+        unsafe { &**self.0 }
+    }
+
+    /// Sets the provided property to a string value and commits the change.
+    pub fn set_string(&mut self, key: &PROPERTYKEY, value: &str) -> Result<(), Error> {
+        let mut value = make_utf16(value);
+
+        // The store copies the value, so the string does not need to be allocated with
+        // `CoTaskMemAlloc`.
+        let mut variant: PROPVARIANT = unsafe { std::mem::zeroed() };
+        variant.Anonymous.Anonymous.vt = VT_LPWSTR;
+        variant.Anonymous.Anonymous.Anonymous.pwszVal = value.as_mut_ptr();
+
+        let hr = unsafe { (self.vtbl().set_value)(self.0 as *mut c_void, key, &variant) };
+        if hr < 0 {
+            return Err(Error::from_hresult(hr));
+        }
+
+        let hr = unsafe { (self.vtbl().commit)(self.0 as *mut c_void) };
+        if hr < 0 {
+            return Err(Error::from_hresult(hr));
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for PropertyStore {
+    fn drop(&mut self) {
+        unsafe { (self.vtbl().release)(self.0 as *mut c_void) };
+    }
+}
+
+/// The identifier of the `IUnknown` interface.
+pub const IID_IUNKNOWN: GUID = GUID::from_u128(0x00000000_0000_0000_c000_000000000046);
+
+/// The vtable of the `IDataObject` interface.
+#[repr(C)]
+struct IDataObjectVtbl {
+    query_interface:
+        unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+    release: unsafe extern "system" fn(*mut c_void) -> u32,
+    get_data: unsafe extern "system" fn(*mut c_void, *const FORMATETC, *mut STGMEDIUM) -> HRESULT,
+    get_data_here:
+        unsafe extern "system" fn(*mut c_void, *const FORMATETC, *mut STGMEDIUM) -> HRESULT,
+    query_get_data: unsafe extern "system" fn(*mut c_void, *const FORMATETC) -> HRESULT,
+    // The remaining methods are not used.
+}
+
+/// A shared reference to an `IDataObject` object, released when dropped.
+pub struct DataObject(*mut *const IDataObjectVtbl);
+
+impl DataObject {
+    /// Creates a new reference to the provided `IDataObject` object.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid `IDataObject` pointer.
+    pub unsafe fn from_raw(raw: *mut c_void) -> Self {
+        let this = Self(raw as *mut *const IDataObjectVtbl);
+        unsafe { (this.vtbl().add_ref)(raw) };
+        this
+    }
+
+    /// Returns the vtable of the object.
+    #[inline]
+    fn vtbl(&self) -> &IDataObjectVtbl {
+        unsafe { &**self.0 }
+    }
+
+    /// Returns the description of the provided format, stored in global memory.
+    fn format_etc(format: u16) -> FORMATETC {
+        FORMATETC {
+            cfFormat: format,
+            ptd: std::ptr::null_mut(),
+            dwAspect: DVASPECT_CONTENT,
+            lindex: -1,
+            tymed: TYMED_HGLOBAL as u32,
+        }
+    }
+
+    /// Returns whether the object can provide data in the provided clipboard format.
+    pub fn has(&self, format: u16) -> bool {
+        let format = Self::format_etc(format);
+        let hr = unsafe { (self.vtbl().query_get_data)(self.0 as *mut c_void, &format) };
+
+        // `S_FALSE` is returned when the format is not supported.
+        hr == 0
+    }
+
+    /// Returns a copy of the data of the object in the provided clipboard format.
+    pub fn get(&self, format: u16) -> Option<Vec<u8>> {
+        let format = Self::format_etc(format);
+        let mut medium: STGMEDIUM = unsafe { std::mem::zeroed() };
+
+        let hr = unsafe { (self.vtbl().get_data)(self.0 as *mut c_void, &format, &mut medium) };
+        if hr < 0 {
+            return None;
+        }
+
+        let ret = if medium.tymed == TYMED_HGLOBAL {
+            unsafe { read_global(medium.Anonymous.hGlobal) }
+        } else {
+            None
+        };
+
+        unsafe { ReleaseStgMedium(&mut medium) };
+        ret
+    }
+}
+
+impl Clone for DataObject {
+    #[inline]
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw(self.0 as *mut c_void) }
+    }
+}
+
+/// Two references are equal when they refer to the same object.
+impl PartialEq for DataObject {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Drop for DataObject {
+    fn drop(&mut self) {
+        unsafe { (self.vtbl().release)(self.0 as *mut c_void) };
+    }
+}
+
+/// The vtable of the `IDropTarget` interface.
+#[repr(C)]
+pub struct IDropTargetVtbl {
+    pub query_interface:
+        unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+    pub add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+    pub release: unsafe extern "system" fn(*mut c_void) -> u32,
+    pub drag_enter:
+        unsafe extern "system" fn(*mut c_void, *mut c_void, u32, POINTL, *mut u32) -> HRESULT,
+    pub drag_over: unsafe extern "system" fn(*mut c_void, u32, POINTL, *mut u32) -> HRESULT,
+    pub drag_leave: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+    pub drop: unsafe extern "system" fn(*mut c_void, *mut c_void, u32, POINTL, *mut u32) -> HRESULT,
+}
+
+/// The identifier of the `IDropTarget` interface.
+pub const IID_IDROP_TARGET: GUID = GUID::from_u128(0x00000122_0000_0000_c000_000000000046);
+
+/// Keeps COM initialized on the current thread for as long as it exists.
+pub struct ComInit(bool);
+
+impl ComInit {
+    /// Initializes COM on the current thread, if it has not been initialized already.
+    pub fn new() -> Result<Self, Error> {
+        use windows_sys::Win32::Foundation::RPC_E_CHANGED_MODE;
+        use windows_sys::Win32::System::Com::{CoInitializeEx, COINIT_APARTMENTTHREADED};
+
+        let hr = unsafe { CoInitializeEx(std::ptr::null(), COINIT_APARTMENTTHREADED) };
+
+        // The thread has already been initialized for the multithreaded apartment, which the
+        // objects used by this crate support as well.
+        if hr == RPC_E_CHANGED_MODE {
+            Ok(Self(false))
+        } else if hr < 0 {
+            Err(Error::from_hresult(hr))
+        } else {
+            Ok(Self(true))
+        }
+    }
+}
+
+impl Drop for ComInit {
+    fn drop(&mut self) {
+        use windows_sys::Win32::System::Com::CoUninitialize;
+
+        if self.0 {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
+
+/// Creates an instance of the provided class, and returns its implementation of the provided
+/// interface.
+fn create_instance(clsid: &GUID, iid: &GUID) -> Result<*mut c_void, Error> {
+    use windows_sys::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+
+    let mut object = std::ptr::null_mut();
+    let hr = unsafe {
+        CoCreateInstance(
+            clsid,
+            std::ptr::null_mut(),
+            CLSCTX_INPROC_SERVER,
+            iid,
+            &mut object,
+        )
+    };
+
+    if hr < 0 {
+        Err(Error::from_hresult(hr))
+    } else {
+        Ok(object)
+    }
+}
+
+/// The vtable of the `ITaskbarList3` interface.
+#[repr(C)]
+struct ITaskbarList3Vtbl {
+    query_interface:
+        unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+    release: unsafe extern "system" fn(*mut c_void) -> u32,
+    hr_init: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+    // The methods between `HrInit` and `SetOverlayIcon` are not used.
+    _unused: [usize; 14],
+    set_overlay_icon: unsafe extern "system" fn(*mut c_void, HWND, HICON, *const u16) -> HRESULT,
+}
+
+/// The identifier of the `TaskbarList` class.
+const CLSID_TASKBAR_LIST: GUID = GUID::from_u128(0x56fdf344_fd6d_11d0_958a_006097c9a090);
+/// The identifier of the `ITaskbarList3` interface.
+const IID_ITASKBAR_LIST3: GUID = GUID::from_u128(0xea1afb91_9e28_4b86_90e9_9e9f8a5eee2f);
+
+/// An owned reference to an `ITaskbarList3` object, released when dropped.
+pub struct TaskbarList {
+    this: *mut *const ITaskbarList3Vtbl,
+    /// COM must remain initialized for as long as the object exists.
+    _com: ComInit,
+}
+
+impl TaskbarList {
+    /// Creates a new [`TaskbarList`] instance.
+    pub fn new() -> Result<Self, Error> {
+        let com = ComInit::new()?;
+        let this = Self {
+            this: create_instance(&CLSID_TASKBAR_LIST, &IID_ITASKBAR_LIST3)? as _,
+            _com: com,
+        };
+
+        let hr = unsafe { (this.vtbl().hr_init)(this.this as *mut c_void) };
+        if hr < 0 {
+            return Err(Error::from_hresult(hr));
+        }
+
+        Ok(this)
+    }
+
+    /// Returns the vtable of the object.
+    #[inline]
+    fn vtbl(&self) -> &ITaskbarList3Vtbl {
+        unsafe { &**self.this }
+    }
+
+    /// Sets the overlay icon displayed over the taskbar button of the provided window.
+    ///
+    /// `description` is a null-terminated UTF-16 string, used by accessibility tools.
+    pub fn set_overlay_icon(
+        &mut self,
+        hwnd: HWND,
+        icon: HICON,
+        description: &[u16],
+    ) -> Result<(), Error> {
+        let hr = unsafe {
+            (self.vtbl().set_overlay_icon)(
+                self.this as *mut c_void,
+                hwnd,
+                icon,
+                description.as_ptr(),
+            )
+        };
+
+        if hr < 0 {
+            Err(Error::from_hresult(hr))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for TaskbarList {
+    fn drop(&mut self) {
+        unsafe { (self.vtbl().release)(self.this as *mut c_void) };
+    }
+}
+
+/// The vtable of the `IUnknown` interface, which starts the vtable of every COM interface.
+#[repr(C)]
+struct IUnknownVtbl {
+    query_interface:
+        unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut c_void) -> u32,
+    release: unsafe extern "system" fn(*mut c_void) -> u32,
+}
+
+/// Returns a new reference to the provided interface of a COM object.
+///
+/// # Safety
+///
+/// `object` must be a valid COM object.
+unsafe fn query_interface(object: *mut c_void, iid: &GUID) -> Result<*mut c_void, Error> {
+    let mut ret = std::ptr::null_mut();
+
+    let hr = unsafe {
+        let vtbl = &**(object as *mut *const IUnknownVtbl);
+        (vtbl.query_interface)(object, iid, &mut ret)
+    };
+
+    if hr < 0 {
+        Err(Error::from_hresult(hr))
+    } else {
+        Ok(ret)
+    }
+}
+
+/// Releases a reference to a COM object.
+///
+/// # Safety
+///
+/// `object` must be a valid COM object, which must not be used afterwards through this
+/// reference.
+unsafe fn release(object: *mut c_void) {
+    unsafe {
+        let vtbl = &**(object as *mut *const IUnknownVtbl);
+        (vtbl.release)(object);
+    }
+}
+
+/// Converts an `HRESULT` into a standard result.
+fn check(hr: HRESULT) -> Result<(), Error> {
+    if hr < 0 {
+        Err(Error::from_hresult(hr))
+    } else {
+        Ok(())
+    }
+}
+
+/// The vtable of the `IShellLinkW` interface.
+#[repr(C)]
+struct IShellLinkWVtbl {
+    unknown: IUnknownVtbl,
+    // `GetPath`, `GetIDList`, `SetIDList` and `GetDescription` are not used.
+    _unused0: [usize; 4],
+    set_description: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    // `GetWorkingDirectory`, `SetWorkingDirectory` and `GetArguments` are not used.
+    _unused1: [usize; 3],
+    set_arguments: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    // `GetHotkey`, `SetHotkey`, `GetShowCmd`, `SetShowCmd` and `GetIconLocation` are not used.
+    _unused2: [usize; 5],
+    set_icon_location: unsafe extern "system" fn(*mut c_void, *const u16, i32) -> HRESULT,
+    // `SetRelativePath` and `Resolve` are not used.
+    _unused3: [usize; 2],
+    set_path: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+}
+
+/// The identifier of the `ShellLink` class.
+const CLSID_SHELL_LINK: GUID = GUID::from_u128(0x00021401_0000_0000_c000_000000000046);
+/// The identifier of the `IShellLinkW` interface.
+const IID_ISHELL_LINK_W: GUID = GUID::from_u128(0x000214f9_0000_0000_c000_000000000046);
+
+/// An owned reference to an `IShellLinkW` object, released when dropped.
+///
+/// COM must be initialized while the object exists.
+pub struct ShellLink(*mut *const IShellLinkWVtbl);
+
+impl ShellLink {
+    /// Creates a new shell link that points to the provided program.
+    ///
+    /// `path` and `arguments` are null-terminated UTF-16 strings.
+    pub fn new(path: &[u16], arguments: &[u16]) -> Result<Self, Error> {
+        let this = Self(create_instance(&CLSID_SHELL_LINK, &IID_ISHELL_LINK_W)? as _);
+
+        unsafe {
+            check((this.vtbl().set_path)(this.as_raw(), path.as_ptr()))?;
+            check((this.vtbl().set_arguments)(
+                this.as_raw(),
+                arguments.as_ptr(),
+            ))?;
+            // Use the icon of the program.
+            check((this.vtbl().set_icon_location)(
+                this.as_raw(),
+                path.as_ptr(),
+                0,
+            ))?;
+        }
+
+        Ok(this)
+    }
+
+    /// Returns the vtable of the object.
+    #[inline]
+    fn vtbl(&self) -> &IShellLinkWVtbl {
+        unsafe { &**self.0 }
+    }
+
+    /// Returns the raw pointer to the object.
+    #[inline]
+    fn as_raw(&self) -> *mut c_void {
+        self.0 as *mut c_void
+    }
+
+    /// Sets the description of the link, displayed as a tooltip.
+    ///
+    /// `description` is a null-terminated UTF-16 string.
+    pub fn set_description(&mut self, description: &[u16]) -> Result<(), Error> {
+        unsafe {
+            check((self.vtbl().set_description)(
+                self.as_raw(),
+                description.as_ptr(),
+            ))
+        }
+    }
+
+    /// Sets the title of the link, displayed in jump lists.
+    pub fn set_title(&mut self, title: &str) -> Result<(), Error> {
+        use windows_sys::Win32::Storage::EnhancedStorage::PKEY_Title;
+
+        unsafe { PropertyStore::for_object(self.as_raw())?.set_string(&PKEY_Title, title) }
+    }
+}
+
+impl Drop for ShellLink {
+    fn drop(&mut self) {
+        unsafe { release(self.as_raw()) };
+    }
+}
+
+/// The vtable of the `IObjectCollection` interface.
+#[repr(C)]
+struct IObjectCollectionVtbl {
+    unknown: IUnknownVtbl,
+    // `GetCount` and `GetAt` are not used.
+    _unused: [usize; 2],
+    add_object: unsafe extern "system" fn(*mut c_void, *mut c_void) -> HRESULT,
+    // The remaining methods are not used.
+}
+
+/// The identifier of the `EnumerableObjectCollection` class.
+const CLSID_ENUMERABLE_OBJECT_COLLECTION: GUID =
+    GUID::from_u128(0x2d3468c1_36a7_43b6_ac24_d3f02fd9607a);
+/// The identifier of the `IObjectCollection` interface.
+const IID_IOBJECT_COLLECTION: GUID = GUID::from_u128(0x5632b1a4_e38a_400a_928a_d4cd63230295);
+/// The identifier of the `IObjectArray` interface.
+const IID_IOBJECT_ARRAY: GUID = GUID::from_u128(0x92ca9dcd_5622_4bba_a805_5e9f541bd8c9);
+
+/// An owned reference to an `IObjectCollection` object, released when dropped.
+///
+/// COM must be initialized while the object exists.
+pub struct ObjectCollection(*mut *const IObjectCollectionVtbl);
+
+impl ObjectCollection {
+    /// Creates a new, empty [`ObjectCollection`].
+    pub fn new() -> Result<Self, Error> {
+        let raw = create_instance(&CLSID_ENUMERABLE_OBJECT_COLLECTION, &IID_IOBJECT_COLLECTION)?;
+        Ok(Self(raw as _))
+    }
+
+    /// Adds a shell link to the collection.
+    pub fn add_link(&mut self, link: &ShellLink) -> Result<(), Error> {
+        let vtbl = unsafe { &**self.0 };
+        unsafe { check((vtbl.add_object)(self.0 as *mut c_void, link.as_raw())) }
+    }
+}
+
+impl Drop for ObjectCollection {
+    fn drop(&mut self) {
+        unsafe { release(self.0 as *mut c_void) };
+    }
+}
+
+/// The vtable of the `ICustomDestinationList` interface.
+#[repr(C)]
+struct ICustomDestinationListVtbl {
+    unknown: IUnknownVtbl,
+    set_app_id: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    begin_list:
+        unsafe extern "system" fn(*mut c_void, *mut u32, *const GUID, *mut *mut c_void) -> HRESULT,
+    append_category: unsafe extern "system" fn(*mut c_void, *const u16, *mut c_void) -> HRESULT,
+    append_known_category: unsafe extern "system" fn(*mut c_void, i32) -> HRESULT,
+    add_user_tasks: unsafe extern "system" fn(*mut c_void, *mut c_void) -> HRESULT,
+    commit_list: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+    get_removed_destinations:
+        unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+    delete_list: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    abort_list: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+}
+
+/// The identifier of the `DestinationList` class.
+const CLSID_DESTINATION_LIST: GUID = GUID::from_u128(0x77f10cf0_3db5_4966_b520_b7c54fd35ed6);
+/// The identifier of the `ICustomDestinationList` interface.
+const IID_ICUSTOM_DESTINATION_LIST: GUID = GUID::from_u128(0x6332debf_87b5_4670_90c0_5e57b408a49e);
+
+/// An owned reference to an `ICustomDestinationList` object, released when dropped.
+///
+/// COM must be initialized while the object exists.
+pub struct DestinationList(*mut *const ICustomDestinationListVtbl);
+
+impl DestinationList {
+    /// Creates a new [`DestinationList`].
+    ///
+    /// `app_id` is the null-terminated UTF-16 application ID whose jump list is edited. If
+    /// `None`, the ID of the process is used.
+    pub fn new(app_id: Option<&[u16]>) -> Result<Self, Error> {
+        let raw = create_instance(&CLSID_DESTINATION_LIST, &IID_ICUSTOM_DESTINATION_LIST)?;
+        let this = Self(raw as _);
+
+        if let Some(app_id) = app_id {
+            unsafe { check((this.vtbl().set_app_id)(this.as_raw(), app_id.as_ptr()))? };
+        }
+
+        Ok(this)
+    }
+
+    /// Returns the vtable of the object.
+    #[inline]
+    fn vtbl(&self) -> &ICustomDestinationListVtbl {
+        unsafe { &**self.0 }
+    }
+
+    /// Returns the raw pointer to the object.
+    #[inline]
+    fn as_raw(&self) -> *mut c_void {
+        self.0 as *mut c_void
+    }
+
+    /// Replaces the jump list with the provided tasks, and eventually the recent items of the
+    /// application.
+    pub fn replace(&mut self, tasks: &ObjectCollection, recent: bool) -> Result<(), Error> {
+        use windows_sys::Win32::UI::Shell::KDC_RECENT;
+
+        unsafe {
+            let mut min_slots = 0;
+            let mut removed = std::ptr::null_mut();
+            check((self.vtbl().begin_list)(
+                self.as_raw(),
+                &mut min_slots,
+                &IID_IOBJECT_ARRAY,
+                &mut removed,
+            ))?;
+            release(removed);
+
+            let ret = (|| {
+                if recent {
+                    check((self.vtbl().append_known_category)(
+                        self.as_raw(),
+                        KDC_RECENT,
+                    ))?;
+                }
+                check((self.vtbl().add_user_tasks)(
+                    self.as_raw(),
+                    tasks.0 as *mut c_void,
+                ))?;
+                check((self.vtbl().commit_list)(self.as_raw()))
+            })();
+
+            if ret.is_err() {
+                (self.vtbl().abort_list)(self.as_raw());
+            }
+
+            ret
+        }
+    }
+}
+
+impl Drop for DestinationList {
+    fn drop(&mut self) {
+        unsafe { release(self.as_raw()) };
+    }
+}
+
+/// The vtable of the `IShellItem` interface.
+#[repr(C)]
+struct IShellItemVtbl {
+    unknown: IUnknownVtbl,
+    // `BindToHandler` and `GetParent` are not used.
+    _unused: [usize; 2],
+    get_display_name: unsafe extern "system" fn(*mut c_void, i32, *mut *mut u16) -> HRESULT,
+    // The remaining methods are not used.
+}
+
+/// The identifier of the `IShellItem` interface.
+const IID_ISHELL_ITEM: GUID = GUID::from_u128(0x43826d1e_e718_42ee_bc55_a1e261c37bfe);
+
+/// An owned reference to an `IShellItem` object, released when dropped.
+///
+/// COM must be initialized while the object exists.
+pub struct ShellItem(*mut *const IShellItemVtbl);
+
+impl ShellItem {
+    /// Returns the shell item that represents the provided path.
+    pub fn from_path(path: &Path) -> Result<Self, Error> {
+        use windows_sys::Win32::UI::Shell::SHCreateItemFromParsingName;
+
+        let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+
+        let mut item = std::ptr::null_mut();
+        let hr = unsafe {
+            SHCreateItemFromParsingName(
+                path.as_ptr(),
+                std::ptr::null_mut(),
+                &IID_ISHELL_ITEM,
+                &mut item,
+            )
+        };
+        check(hr)?;
+
+        Ok(Self(item as _))
+    }
+
+    /// Returns the path of the item in the file system, if it has one.
+    pub fn path(&self) -> Option<PathBuf> {
+        use windows_sys::Win32::System::Com::CoTaskMemFree;
+        use windows_sys::Win32::UI::Shell::SIGDN_FILESYSPATH;
+
+        let vtbl = unsafe { &**self.0 };
+
+        let mut name = std::ptr::null_mut();
+        let hr = unsafe { (vtbl.get_display_name)(self.0 as _, SIGDN_FILESYSPATH, &mut name) };
+        if hr < 0 {
+            return None;
+        }
+
+        let path = unsafe {
+            let len = (0..).take_while(|&i| *name.add(i) != 0).count();
+            OsString::from_wide(std::slice::from_raw_parts(name, len))
+        };
+        unsafe { CoTaskMemFree(name as *const c_void) };
+
+        Some(PathBuf::from(path))
+    }
+}
+
+impl Drop for ShellItem {
+    fn drop(&mut self) {
+        unsafe { release(self.0 as *mut c_void) };
+    }
+}
+
+/// The vtable of the `IShellItemArray` interface.
+#[repr(C)]
+struct IShellItemArrayVtbl {
+    unknown: IUnknownVtbl,
+    // `BindToHandler`, `GetPropertyStore`, `GetPropertyDescriptionList` and `GetAttributes` are
+    // not used.
+    _unused: [usize; 4],
+    get_count: unsafe extern "system" fn(*mut c_void, *mut u32) -> HRESULT,
+    get_item_at: unsafe extern "system" fn(*mut c_void, u32, *mut *mut c_void) -> HRESULT,
+    // `EnumItems` is not used.
+}
+
+/// An owned reference to an `IShellItemArray` object, released when dropped.
+///
+/// COM must be initialized while the object exists.
+struct ShellItemArray(*mut *const IShellItemArrayVtbl);
+
+impl ShellItemArray {
+    /// Returns the items of the array.
+    fn items(&self) -> Result<Vec<ShellItem>, Error> {
+        let vtbl = unsafe { &**self.0 };
+
+        let mut count = 0;
+        unsafe { check((vtbl.get_count)(self.0 as _, &mut count))? };
+
+        (0..count)
+            .map(|i| {
+                let mut item = std::ptr::null_mut();
+                unsafe { check((vtbl.get_item_at)(self.0 as _, i, &mut item))? };
+                Ok(ShellItem(item as _))
+            })
+            .collect()
+    }
+}
+
+impl Drop for ShellItemArray {
+    fn drop(&mut self) {
+        unsafe { release(self.0 as *mut c_void) };
+    }
+}
+
+/// The vtable of the `IFileDialog` interface.
+#[repr(C)]
+struct IFileDialogVtbl {
+    unknown: IUnknownVtbl,
+    show: unsafe extern "system" fn(*mut c_void, HWND) -> HRESULT,
+    set_file_types:
+        unsafe extern "system" fn(*mut c_void, u32, *const COMDLG_FILTERSPEC) -> HRESULT,
+    // `SetFileTypeIndex`, `GetFileTypeIndex`, `Advise` and `Unadvise` are not used.
+    _unused0: [usize; 4],
+    set_options: unsafe extern "system" fn(*mut c_void, u32) -> HRESULT,
+    get_options: unsafe extern "system" fn(*mut c_void, *mut u32) -> HRESULT,
+    set_default_folder: unsafe extern "system" fn(*mut c_void, *mut c_void) -> HRESULT,
+    // `SetFolder`, `GetFolder` and `GetCurrentSelection` are not used.
+    _unused1: [usize; 3],
+    set_file_name: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    // `GetFileName` is not used.
+    _unused2: [usize; 1],
+    set_title: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    // `SetOkButtonLabel` and `SetFileNameLabel` are not used.
+    _unused3: [usize; 2],
+    get_result: unsafe extern "system" fn(*mut c_void, *mut *mut c_void) -> HRESULT,
+    // `AddPlace` is not used.
+    _unused4: [usize; 1],
+    set_default_extension: unsafe extern "system" fn(*mut c_void, *const u16) -> HRESULT,
+    // `Close`, `SetClientGuid`, `ClearClientData` and `SetFilter` are not used.
+    _unused5: [usize; 4],
+}
+
+/// The vtable of the `IFileOpenDialog` interface.
+#[repr(C)]
+struct IFileOpenDialogVtbl {
+    dialog: IFileDialogVtbl,
+    get_results: unsafe extern "system" fn(*mut c_void, *mut *mut c_void) -> HRESULT,
+    // `GetSelectedItems` is not used.
+}
+
+/// The identifier of the `FileOpenDialog` class.
+const CLSID_FILE_OPEN_DIALOG: GUID = GUID::from_u128(0xdc1c5a9c_e88a_4dde_a5a1_60f82a20aef7);
+/// The identifier of the `FileSaveDialog` class.
+const CLSID_FILE_SAVE_DIALOG: GUID = GUID::from_u128(0xc0b4e2f3_ba21_4773_8dba_335ec946eb8b);
+/// The identifier of the `IFileOpenDialog` interface.
+const IID_IFILE_OPEN_DIALOG: GUID = GUID::from_u128(0xd57c7288_d4ad_4768_be02_9d969532d960);
+/// The identifier of the `IFileSaveDialog` interface.
+const IID_IFILE_SAVE_DIALOG: GUID = GUID::from_u128(0x84bccd23_5fde_4cdb_aea4_af64b83d78ab);
+
+/// An owned reference to an `IFileOpenDialog` or `IFileSaveDialog` object, released when
+/// dropped.
+///
+/// COM must be initialized while the object exists.
+pub struct FileDialog {
+    this: *mut *const IFileDialogVtbl,
+    /// Whether the object is an open dialog, which implements `IFileOpenDialog`.
+    open: bool,
+}
+
+impl FileDialog {
+    /// Creates a new dialog that lets the user pick existing files.
+    pub fn new_open() -> Result<Self, Error> {
+        Ok(Self {
+            this: create_instance(&CLSID_FILE_OPEN_DIALOG, &IID_IFILE_OPEN_DIALOG)? as _,
+            open: true,
+        })
+    }
+
+    /// Creates a new dialog that lets the user choose where to save a file.
+    pub fn new_save() -> Result<Self, Error> {
+        Ok(Self {
+            this: create_instance(&CLSID_FILE_SAVE_DIALOG, &IID_IFILE_SAVE_DIALOG)? as _,
+            open: false,
+        })
+    }
+
+    /// Returns the vtable of the object.
+    #[inline]
+    fn vtbl(&self) -> &IFileDialogVtbl {
+        unsafe { &**self.this }
+    }
+
+    /// Returns the raw pointer to the object.
+    #[inline]
+    fn as_raw(&self) -> *mut c_void {
+        self.this as *mut c_void
+    }
+
+    /// Adds the provided `FOS_*` options to the dialog.
+    pub fn add_options(&mut self, options: u32) -> Result<(), Error> {
+        let mut current = 0;
+        unsafe {
+            check((self.vtbl().get_options)(self.as_raw(), &mut current))?;
+            check((self.vtbl().set_options)(self.as_raw(), current | options))
+        }
+    }
+
+    /// Sets the file types the user can choose from.
+    ///
+    /// Each file type is a pair of null-terminated UTF-16 strings: its name, and the
+    /// semicolon-separated patterns of its files (e.g. `*.png;*.jpg`).
+    pub fn set_file_types(&mut self, types: &[(Vec<u16>, Vec<u16>)]) -> Result<(), Error> {
+        let specs: Vec<COMDLG_FILTERSPEC> = types
+            .iter()
+            .map(|(name, spec)| COMDLG_FILTERSPEC {
+                pszName: name.as_ptr(),
+                pszSpec: spec.as_ptr(),
+            })
+            .collect();
+
+        unsafe {
+            check((self.vtbl().set_file_types)(
+                self.as_raw(),
+                specs.len() as u32,
+                specs.as_ptr(),
+            ))
+        }
+    }
+
+    /// Sets the folder displayed when the dialog is opened, if the user has not used the dialog
+    /// recently.
+    pub fn set_default_folder(&mut self, folder: &ShellItem) -> Result<(), Error> {
+        unsafe {
+            check((self.vtbl().set_default_folder)(
+                self.as_raw(),
+                folder.0 as _,
+            ))
+        }
+    }
+
+    /// Sets the initial file name of the dialog.
+    ///
+    /// `name` is a null-terminated UTF-16 string.
+    pub fn set_file_name(&mut self, name: &[u16]) -> Result<(), Error> {
+        unsafe { check((self.vtbl().set_file_name)(self.as_raw(), name.as_ptr())) }
+    }
+
+    /// Sets the title of the dialog.
+    ///
+    /// `title` is a null-terminated UTF-16 string.
+    pub fn set_title(&mut self, title: &[u16]) -> Result<(), Error> {
+        unsafe { check((self.vtbl().set_title)(self.as_raw(), title.as_ptr())) }
+    }
+
+    /// Sets the extension appended to the file names entered by the user without one.
+    ///
+    /// `extension` is a null-terminated UTF-16 string, without the leading dot.
+    pub fn set_default_extension(&mut self, extension: &[u16]) -> Result<(), Error> {
+        unsafe {
+            check((self.vtbl().set_default_extension)(
+                self.as_raw(),
+                extension.as_ptr(),
+            ))
+        }
+    }
+
+    /// Shows the dialog, and blocks until the user closes it.
+    ///
+    /// `false` is returned if the user has canceled the dialog.
+    pub fn show(&mut self, parent: HWND) -> Result<bool, Error> {
+        use windows_sys::Win32::Foundation::ERROR_CANCELLED;
+
+        let hr = unsafe { (self.vtbl().show)(self.as_raw(), parent) };
+        if Error::from_hresult(hr) == Error(ERROR_CANCELLED) {
+            Ok(false)
+        } else {
+            check(hr).map(|()| true)
+        }
+    }
+
+    /// Returns the item chosen by the user.
+    pub fn result(&self) -> Result<ShellItem, Error> {
+        let mut item = std::ptr::null_mut();
+        unsafe { check((self.vtbl().get_result)(self.as_raw(), &mut item))? };
+        Ok(ShellItem(item as _))
+    }
+
+    /// Returns the items chosen by the user.
+    ///
+    /// This is only available for open dialogs.
+    pub fn results(&self) -> Result<Vec<ShellItem>, Error> {
+        assert!(self.open, "only open dialogs can return multiple items");
+
+        let vtbl = unsafe { &**(self.this as *mut *const IFileOpenDialogVtbl) };
+
+        let mut items = std::ptr::null_mut();
+        unsafe { check((vtbl.get_results)(self.as_raw(), &mut items))? };
+        ShellItemArray(items as _).items()
+    }
+}
+
+impl Drop for FileDialog {
+    fn drop(&mut self) {
+        unsafe { release(self.as_raw()) };
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use std::sync::Arc;
+
+use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+use super::{create_icon, Error};
+use crate::CursorIcon;
+
+/// The [`crate::Cursor`] implementation for Windows.
+#[derive(Clone)]
+pub struct Cursor(Arc<CursorHandle>);
+
+impl Cursor {
+    /// See [`crate::Cursor::from_rgba`].
+    pub fn from_rgba(
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot: (u32, u32),
+    ) -> Result<Self, Error> {
+        let handle = create_icon(rgba, width, height, Some(hotspot))?;
+        Ok(Self(Arc::new(CursorHandle(handle))))
+    }
+
+    /// See [`crate::Cursor::from_cursor_file`].
+    pub fn from_cursor_file(data: &[u8]) -> Result<Self, Error> {
+        // Animated cursors are RIFF files, which the system is able to load as-is. Static
+        // cursors must be converted into the format of cursor resources first.
+        let handle = if data.starts_with(b"RIFF") {
+            create_cursor_from_resource(data)?
+        } else {
+            create_cursor_from_resource(&static_cursor_resource(data)?)?
+        };
+
+        Ok(Self(Arc::new(CursorHandle(handle))))
+    }
+
+    /// Returns the raw handle of the cursor.
+    #[inline]
+    pub fn handle(&self) -> HCURSOR {
+        self.0 .0
+    }
+}
+
+impl fmt::Debug for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.handle()).finish()
+    }
+}
+
+/// An owned `HCURSOR` handle, destroyed when dropped.
+struct CursorHandle(HCURSOR);
+
+impl Drop for CursorHandle {
+    fn drop(&mut self) {
+        unsafe { DestroyCursor(self.0) };
+    }
+}
+
+/// Returns the handle of the provided system cursor.
+///
+/// System cursors are shared and must not be destroyed.
+pub fn system_cursor(icon: CursorIcon) -> HCURSOR {
+    let name = match icon {
+        CursorIcon::Arrow => IDC_ARROW,
+        CursorIcon::IBeam => IDC_IBEAM,
+        CursorIcon::Hand => IDC_HAND,
+        CursorIcon::Crosshair => IDC_CROSS,
+        CursorIcon::Wait => IDC_WAIT,
+        CursorIcon::Progress => IDC_APPSTARTING,
+        CursorIcon::Help => IDC_HELP,
+        CursorIcon::NotAllowed => IDC_NO,
+        CursorIcon::Move => IDC_SIZEALL,
+        CursorIcon::ResizeHorizontal => IDC_SIZEWE,
+        CursorIcon::ResizeVertical => IDC_SIZENS,
+        CursorIcon::ResizeNwSe => IDC_SIZENWSE,
+        CursorIcon::ResizeNeSw => IDC_SIZENESW,
+    };
+
+    unsafe { LoadCursorW(0, name) }
+}
+
+/// Reads a little-endian `u16` at the provided offset.
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Reads a little-endian `u32` at the provided offset.
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Extracts the image that best matches the size of system cursors from the content of a `.cur`
+/// file.
+///
+/// The returned buffer is in the format of cursor resources: the hotspot of the cursor, followed
+/// by the image itself.
+fn static_cursor_resource(data: &[u8]) -> Result<Vec<u8>, Error> {
+    use windows_sys::Win32::Foundation::ERROR_INVALID_DATA;
+
+    /// The size of the header of the file.
+    const HEADER_SIZE: usize = 6;
+    /// The size of each entry of the directory that follows the header.
+    const ENTRY_SIZE: usize = 16;
+
+    let invalid = || Error(ERROR_INVALID_DATA);
+
+    // The type of cursor files is 2. Icon files use 1.
+    if read_u16(data, 0) != Some(0) || read_u16(data, 2) != Some(2) {
+        return Err(invalid());
+    }
+
+    let count = read_u16(data, 4).ok_or_else(invalid)? as usize;
+    let desired = unsafe { GetSystemMetrics(SM_CXCURSOR) } as u32;
+
+    // A width of 0 stands for 256 pixels. Prefer the smallest image that is at least as large
+    // as the system cursors, and the largest one otherwise.
+    let entry = (0..count)
+        .map(|i| HEADER_SIZE + i * ENTRY_SIZE)
+        .filter(|&offset| offset + ENTRY_SIZE <= data.len())
+        .max_by_key(|&offset| {
+            let width = match data[offset] {
+                0 => 256,
+                width => width as u32,
+            };
+
+            if width >= desired {
+                u32::MAX - width
+            } else {
+                width
+            }
+        })
+        .ok_or_else(invalid)?;
+
+    let hotspot_x = read_u16(data, entry + 4).ok_or_else(invalid)?;
+    let hotspot_y = read_u16(data, entry + 6).ok_or_else(invalid)?;
+    let size = read_u32(data, entry + 8).ok_or_else(invalid)? as usize;
+    let offset = read_u32(data, entry + 12).ok_or_else(invalid)? as usize;
+
+    let image = offset
+        .checked_add(size)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(invalid)?;
+
+    let mut resource = Vec::with_capacity(4 + image.len());
+    resource.extend_from_slice(&hotspot_x.to_le_bytes());
+    resource.extend_from_slice(&hotspot_y.to_le_bytes());
+    resource.extend_from_slice(image);
+    Ok(resource)
+}
+
+/// Creates a cursor from the content of a cursor resource, or of an animated cursor file.
+fn create_cursor_from_resource(data: &[u8]) -> Result<HCURSOR, Error> {
+    // The version of the resource format is always 3.0.
+    let ret = unsafe {
+        CreateIconFromResourceEx(
+            data.as_ptr(),
+            data.len() as u32,
+            0,
+            0x0003_0000,
+            0,
+            0,
+            LR_DEFAULTCOLOR,
+        )
+    };
+
+    if ret == 0 {
+        Err(Error::last())
+    } else {
+        Ok(ret)
+    }
+}
@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+use super::com::{ComInit, FileDialog, ShellItem};
+use super::hwnd::make_utf16;
+use super::Error;
+use crate::{MessageBoxButton, MessageBoxButtons};
+
+/// See [`crate::message_box`].
+pub fn message_box(
+    parent: HWND,
+    title: &str,
+    text: &str,
+    buttons: MessageBoxButtons,
+) -> Result<MessageBoxButton, Error> {
+    let style = match buttons {
+        MessageBoxButtons::Ok => MB_OK,
+        MessageBoxButtons::OkCancel => MB_OKCANCEL,
+        MessageBoxButtons::YesNo => MB_YESNO,
+        MessageBoxButtons::YesNoCancel => MB_YESNOCANCEL,
+        MessageBoxButtons::RetryCancel => MB_RETRYCANCEL,
+    };
+
+    let title = make_utf16(title);
+    let text = make_utf16(text);
+
+    // Without a parent, the message box would not be brought to the foreground.
+    let style = if parent == 0 {
+        style | MB_SETFOREGROUND
+    } else {
+        style
+    };
+
+    let ret = unsafe { MessageBoxW(parent, text.as_ptr(), title.as_ptr(), style) };
+
+    match ret {
+        IDOK => Ok(MessageBoxButton::Ok),
+        IDCANCEL => Ok(MessageBoxButton::Cancel),
+        IDYES => Ok(MessageBoxButton::Yes),
+        IDNO => Ok(MessageBoxButton::No),
+        IDRETRY => Ok(MessageBoxButton::Retry),
+        _ => Err(Error::last()),
+    }
+}
+
+/// The kinds of file dialogs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileDialogKind {
+    /// The user picks existing files.
+    Open,
+    /// The user chooses where to save a file.
+    Save,
+    /// The user picks an existing folder.
+    Folder,
+}
+
+/// Creates a dialog of the provided kind, configured with the provided options.
+fn create_file_dialog(
+    dialog: &crate::FileDialog,
+    kind: FileDialogKind,
+) -> Result<FileDialog, Error> {
+    use windows_sys::Win32::UI::Shell::{FOS_FORCEFILESYSTEM, FOS_PICKFOLDERS};
+
+    let mut ret = match kind {
+        FileDialogKind::Open | FileDialogKind::Folder => FileDialog::new_open()?,
+        FileDialogKind::Save => FileDialog::new_save()?,
+    };
+
+    // Only paths in the file system can be returned.
+    ret.add_options(FOS_FORCEFILESYSTEM)?;
+
+    if kind == FileDialogKind::Folder {
+        ret.add_options(FOS_PICKFOLDERS)?;
+    }
+
+    if let Some(title) = dialog.title {
+        ret.set_title(&make_utf16(title))?;
+    }
+
+    // Folder pickers do not display files.
+    if !dialog.filters.is_empty() && kind != FileDialogKind::Folder {
+        let types: Vec<_> = dialog
+            .filters
+            .iter()
+            .map(|filter| {
+                let spec = filter
+                    .extensions
+                    .iter()
+                    .map(|ext| format!("*.{ext}"))
+                    .collect::<Vec<_>>()
+                    .join(";");
+                (make_utf16(filter.name), make_utf16(&spec))
+            })
+            .collect();
+        ret.set_file_types(&types)?;
+
+        // The dialog replaces the default extension with the first one of the selected type.
+        if let Some(ext) = dialog.filters[0].extensions.first() {
+            ret.set_default_extension(&make_utf16(ext))?;
+        }
+    }
+
+    // A directory that does not exist is not an error worth reporting.
+    if let Some(folder) = dialog
+        .directory
+        .and_then(|dir| ShellItem::from_path(dir).ok())
+    {
+        ret.set_default_folder(&folder)?;
+    }
+
+    if let Some(name) = dialog.file_name {
+        ret.set_file_name(&make_utf16(name))?;
+    }
+
+    Ok(ret)
+}
+
+/// See [`crate::FileDialog::pick_files`].
+pub fn pick_files(
+    dialog: &crate::FileDialog,
+    parent: HWND,
+    multiple: bool,
+) -> Result<Option<Vec<PathBuf>>, Error> {
+    use windows_sys::Win32::UI::Shell::{FOS_ALLOWMULTISELECT, FOS_FILEMUSTEXIST};
+
+    let _com = ComInit::new()?;
+    let mut file_dialog = create_file_dialog(dialog, FileDialogKind::Open)?;
+
+    let mut options = FOS_FILEMUSTEXIST;
+    if multiple {
+        options |= FOS_ALLOWMULTISELECT;
+    }
+    file_dialog.add_options(options)?;
+
+    if !file_dialog.show(parent)? {
+        return Ok(None);
+    }
+
+    let paths = file_dialog
+        .results()?
+        .iter()
+        .filter_map(ShellItem::path)
+        .collect();
+    Ok(Some(paths))
+}
+
+/// See [`crate::FileDialog::save_file`].
+pub fn save_file(dialog: &crate::FileDialog, parent: HWND) -> Result<Option<PathBuf>, Error> {
+    use windows_sys::Win32::UI::Shell::FOS_OVERWRITEPROMPT;
+
+    let _com = ComInit::new()?;
+    let mut file_dialog = create_file_dialog(dialog, FileDialogKind::Save)?;
+    file_dialog.add_options(FOS_OVERWRITEPROMPT)?;
+
+    if !file_dialog.show(parent)? {
+        return Ok(None);
+    }
+
+    Ok(file_dialog.result()?.path())
+}
+
+/// See [`crate::FileDialog::pick_folder`].
+pub fn pick_folder(dialog: &crate::FileDialog, parent: HWND) -> Result<Option<PathBuf>, Error> {
+    let _com = ComInit::new()?;
+    let mut file_dialog = create_file_dialog(dialog, FileDialogKind::Folder)?;
+
+    if !file_dialog.show(parent)? {
+        return Ok(None);
+    }
+
+    Ok(file_dialog.result()?.path())
+}
@@ -0,0 +1,303 @@
+use std::ffi::c_void;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use windows_sys::core::{GUID, HRESULT};
+use windows_sys::Win32::Foundation::{E_NOINTERFACE, HWND, POINT, POINTL, S_OK};
+use windows_sys::Win32::Graphics::Gdi::ScreenToClient;
+use windows_sys::Win32::System::Ole::*;
+
+use super::clipboard::{decode_drop_files, decode_text, register_format};
+use super::com::{DataObject, IDropTargetVtbl, IID_IDROP_TARGET, IID_IUNKNOWN};
+use super::wndproc::State;
+use super::Error;
+use crate::{DropEffect, PhysicalPosition};
+
+/// The [`crate::DragData`] implementation for Windows.
+#[derive(Clone, PartialEq)]
+pub struct DragData(DataObject);
+
+impl DragData {
+    /// See [`crate::DragData::has_text`].
+    #[inline]
+    pub fn has_text(&self) -> bool {
+        self.0.has(CF_UNICODETEXT)
+    }
+
+    /// See [`crate::DragData::text`].
+    pub fn text(&self) -> Option<String> {
+        Some(decode_text(&self.0.get(CF_UNICODETEXT)?))
+    }
+
+    /// See [`crate::DragData::has_files`].
+    #[inline]
+    pub fn has_files(&self) -> bool {
+        self.0.has(CF_HDROP)
+    }
+
+    /// See [`crate::DragData::files`].
+    pub fn files(&self) -> Option<Vec<PathBuf>> {
+        decode_drop_files(&self.0.get(CF_HDROP)?)
+    }
+
+    /// See [`crate::DragData::has_format`].
+    pub fn has_format(&self, format: &str) -> bool {
+        register_format(format).is_ok_and(|format| self.0.has(format as u16))
+    }
+
+    /// See [`crate::DragData::data`].
+    pub fn data(&self, format: &str) -> Option<Vec<u8>> {
+        self.0.get(register_format(format).ok()? as u16)
+    }
+}
+
+impl fmt::Debug for DragData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragData")
+            .field("has_text", &self.has_text())
+            .field("has_files", &self.has_files())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Converts a [`DropEffect`] into the corresponding `DROPEFFECT` value.
+fn drop_effect_to_raw(effect: DropEffect) -> DROPEFFECT {
+    match effect {
+        DropEffect::None => DROPEFFECT_NONE,
+        DropEffect::Copy => DROPEFFECT_COPY,
+        DropEffect::Move => DROPEFFECT_MOVE,
+        DropEffect::Link => DROPEFFECT_LINK,
+    }
+}
+
+/// Converts a `DROPEFFECT` value into the corresponding [`DropEffect`].
+fn drop_effect_from_raw(effect: DROPEFFECT) -> DropEffect {
+    match effect {
+        DROPEFFECT_COPY => DropEffect::Copy,
+        DROPEFFECT_MOVE => DropEffect::Move,
+        DROPEFFECT_LINK => DropEffect::Link,
+        _ => DropEffect::None,
+    }
+}
+
+/// An implementation of the `IDropTarget` interface that converts the notifications it receives
+/// into [`crate::Event`]s.
+#[repr(C)]
+struct DropTarget {
+    vtbl: *const IDropTargetVtbl,
+    refs: AtomicU32,
+    hwnd: HWND,
+    /// The state of the window.
+    ///
+    /// The drop target is revoked when the window is destroyed, before the state is dropped.
+    state: *mut State,
+}
+
+static DROP_TARGET_VTBL: IDropTargetVtbl = IDropTargetVtbl {
+    query_interface: DropTarget::query_interface,
+    add_ref: DropTarget::add_ref,
+    release: DropTarget::release,
+    drag_enter: DropTarget::drag_enter,
+    drag_over: DropTarget::drag_over,
+    drag_leave: DropTarget::drag_leave,
+    drop: DropTarget::drop,
+};
+
+impl DropTarget {
+    /// Converts the provided point from screen coordinates to client coordinates.
+    fn to_client(&self, pt: POINTL) -> PhysicalPosition {
+        let mut point = POINT { x: pt.x, y: pt.y };
+        unsafe { ScreenToClient(self.hwnd, &mut point) };
+        PhysicalPosition::new(point.x, point.y)
+    }
+
+    /// Returns the effect chosen by the window, restricted to the effects allowed by the source.
+    ///
+    /// # Safety
+    ///
+    /// `effect` must be a valid pointer, which contains the allowed effects on input.
+    unsafe fn apply_effect(&self, effect: *mut u32) -> DropEffect {
+        let state = unsafe { &*self.state };
+        let chosen = drop_effect_to_raw(state.drop_effect());
+
+        unsafe {
+            if *effect & chosen == 0 {
+                *effect = DROPEFFECT_NONE;
+            } else {
+                *effect = chosen;
+            }
+
+            drop_effect_from_raw(*effect)
+        }
+    }
+
+    unsafe extern "system" fn query_interface(
+        this: *mut c_void,
+        iid: *const GUID,
+        out: *mut *mut c_void,
+    ) -> HRESULT {
+        unsafe {
+            let iid = &*iid;
+            let is = |other: &GUID| {
+                (iid.data1, iid.data2, iid.data3, iid.data4)
+                    == (other.data1, other.data2, other.data3, other.data4)
+            };
+
+            if is(&IID_IUNKNOWN) || is(&IID_IDROP_TARGET) {
+                Self::add_ref(this);
+                *out = this;
+                S_OK
+            } else {
+                *out = std::ptr::null_mut();
+                E_NOINTERFACE
+            }
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut c_void) -> u32 {
+        let this = unsafe { &*(this as *const Self) };
+        this.refs.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    unsafe extern "system" fn release(this: *mut c_void) -> u32 {
+        let refs = unsafe {
+            (*(this as *const Self))
+                .refs
+                .fetch_sub(1, Ordering::Release)
+                - 1
+        };
+
+        if refs == 0 {
+            std::sync::atomic::fence(Ordering::Acquire);
+            drop(unsafe { Box::from_raw(this as *mut Self) });
+        }
+
+        refs
+    }
+
+    unsafe extern "system" fn drag_enter(
+        this: *mut c_void,
+        data: *mut c_void,
+        _keys: u32,
+        pt: POINTL,
+        effect: *mut u32,
+    ) -> HRESULT {
+        unsafe {
+            let this = &*(this as *const Self);
+            let position = this.to_client(pt);
+            let data = crate::DragData(DragData(DataObject::from_raw(data)));
+            (*this.state).send_event(crate::Event::DragEntered { position, data });
+            this.apply_effect(effect);
+        }
+
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_over(
+        this: *mut c_void,
+        _keys: u32,
+        pt: POINTL,
+        effect: *mut u32,
+    ) -> HRESULT {
+        unsafe {
+            let this = &*(this as *const Self);
+            let position = this.to_client(pt);
+            (*this.state).send_event(crate::Event::DragMoved { position });
+            this.apply_effect(effect);
+        }
+
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_leave(this: *mut c_void) -> HRESULT {
+        unsafe {
+            let this = &*(this as *const Self);
+            (*this.state).send_event(crate::Event::DragLeft);
+        }
+
+        S_OK
+    }
+
+    unsafe extern "system" fn drop(
+        this: *mut c_void,
+        data: *mut c_void,
+        _keys: u32,
+        pt: POINTL,
+        effect: *mut u32,
+    ) -> HRESULT {
+        unsafe {
+            let this = &*(this as *const Self);
+            let position = this.to_client(pt);
+
+            let event = match this.apply_effect(effect) {
+                DropEffect::None => crate::Event::DragLeft,
+                effect => crate::Event::Dropped {
+                    position,
+                    data: crate::DragData(DragData(DataObject::from_raw(data))),
+                    effect,
+                },
+            };
+            (*this.state).send_event(event);
+        }
+
+        S_OK
+    }
+}
+
+/// Registers the provided window as a drop target.
+///
+/// The notifications received by the drop target are sent as events through `state`. The
+/// registration must be revoked with [`revoke_drop_target`] when the window is destroyed.
+///
+/// # Safety
+///
+/// `state` must remain valid until [`revoke_drop_target`] is called.
+pub unsafe fn register_drop_target(hwnd: HWND, state: *mut State) -> Result<(), Error> {
+    // OLE must be initialized on the thread that owns the window.
+    let hr = OLE_INIT.with(|init| init.0);
+    if hr < 0 {
+        return Err(Error::from_hresult(hr));
+    }
+
+    let target = Box::into_raw(Box::new(DropTarget {
+        vtbl: &DROP_TARGET_VTBL,
+        refs: AtomicU32::new(1),
+        hwnd,
+        state,
+    }));
+
+    // The system keeps its own reference to the drop target.
+    let hr = unsafe { RegisterDragDrop(hwnd, target as *mut c_void) };
+    unsafe { DropTarget::release(target as *mut c_void) };
+
+    if hr < 0 {
+        Err(Error::from_hresult(hr))
+    } else {
+        Ok(())
+    }
+}
+
+/// Revokes a drop target registered with [`register_drop_target`].
+pub fn revoke_drop_target(hwnd: HWND) {
+    unsafe { RevokeDragDrop(hwnd) };
+}
+
+/// Keeps OLE initialized on the current thread until it exits.
+///
+/// This holds the result of `OleInitialize`, which fails if the thread has been initialized for
+/// the multithreaded COM apartment. Drag-and-drop is unavailable on such threads.
+struct OleInit(HRESULT);
+
+impl Drop for OleInit {
+    fn drop(&mut self) {
+        if self.0 >= 0 {
+            unsafe { OleUninitialize() };
+        }
+    }
+}
+
+thread_local! {
+    /// Initializes OLE once per thread, the first time a drop target is registered on it.
+    static OLE_INIT: OleInit = OleInit(unsafe { OleInitialize(std::ptr::null()) });
+}
@@ -1,6 +1,18 @@
 use std::fmt;
 
-use windows_sys::Win32::Foundation::{GetLastError, SetLastError, ERROR_SUCCESS, WIN32_ERROR};
+use windows_sys::core::HRESULT;
+use windows_sys::Win32::Foundation::{
+    GetLastError, SetLastError, ERROR_ACCESS_DENIED, ERROR_ALREADY_EXISTS,
+    ERROR_CALL_NOT_IMPLEMENTED, ERROR_CLASS_ALREADY_EXISTS, ERROR_CLASS_DOES_NOT_EXIST,
+    ERROR_FILE_EXISTS, ERROR_FILE_NOT_FOUND, ERROR_HOTKEY_ALREADY_REGISTERED, ERROR_INVALID_FLAGS,
+    ERROR_INVALID_HANDLE, ERROR_INVALID_INDEX, ERROR_INVALID_PARAMETER,
+    ERROR_INVALID_WINDOW_HANDLE, ERROR_NOT_ENOUGH_MEMORY, ERROR_NOT_ENOUGH_QUOTA,
+    ERROR_NOT_SUPPORTED, ERROR_NO_MORE_USER_HANDLES, ERROR_NO_SYSTEM_RESOURCES, ERROR_OUTOFMEMORY,
+    ERROR_PATH_NOT_FOUND, ERROR_PRIVILEGE_NOT_HELD, ERROR_PROC_NOT_FOUND, ERROR_SUCCESS,
+    ERROR_TIMEOUT, E_NOTIMPL, E_POINTER, WAIT_TIMEOUT, WIN32_ERROR,
+};
+
+use crate::ErrorKind;
 
 /// The error type on the Windows platform.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -10,6 +22,20 @@ impl Error {
     /// The success error code.
     pub const SUCCESS: Self = Self(ERROR_SUCCESS);
 
+    /// Creates an [`Error`] from the provided `HRESULT`.
+    ///
+    /// `HRESULT`s that wrap a Win32 error code are unwrapped. Other values are kept as-is, which
+    /// the system is still able to describe.
+    pub fn from_hresult(hr: HRESULT) -> Self {
+        let hr = hr as u32;
+
+        if hr & 0xFFFF_0000 == 0x8007_0000 {
+            Self(hr & 0xFFFF)
+        } else {
+            Self(hr)
+        }
+    }
+
     /// Returns the last error code that occured on the current thread.
     #[inline]
     pub fn last() -> Self {
@@ -22,6 +48,47 @@ impl Error {
         unsafe { SetLastError(self.0) };
     }
 
+    /// See [`crate::Error::raw_os_error`].
+    #[inline]
+    pub fn raw_os_error(self) -> Option<i32> {
+        Some(self.0 as i32)
+    }
+
+    /// See [`crate::Error::kind`].
+    pub fn kind(self) -> ErrorKind {
+        // `HRESULT`s that do not wrap a Win32 error code are kept as-is.
+        const E_NOTIMPL_CODE: u32 = E_NOTIMPL as u32;
+        const E_POINTER_CODE: u32 = E_POINTER as u32;
+
+        match self.0 {
+            ERROR_NOT_SUPPORTED
+            | ERROR_CALL_NOT_IMPLEMENTED
+            | ERROR_PROC_NOT_FOUND
+            | E_NOTIMPL_CODE => ErrorKind::NotSupported,
+            ERROR_ACCESS_DENIED | ERROR_PRIVILEGE_NOT_HELD => ErrorKind::PermissionDenied,
+            ERROR_INVALID_PARAMETER
+            | ERROR_INVALID_FLAGS
+            | ERROR_INVALID_INDEX
+            | ERROR_INVALID_HANDLE
+            | ERROR_INVALID_WINDOW_HANDLE
+            | E_POINTER_CODE => ErrorKind::InvalidArgument,
+            ERROR_NOT_ENOUGH_MEMORY
+            | ERROR_OUTOFMEMORY
+            | ERROR_NO_SYSTEM_RESOURCES
+            | ERROR_NOT_ENOUGH_QUOTA
+            | ERROR_NO_MORE_USER_HANDLES => ErrorKind::OutOfResources,
+            ERROR_FILE_NOT_FOUND | ERROR_PATH_NOT_FOUND | ERROR_CLASS_DOES_NOT_EXIST => {
+                ErrorKind::NotFound
+            }
+            ERROR_ALREADY_EXISTS
+            | ERROR_FILE_EXISTS
+            | ERROR_CLASS_ALREADY_EXISTS
+            | ERROR_HOTKEY_ALREADY_REGISTERED => ErrorKind::AlreadyExists,
+            ERROR_TIMEOUT | WAIT_TIMEOUT => ErrorKind::TimedOut,
+            _ => ErrorKind::Os,
+        }
+    }
+
     /// Returns the message associated with the error, as a raw UTF-16 string.
     pub fn read_message(self, buf: &mut [u16]) -> Result<usize, Error> {
         use windows_sys::Win32::System::Diagnostics::Debug::*;
@@ -48,15 +115,20 @@ impl Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use std::ffi::OsString;
-        use std::os::windows::ffi::OsStringExt;
+        use fmt::Write;
+
+        /// The maximum length of a message, in UTF-16 code units.
+        const MAX_LEN: usize = 1024;
 
-        let mut buf = Box::new([0u16; 1024]);
-        let len = self.read_message(buf.as_mut()).map_err(|_| fmt::Error)?;
-        let message = OsString::from_wide(&buf[..len]);
-        let s = message.to_str().ok_or(fmt::Error)?;
+        let mut wide = [0u16; MAX_LEN];
+        let Ok(len) = self.read_message(&mut wide) else {
+            // Not every error code has a message, such as those of unknown `HRESULT`s.
+            return write!(f, "OS error {:#x}", self.0);
+        };
 
-        f.pad(s)
+        char::decode_utf16(wide[..len].iter().copied())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .try_for_each(|c| f.write_char(c))
     }
 }
 
@@ -1,19 +1,40 @@
 use std::mem::size_of;
+use std::sync::{Mutex, PoisonError};
 
 use bitflags::bitflags;
-use windows_sys::Win32::Foundation::{HMODULE, HWND, RECT};
+use windows_sys::Win32::Foundation::{HMODULE, HWND, LPARAM, POINT, RECT, WPARAM};
+use windows_sys::Win32::Graphics::Dwm::{
+    DWMSBT_AUTO, DWMSBT_MAINWINDOW, DWMSBT_NONE, DWMSBT_TABBEDWINDOW, DWMSBT_TRANSIENTWINDOW,
+    DWMWCP_DEFAULT, DWMWCP_DONOTROUND, DWMWCP_ROUND, DWMWCP_ROUNDSMALL,
+};
 use windows_sys::Win32::UI::WindowsAndMessaging::*;
 
-use super::Error;
+use super::com::PropertyStore;
+use super::{Error, Monitor};
 
 /// The function signature of the window procedure.
 pub type WndprocFn = unsafe extern "system" fn(HWND, u32, usize, isize) -> isize;
 
+/// The function signature of the procedure used to subclass windows created by someone else.
+pub type SubclassFn = unsafe extern "system" fn(HWND, u32, usize, isize, usize, usize) -> isize;
+
 /// A wrapper around an [`HWND`].
 ///
 /// It automatically destroys the window upon being dropped.
 pub struct Hwnd {
-    class: WindowClass,
+    /// A reference to the class of the window, kept registered for as long as the window
+    /// exists.
+    ///
+    /// This is `None` when the window was created by someone else.
+    _class: Option<WindowClass>,
+    /// The instance of the module that created the window.
+    hinstance: HMODULE,
+    /// The procedure used to subclass the window, if it was created by someone else.
+    ///
+    /// Such windows are not destroyed when dropped. The subclass is removed instead.
+    subclass: Option<SubclassFn>,
+    /// Whether the window should be left alone when dropped, as it has already been destroyed.
+    forgotten: bool,
     hwnd: HWND,
 }
 
@@ -25,13 +46,22 @@ impl Hwnd {
     /// This function sets the window styles to 0 because the `CreateWindowExW` function implies
     /// some styles which may not be desirable. Instead, use [`Hwnd::set_styles`] to set the
     /// styles of the window.
+    ///
+    /// When `owner` is not null, the created window is owned by it. It always stays on top of
+    /// its owner and is destroyed along with it.
+    ///
+    /// The class of the window is registered with the provided `CS_*` styles, unless another
+    /// window already uses a class with the same name and styles. See [`WindowClass::new`].
     pub fn new(
         title: &str,
         position: Option<(i32, i32)>,
         size: Option<(u32, u32)>,
+        owner: HWND,
+        class_name: &str,
+        class_style: u32,
         wndproc: WndprocFn,
     ) -> Result<Self, Error> {
-        let class = WindowClass::new(wndproc)?;
+        let class = WindowClass::new(class_name, class_style, wndproc)?;
 
         let name = make_utf16(title);
         let (x, y) = position.unwrap_or((CW_USEDEFAULT, CW_USEDEFAULT));
@@ -49,7 +79,7 @@ impl Hwnd {
                 y,
                 width,
                 height,
-                0,
+                owner,
                 0,
                 class.hinstance,
                 std::ptr::null_mut(),
@@ -59,10 +89,50 @@ impl Hwnd {
         if hwnd == 0 {
             Err(Error::last())
         } else {
-            Ok(Self { hwnd, class })
+            Ok(Self {
+                hinstance: class.hinstance,
+                _class: Some(class),
+                subclass: None,
+                forgotten: false,
+                hwnd,
+            })
         }
     }
 
+    /// Creates a new [`Hwnd`] instance from a window created by someone else.
+    ///
+    /// The window is subclassed with the provided procedure, which receives `data` as its last
+    /// argument. The subclass is removed when the returned [`Hwnd`] is dropped, but the window
+    /// itself is not destroyed.
+    ///
+    /// # Safety
+    ///
+    /// The window must belong to the current thread and must remain valid until the returned
+    /// [`Hwnd`] is dropped.
+    #[cfg(feature = "raw-window-handle")]
+    pub unsafe fn adopt(hwnd: HWND, subclass: SubclassFn, data: usize) -> Result<Self, Error> {
+        use windows_sys::Win32::Foundation::ERROR_INVALID_WINDOW_HANDLE;
+        use windows_sys::Win32::UI::Shell::SetWindowSubclass;
+
+        if unsafe { IsWindow(hwnd) } == 0 {
+            return Err(Error(ERROR_INVALID_WINDOW_HANDLE));
+        }
+
+        let hinstance = unsafe { GetWindowLongPtrW(hwnd, GWLP_HINSTANCE) };
+
+        if unsafe { SetWindowSubclass(hwnd, Some(subclass), 0, data) } == 0 {
+            return Err(Error::last());
+        }
+
+        Ok(Self {
+            _class: None,
+            hinstance,
+            subclass: Some(subclass),
+            forgotten: false,
+            hwnd,
+        })
+    }
+
     /// Sets the style of the window.
     pub fn set_styles(&mut self, style: WindowStyles) -> Result<(), Error> {
         unsafe {
@@ -77,6 +147,266 @@ impl Hwnd {
         }
     }
 
+    /// Gets the current styles of the window.
+    #[inline]
+    pub fn get_styles(&self) -> Result<WindowStyles, Error> {
+        WindowStyles::of(self.hwnd)
+    }
+
+    /// Sets the opacity of the window.
+    ///
+    /// The window must have the [`LAYERED`](WindowStyles::LAYERED) style.
+    pub fn set_layered_alpha(&mut self, alpha: u8) -> Result<(), Error> {
+        let ret = unsafe { SetLayeredWindowAttributes(self.hwnd, 0, alpha, LWA_ALPHA) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Makes the system render the content of the window on top of a transparent background,
+    /// respecting the alpha channel of the rendered pixels.
+    pub fn enable_transparency(&mut self) -> Result<(), Error> {
+        use windows_sys::Win32::Graphics::Dwm::*;
+        use windows_sys::Win32::Graphics::Gdi::{CreateRectRgn, DeleteObject};
+
+        unsafe {
+            // Using an empty region makes the blur itself invisible while still enabling the
+            // per-pixel transparency of the window.
+            let region = CreateRectRgn(0, 0, -1, -1);
+
+            let info = DWM_BLURBEHIND {
+                dwFlags: DWM_BB_ENABLE | DWM_BB_BLURREGION,
+                fEnable: 1,
+                hRgnBlur: region,
+                fTransitionOnMaximized: 0,
+            };
+
+            let hr = DwmEnableBlurBehindWindow(self.hwnd, &info);
+            DeleteObject(region);
+
+            if hr < 0 {
+                Err(Error::from_hresult(hr))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Sets where the content of the window can be displayed.
+    ///
+    /// This is used to prevent the window from being captured by screenshots and screen
+    /// recordings.
+    pub fn set_display_affinity(&mut self, affinity: DisplayAffinity) -> Result<(), Error> {
+        let ret = unsafe { SetWindowDisplayAffinity(self.hwnd, affinity as u32) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets the material drawn by the system behind the window.
+    ///
+    /// This is only supported starting with Windows 11, version 22H2.
+    #[inline]
+    pub fn set_system_backdrop(&mut self, backdrop: SystemBackdrop) -> Result<(), Error> {
+        use windows_sys::Win32::Graphics::Dwm::DWMWA_SYSTEMBACKDROP_TYPE;
+        set_dwm_attribute(self.hwnd, DWMWA_SYSTEMBACKDROP_TYPE, backdrop as i32)
+    }
+
+    /// Sets the rounding policy of the corners of the window.
+    ///
+    /// This is only supported starting with Windows 11.
+    #[inline]
+    pub fn set_corner_preference(&mut self, preference: CornerPreference) -> Result<(), Error> {
+        use windows_sys::Win32::Graphics::Dwm::DWMWA_WINDOW_CORNER_PREFERENCE;
+        set_dwm_attribute(self.hwnd, DWMWA_WINDOW_CORNER_PREFERENCE, preference as i32)
+    }
+
+    /// Starts an interactive move or resize of the window, as if the user had pressed the left
+    /// mouse button over the provided non-client area.
+    ///
+    /// The operation follows the mouse until the button is released.
+    pub fn begin_system_drag(&mut self, area: HitTest) -> Result<(), Error> {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::ReleaseCapture;
+
+        let (x, y) = get_cursor_pos()?;
+        let lparam = (x as u16 as u32 | (y as u16 as u32) << 16) as LPARAM;
+
+        unsafe {
+            // The system move loop cannot start while the mouse is captured.
+            ReleaseCapture();
+
+            if PostMessageW(self.hwnd, WM_NCLBUTTONDOWN, area as WPARAM, lparam) == 0 {
+                Err(Error::last())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Shows the system menu of the window at the provided position, in screen coordinates.
+    ///
+    /// The menu items are enabled or disabled according to the current state of the window. If
+    /// the user selects an item, the corresponding command is posted to the window.
+    pub fn show_system_menu(&mut self, x: i32, y: i32, resizable: bool) {
+        unsafe {
+            let menu = GetSystemMenu(self.hwnd, 0);
+            if menu == 0 {
+                return;
+            }
+
+            let maximized = IsZoomed(self.hwnd) != 0;
+            let minimized = IsIconic(self.hwnd) != 0;
+
+            let set_enabled = |item: u32, enabled: bool| {
+                let mut info: MENUITEMINFOW = std::mem::zeroed();
+                info.cbSize = size_of::<MENUITEMINFOW>() as u32;
+                info.fMask = MIIM_STATE;
+                info.fState = if enabled { MFS_ENABLED } else { MFS_DISABLED };
+                SetMenuItemInfoW(menu, item, 0, &info);
+            };
+
+            set_enabled(SC_RESTORE, maximized || minimized);
+            set_enabled(SC_MOVE, !maximized && !minimized);
+            set_enabled(SC_SIZE, resizable && !maximized && !minimized);
+            set_enabled(SC_MINIMIZE, !minimized);
+            set_enabled(SC_MAXIMIZE, resizable && !maximized);
+            set_enabled(SC_CLOSE, true);
+
+            SetMenuDefaultItem(menu, SC_CLOSE, 0);
+
+            // Respect the alignment preferred by the user (right-handed or left-handed).
+            let align = if GetSystemMetrics(SM_MENUDROPALIGNMENT) != 0 {
+                TPM_RIGHTALIGN
+            } else {
+                TPM_LEFTALIGN
+            };
+
+            let cmd = TrackPopupMenu(
+                menu,
+                TPM_RETURNCMD | align,
+                x,
+                y,
+                0,
+                self.hwnd,
+                std::ptr::null(),
+            );
+
+            if cmd != 0 {
+                PostMessageW(self.hwnd, WM_SYSCOMMAND, cmd as WPARAM, 0);
+            }
+        }
+    }
+
+    /// Extends the frame drawn by the system into the client area of the window.
+    ///
+    /// The provided margins are expressed in the following order: `(left, right, top, bottom)`.
+    pub fn extend_frame(
+        &mut self,
+        left: i32,
+        right: i32,
+        top: i32,
+        bottom: i32,
+    ) -> Result<(), Error> {
+        use windows_sys::Win32::Graphics::Dwm::DwmExtendFrameIntoClientArea;
+        use windows_sys::Win32::UI::Controls::MARGINS;
+
+        let margins = MARGINS {
+            cxLeftWidth: left,
+            cxRightWidth: right,
+            cyTopHeight: top,
+            cyBottomHeight: bottom,
+        };
+
+        let hr = unsafe { DwmExtendFrameIntoClientArea(self.hwnd, &margins) };
+
+        if hr < 0 {
+            Err(Error::from_hresult(hr))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets whether the title bar of the window should be drawn using the dark theme.
+    #[inline]
+    pub fn set_dark_mode(&mut self, yes: bool) -> Result<(), Error> {
+        set_dark_mode(self.hwnd, yes)
+    }
+
+    /// Returns the monitor that has the largest area of intersection with the window.
+    #[inline]
+    pub fn monitor(&self) -> Monitor {
+        Monitor::from_window(self.hwnd)
+    }
+
+    /// Makes the window a child of the provided window.
+    ///
+    /// The [`CHILD`](WindowStyles::CHILD) style must have been set beforehand.
+    pub fn set_parent(&mut self, parent: HWND) -> Result<(), Error> {
+        let ret = unsafe { SetParent(self.hwnd, parent) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets the application user model ID of the window, which determines how the taskbar
+    /// groups it with other windows.
+    pub fn set_app_user_model_id(&mut self, id: &str) -> Result<(), Error> {
+        use windows_sys::Win32::Storage::EnhancedStorage::PKEY_AppUserModel_ID;
+
+        PropertyStore::for_window(self.hwnd)?.set_string(&PKEY_AppUserModel_ID, id)
+    }
+
+    /// Prevents the window from being destroyed (or its subclass from being removed) when this
+    /// [`Hwnd`] is dropped.
+    ///
+    /// This must be called once the window has been destroyed by someone else, as its handle may
+    /// be reused for another window.
+    #[inline]
+    pub fn forget(&mut self) {
+        self.forgotten = true;
+    }
+
+    /// Returns the raw handle of the window.
+    #[inline]
+    pub fn raw(&self) -> HWND {
+        self.hwnd
+    }
+
+    /// Posts a `WM_CLOSE` message to the window, which is handled the next time messages are
+    /// processed.
+    pub fn post_close(&mut self) -> Result<(), Error> {
+        let ret = unsafe { PostMessageW(self.hwnd, WM_CLOSE, 0, 0) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the DPI of the window.
+    ///
+    /// The default DPI, corresponding to a scale factor of 1, is 96.
+    #[inline]
+    pub fn dpi(&self) -> u32 {
+        get_window_dpi(self.hwnd)
+    }
+
+    /// Returns whether the window is currently visible.
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        unsafe { IsWindowVisible(self.hwnd) != 0 }
+    }
+
     /// Sets the window's show state.
     ///
     /// # Returns
@@ -90,38 +420,309 @@ impl Hwnd {
         ret != 0
     }
 
-    /// Enables raw input for the window for the mouse and keyboard.
-    pub fn enable_raw_input(&mut self) -> Result<(), Error> {
+    /// Gets the placement of the window, including its show state and its restored position.
+    pub fn get_placement(&self) -> Result<WINDOWPLACEMENT, Error> {
+        let mut placement: WINDOWPLACEMENT = unsafe { std::mem::zeroed() };
+        placement.length = size_of::<WINDOWPLACEMENT>() as u32;
+
+        let ret = unsafe { GetWindowPlacement(self.hwnd, &mut placement) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(placement)
+        }
+    }
+
+    /// Sets the placement of the window, including its show state and its restored position.
+    pub fn set_placement(&mut self, placement: &WINDOWPLACEMENT) -> Result<(), Error> {
+        let ret = unsafe { SetWindowPlacement(self.hwnd, placement) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Shows the window in its minimized state.
+    ///
+    /// When `restore_to_maximized` is set, the window is maximized once it is restored. When
+    /// `activate` is not set, the active window remains active.
+    pub fn show_minimized(
+        &mut self,
+        restore_to_maximized: bool,
+        activate: bool,
+    ) -> Result<(), Error> {
+        let mut placement = self.get_placement()?;
+        placement.showCmd = if activate {
+            SW_SHOWMINIMIZED
+        } else {
+            SW_SHOWMINNOACTIVE
+        };
+        if restore_to_maximized {
+            placement.flags |= WPF_RESTORETOMAXIMIZED;
+        }
+        self.set_placement(&placement)
+    }
+
+    /// Returns whether the window is currently minimized.
+    #[inline]
+    pub fn is_minimized(&self) -> Result<bool, Error> {
+        self.get_placement()
+            .map(|placement| placement.showCmd == SW_SHOWMINIMIZED)
+    }
+
+    /// Returns whether the window is currently maximized.
+    #[inline]
+    pub fn is_maximized(&self) -> Result<bool, Error> {
+        self.get_placement()
+            .map(|placement| placement.showCmd == SW_SHOWMAXIMIZED)
+    }
+
+    /// Returns whether the window is the foreground window (the one the user is currently
+    /// working with).
+    #[inline]
+    pub fn is_foreground(&self) -> bool {
+        unsafe { GetForegroundWindow() == self.hwnd }
+    }
+
+    /// Brings the window to the foreground and activates it.
+    ///
+    /// When the system refuses to change the foreground window, the taskbar button of the window
+    /// flashes until the user activates it instead.
+    ///
+    /// # Returns
+    ///
+    /// This function returns whether the window has actually been brought to the foreground.
+    pub fn set_foreground(&mut self) -> bool {
+        use windows_sys::Win32::System::Threading::{AttachThreadInput, GetCurrentThreadId};
+
+        if unsafe { SetForegroundWindow(self.hwnd) } != 0 {
+            return true;
+        }
+
+        // The system only allows the thread that received the last input event to change the
+        // foreground window. Sharing the input state of the thread of the current foreground
+        // window lifts that restriction in most cases. The threads are detached right after, as
+        // they would otherwise keep sharing their keyboard state and their focus.
+        //
+        // See the remarks of the `SetForegroundWindow` documentation:
+        //
+        //     https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setforegroundwindow
+        //
+        let current = unsafe { GetCurrentThreadId() };
+        let foreground =
+            unsafe { GetWindowThreadProcessId(GetForegroundWindow(), std::ptr::null_mut()) };
+        let attached = foreground != 0
+            && foreground != current
+            && unsafe { AttachThreadInput(current, foreground, 1) } != 0;
+
+        let ret = unsafe { SetForegroundWindow(self.hwnd) } != 0;
+
+        if attached {
+            unsafe { AttachThreadInput(current, foreground, 0) };
+        }
+
+        if !ret {
+            self.flash(Flash::Tray);
+        }
+
+        ret
+    }
+
+    /// Enables or disables mouse and keyboard input to the window.
+    #[inline]
+    pub fn set_enabled(&mut self, yes: bool) {
+        set_window_enabled(self.hwnd, yes);
+    }
+
+    /// Returns whether the window accepts mouse and keyboard input.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::IsWindowEnabled;
+
+        unsafe { IsWindowEnabled(self.hwnd) != 0 }
+    }
+
+    /// Captures the mouse, or releases it if the window currently has it captured.
+    pub fn set_mouse_capture(&mut self, yes: bool) {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+            GetCapture, ReleaseCapture, SetCapture,
+        };
+
+        unsafe {
+            if yes {
+                // The return value is the window that previously had the capture.
+                SetCapture(self.hwnd);
+            } else if GetCapture() == self.hwnd {
+                ReleaseCapture();
+            }
+        }
+    }
+
+    /// Returns whether the window currently has the mouse captured.
+    #[inline]
+    pub fn has_mouse_capture(&self) -> bool {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetCapture;
+
+        unsafe { GetCapture() == self.hwnd }
+    }
+
+    /// Registers a global hotkey that sends `WM_HOTKEY` messages to the window.
+    pub fn register_hotkey(
+        &mut self,
+        id: u16,
+        modifiers: crate::Modifiers,
+        vkey: u16,
+    ) -> Result<(), Error> {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::*;
+
+        // Holding the hotkey down would otherwise send a message for every repetition.
+        let mut flags = MOD_NOREPEAT;
+        if modifiers.contains(crate::Modifiers::SHIFT) {
+            flags |= MOD_SHIFT;
+        }
+        if modifiers.contains(crate::Modifiers::CONTROL) {
+            flags |= MOD_CONTROL;
+        }
+        if modifiers.contains(crate::Modifiers::ALT) {
+            flags |= MOD_ALT;
+        }
+        if modifiers.contains(crate::Modifiers::META) {
+            flags |= MOD_WIN;
+        }
+
+        let ret = unsafe { RegisterHotKey(self.hwnd, id as i32, flags, vkey as u32) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Unregisters a global hotkey previously registered with [`Hwnd::register_hotkey`].
+    pub fn unregister_hotkey(&mut self, id: u16) {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::UnregisterHotKey;
+
+        // This only fails when the hotkey is not registered.
+        unsafe { UnregisterHotKey(self.hwnd, id as i32) };
+    }
+
+    /// Sets whether the input method editor of the system is available to the window.
+    pub fn set_ime_allowed(&mut self, yes: bool) {
+        use windows_sys::Win32::UI::Input::Ime::{ImmAssociateContextEx, IACE_DEFAULT};
+
+        // Associating the null context disables the input method editor.
+        let flags = if yes { IACE_DEFAULT } else { 0 };
+        unsafe { ImmAssociateContextEx(self.hwnd, 0, flags) };
+    }
+
+    /// Sets one of the icons of the window.
+    ///
+    /// The icon must remain valid for as long as it is used by the window.
+    #[inline]
+    pub fn set_icon(&mut self, kind: IconKind, icon: HICON) {
+        unsafe { SendMessageW(self.hwnd, WM_SETICON, kind as WPARAM, icon) };
+    }
+
+    /// Flashes the window's caption and/or taskbar button.
+    ///
+    /// Unless `flash` is [`Flash::Stop`], the window flashes until it comes to the foreground.
+    pub fn flash(&mut self, flash: Flash) {
+        let mut flags = flash as FLASHWINFO_FLAGS;
+        if flags != FLASHW_STOP {
+            flags |= FLASHW_TIMERNOFG;
+        }
+
+        let info = FLASHWINFO {
+            cbSize: size_of::<FLASHWINFO>() as u32,
+            hwnd: self.hwnd,
+            dwFlags: flags,
+            uCount: 0,
+            dwTimeout: 0,
+        };
+
+        // The return value indicates the previous state of the window, not an error.
+        unsafe { FlashWindowEx(&info) };
+    }
+
+    /// Registers the window for raw input from the provided device classes.
+    ///
+    /// The device classes in `remove` are unregistered instead. Device classes that are in
+    /// neither set are left untouched.
+    ///
+    /// The system only sends the raw input of a device class to a single window of the process.
+    /// The last window registered for a device class receives its raw input, until it is
+    /// unregistered. The previous one then receives it again. See [`RAW_INPUT_WINDOWS`].
+    pub fn register_raw_input(
+        &mut self,
+        add: crate::RawInputDevices,
+        remove: crate::RawInputDevices,
+    ) -> Result<(), Error> {
         use windows_sys::Win32::Devices::HumanInterfaceDevice::*;
         use windows_sys::Win32::UI::Input::*;
 
-        unsafe {
-            let devices = [
-                RAWINPUTDEVICE {
-                    usUsagePage: HID_USAGE_PAGE_GENERIC,
-                    usUsage: HID_USAGE_GENERIC_KEYBOARD,
-                    dwFlags: 0,
-                    hwndTarget: self.hwnd,
-                },
-                RAWINPUTDEVICE {
-                    usUsagePage: HID_USAGE_PAGE_GENERIC,
-                    usUsage: HID_USAGE_GENERIC_MOUSE,
-                    dwFlags: 0,
-                    hwndTarget: self.hwnd,
-                },
-            ];
-
-            let ret = RegisterRawInputDevices(
+        let classes = [
+            (crate::RawInputDevices::KEYBOARD, HID_USAGE_GENERIC_KEYBOARD),
+            (crate::RawInputDevices::MOUSE, HID_USAGE_GENERIC_MOUSE),
+        ];
+
+        let mut registered = RAW_INPUT_WINDOWS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let mut devices = Vec::with_capacity(classes.len());
+        for ((class, usage), windows) in classes.into_iter().zip(registered.iter_mut()) {
+            let target = if add.contains(class) {
+                windows.retain(|&hwnd| hwnd != self.hwnd);
+                windows.push(self.hwnd);
+                self.hwnd
+            } else if remove.contains(class) {
+                let Some(index) = windows.iter().position(|&hwnd| hwnd == self.hwnd) else {
+                    continue;
+                };
+
+                // The registration of the device class only changes if the window was its
+                // target. Otherwise, the target keeps receiving its raw input.
+                windows.remove(index);
+                if index != windows.len() {
+                    continue;
+                }
+
+                windows.last().copied().unwrap_or(0)
+            } else {
+                continue;
+            };
+
+            // The target must be null when removing a device class.
+            let flags = if target == 0 { RIDEV_REMOVE } else { 0 };
+
+            devices.push(RAWINPUTDEVICE {
+                usUsagePage: HID_USAGE_PAGE_GENERIC,
+                usUsage: usage,
+                dwFlags: flags,
+                hwndTarget: target,
+            });
+        }
+
+        if devices.is_empty() {
+            return Ok(());
+        }
+
+        let ret = unsafe {
+            RegisterRawInputDevices(
                 devices.as_ptr(),
                 devices.len() as u32,
                 size_of::<RAWINPUTDEVICE>() as u32,
-            );
+            )
+        };
 
-            if ret == 0 {
-                Err(Error::last())
-            } else {
-                Ok(())
-            }
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
         }
     }
 
@@ -141,6 +742,34 @@ impl Hwnd {
         Ok(())
     }
 
+    /// Returns the `CS_*` styles of the class of the window.
+    pub fn class_style(&self) -> u32 {
+        unsafe { GetClassLongPtrW(self.hwnd, GCL_STYLE) as u32 }
+    }
+
+    /// Sets the `CS_*` styles of the class of the window.
+    ///
+    /// This affects every window of the class. The windows created afterwards with the previous
+    /// styles of the class get a class of their own.
+    pub fn set_class_style(&mut self, style: u32) -> Result<(), Error> {
+        Error::SUCCESS.make_last();
+
+        let ret = unsafe { SetClassLongPtrW(self.hwnd, GCL_STYLE, style as isize) };
+
+        if ret == 0 {
+            let err = Error::last();
+            if err != Error::SUCCESS {
+                return Err(err);
+            }
+        }
+
+        if let Some(class) = &self._class {
+            class.set_style(style);
+        }
+
+        Ok(())
+    }
+
     /// Gets the rectangle of the client area of the window.
     ///
     /// # Returns
@@ -158,9 +787,149 @@ impl Hwnd {
         }
     }
 
+    /// Gets the rectangle of the window, including its decorations, in screen coordinates.
+    ///
+    /// # Returns
+    ///
+    /// On success, the bounds of the window are returned in the following order:
+    /// `(left, top, right, bottom)`.
+    pub fn get_window_rect(&self) -> Result<(i32, i32, i32, i32), Error> {
+        let mut rect = unsafe { std::mem::zeroed() };
+        let ret = unsafe { GetWindowRect(self.hwnd, &mut rect) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok((rect.left, rect.top, rect.right, rect.bottom))
+        }
+    }
+
+    /// Converts the provided client-area coordinates into screen coordinates.
+    pub fn client_to_screen(&self, x: i32, y: i32) -> Result<(i32, i32), Error> {
+        use windows_sys::Win32::Graphics::Gdi::ClientToScreen;
+
+        let mut point = POINT { x, y };
+        let ret = unsafe { ClientToScreen(self.hwnd, &mut point) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok((point.x, point.y))
+        }
+    }
+
+    /// Converts the provided screen coordinates into client-area coordinates.
+    pub fn screen_to_client(&self, x: i32, y: i32) -> Result<(i32, i32), Error> {
+        use windows_sys::Win32::Graphics::Gdi::ScreenToClient;
+
+        let mut point = POINT { x, y };
+        let ret = unsafe { ScreenToClient(self.hwnd, &mut point) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok((point.x, point.y))
+        }
+    }
+
+    /// Changes the size, position and Z order of the window.
+    ///
+    /// The parameters that are actually taken into account depend on the provided `flags`.
+    pub fn set_window_pos(
+        &mut self,
+        insert_after: HWND,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        flags: SET_WINDOW_POS_FLAGS,
+    ) -> Result<(), Error> {
+        let ret = unsafe { SetWindowPos(self.hwnd, insert_after, x, y, width, height, flags) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Moves the window to the provided position, in screen coordinates.
+    ///
+    /// The position is the one of the top-left corner of the window, including its
+    /// decorations.
+    #[inline]
+    pub fn set_position(&mut self, x: i32, y: i32) -> Result<(), Error> {
+        self.set_window_pos(0, x, y, 0, 0, SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE)
+    }
+
+    /// Resizes the window to the provided size, including its decorations.
+    #[inline]
+    pub fn set_size(&mut self, width: u32, height: u32) -> Result<(), Error> {
+        self.set_window_pos(
+            0,
+            0,
+            0,
+            width as i32,
+            height as i32,
+            SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE,
+        )
+    }
+
+    /// Changes the position of the window in the Z order.
+    #[inline]
+    pub fn set_z_order(&mut self, insert_after: InsertAfter) -> Result<(), Error> {
+        self.set_window_pos(
+            insert_after as HWND,
+            0,
+            0,
+            0,
+            0,
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )
+    }
+
+    /// Moves and resizes the window to the provided rectangle, in screen coordinates.
+    ///
+    /// The system is notified that the frame of the window may have changed, which is required
+    /// after its styles have been modified.
+    #[inline]
+    pub fn set_outer_rect(
+        &mut self,
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    ) -> Result<(), Error> {
+        self.set_window_pos(
+            0,
+            left,
+            top,
+            right - left,
+            bottom - top,
+            SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+        )
+    }
+
+    /// Notifies the system that the frame of the window may have changed, without moving or
+    /// resizing it.
+    #[inline]
+    pub fn refresh_frame(&mut self) -> Result<(), Error> {
+        self.set_window_pos(
+            0,
+            0,
+            0,
+            0,
+            0,
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+        )
+    }
+
     /// Executes the message handler of the window, calling it for *potentially* multiple messages,
     /// but not necessarily all.
-    pub fn get_messages(&mut self) -> Result<(), Error> {
+    ///
+    /// When `translate` is set, keyboard messages are translated into character messages. System
+    /// keys are always translated, see [`translate_message`].
+    pub fn get_messages(&mut self, translate: bool) -> Result<(), Error> {
         unsafe {
             let mut msg: MSG = std::mem::zeroed();
             let ret = GetMessageW(&mut msg, self.hwnd, 0, 0);
@@ -169,7 +938,7 @@ impl Hwnd {
                 -1 => Err(Error::last()),
                 0 => Ok(()),
                 _ => {
-                    TranslateMessage(&msg);
+                    translate_message(&msg, translate);
                     DispatchMessageW(&msg);
 
                     Ok(())
@@ -181,8 +950,10 @@ impl Hwnd {
     /// Executes the message handler of the window, calling it for *potentially* multiple messages,
     /// but not necessarily all.
     ///
-    /// This function does not block, and returns `true` if a message was handled.
-    pub fn peek_messages(&mut self) -> bool {
+    /// This function does not block, and returns `true` if a message was handled. When
+    /// `translate` is set, keyboard messages are translated into character messages. System keys
+    /// are always translated, see [`translate_message`].
+    pub fn peek_messages(&mut self, translate: bool) -> bool {
         use windows_sys::Win32::UI::WindowsAndMessaging::*;
 
         unsafe {
@@ -192,7 +963,7 @@ impl Hwnd {
             if ret == 0 {
                 false
             } else {
-                TranslateMessage(&msg);
+                translate_message(&msg, translate);
                 DispatchMessageW(&msg);
 
                 true
@@ -203,7 +974,16 @@ impl Hwnd {
 
 impl Drop for Hwnd {
     fn drop(&mut self) {
-        unsafe { DestroyWindow(self.hwnd) };
+        use windows_sys::Win32::UI::Shell::RemoveWindowSubclass;
+
+        if self.forgotten {
+            return;
+        }
+
+        match self.subclass {
+            Some(subclass) => unsafe { RemoveWindowSubclass(self.hwnd, Some(subclass), 0) },
+            None => unsafe { DestroyWindow(self.hwnd) },
+        };
     }
 }
 
@@ -214,6 +994,173 @@ pub enum ShowWindow {
     Hide = SW_HIDE,
     /// Show the window normally, restoring an eventual previous state.
     ShowNormal = SW_SHOWNORMAL,
+    /// Show the window in its current size and position, without activating it.
+    ShowNoActivate = SW_SHOWNA,
+    /// Minimize the window. The next top-level window in the Z order will take the focus.
+    Minimize = SW_MINIMIZE,
+    /// Maximize the window, showing it if it was hidden.
+    Maximize = SW_MAXIMIZE,
+    /// Restore the window to its original size and position if it was minimized or maximized.
+    Restore = SW_RESTORE,
+}
+
+/// The different places the content of a window can be displayed.
+#[repr(u32)]
+pub enum DisplayAffinity {
+    /// The content of the window can be displayed anywhere.
+    None = WDA_NONE,
+    /// The content of the window is only displayed on a monitor. Captures show a black
+    /// rectangle instead.
+    Monitor = WDA_MONITOR,
+    /// The content of the window is only displayed on a monitor. Captures do not include the
+    /// window at all.
+    ///
+    /// This is only supported starting with Windows 10, version 2004.
+    ExcludeFromCapture = WDA_EXCLUDEFROMCAPTURE,
+}
+
+/// The materials the system can draw behind a window.
+#[repr(i32)]
+pub enum SystemBackdrop {
+    /// The system decides which material to draw.
+    Auto = DWMSBT_AUTO,
+    /// No material is drawn.
+    None = DWMSBT_NONE,
+    /// The material used by long-lived windows (Mica).
+    MainWindow = DWMSBT_MAINWINDOW,
+    /// The material used by transient windows, such as menus (Acrylic).
+    TransientWindow = DWMSBT_TRANSIENTWINDOW,
+    /// The material used by windows with tabs in their title bar (Mica Alt).
+    TabbedWindow = DWMSBT_TABBEDWINDOW,
+}
+
+impl From<crate::Backdrop> for SystemBackdrop {
+    fn from(backdrop: crate::Backdrop) -> Self {
+        use crate::Backdrop;
+
+        match backdrop {
+            Backdrop::Auto => Self::Auto,
+            Backdrop::None => Self::None,
+            Backdrop::Mica => Self::MainWindow,
+            Backdrop::Acrylic => Self::TransientWindow,
+            Backdrop::MicaAlt => Self::TabbedWindow,
+        }
+    }
+}
+
+/// The rounding policies of the corners of a window.
+#[repr(i32)]
+pub enum CornerPreference {
+    /// The system decides whether to round the corners.
+    Default = DWMWCP_DEFAULT,
+    /// The corners are never rounded.
+    DoNotRound = DWMWCP_DONOTROUND,
+    /// The corners are rounded when appropriate.
+    Round = DWMWCP_ROUND,
+    /// The corners are rounded with a small radius when appropriate.
+    RoundSmall = DWMWCP_ROUNDSMALL,
+}
+
+impl From<crate::CornerPreference> for CornerPreference {
+    fn from(preference: crate::CornerPreference) -> Self {
+        use crate::CornerPreference as Preference;
+
+        match preference {
+            Preference::Default => Self::Default,
+            Preference::DoNotRound => Self::DoNotRound,
+            Preference::Round => Self::Round,
+            Preference::RoundSmall => Self::RoundSmall,
+        }
+    }
+}
+
+/// The different areas of a window, as reported by the `WM_NCHITTEST` message.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitTest {
+    /// The client area of the window.
+    Client = HTCLIENT,
+    /// The title bar of the window.
+    Caption = HTCAPTION,
+    /// The left border of the window.
+    Left = HTLEFT,
+    /// The right border of the window.
+    Right = HTRIGHT,
+    /// The top border of the window.
+    Top = HTTOP,
+    /// The upper-left corner of the border of the window.
+    TopLeft = HTTOPLEFT,
+    /// The upper-right corner of the border of the window.
+    TopRight = HTTOPRIGHT,
+    /// The bottom border of the window.
+    Bottom = HTBOTTOM,
+    /// The lower-left corner of the border of the window.
+    BottomLeft = HTBOTTOMLEFT,
+    /// The lower-right corner of the border of the window.
+    BottomRight = HTBOTTOMRIGHT,
+    /// The maximize button of the window.
+    MaxButton = HTMAXBUTTON,
+}
+
+impl From<crate::ResizeDirection> for HitTest {
+    fn from(direction: crate::ResizeDirection) -> Self {
+        use crate::ResizeDirection;
+
+        match direction {
+            ResizeDirection::East => Self::Right,
+            ResizeDirection::North => Self::Top,
+            ResizeDirection::NorthEast => Self::TopRight,
+            ResizeDirection::NorthWest => Self::TopLeft,
+            ResizeDirection::South => Self::Bottom,
+            ResizeDirection::SouthEast => Self::BottomRight,
+            ResizeDirection::SouthWest => Self::BottomLeft,
+            ResizeDirection::West => Self::Left,
+        }
+    }
+}
+
+impl From<crate::HitTestArea> for HitTest {
+    fn from(area: crate::HitTestArea) -> Self {
+        use crate::HitTestArea;
+
+        match area {
+            HitTestArea::Client => Self::Client,
+            HitTestArea::Caption => Self::Caption,
+            HitTestArea::ResizeBorder(direction) => Self::from(direction),
+            HitTestArea::MaximizeButton => Self::MaxButton,
+        }
+    }
+}
+
+/// The different icons of a window.
+#[repr(u32)]
+pub enum IconKind {
+    /// The icon displayed in the title bar of the window.
+    Small = ICON_SMALL,
+    /// The icon displayed in the taskbar and in the task switcher.
+    Big = ICON_BIG,
+}
+
+/// The different ways to flash a window.
+#[repr(u32)]
+pub enum Flash {
+    /// Stop flashing the window.
+    Stop = FLASHW_STOP,
+    /// Flash the taskbar button of the window.
+    Tray = FLASHW_TRAY,
+    /// Flash both the caption and the taskbar button of the window.
+    All = FLASHW_ALL,
+}
+
+/// The different places a window can be moved to in the Z order.
+#[repr(isize)]
+pub enum InsertAfter {
+    /// Places the window at the bottom of the Z order.
+    Bottom = HWND_BOTTOM,
+    /// Places the window above all non-topmost windows, making it topmost.
+    Topmost = HWND_TOPMOST,
+    /// Places the window above all non-topmost windows, but behind all topmost windows.
+    NoTopmost = HWND_NOTOPMOST,
 }
 
 #[cfg(feature = "raw-window-handle")]
@@ -222,10 +1169,10 @@ impl rwh::HasWindowHandle for Hwnd {
         use std::num::NonZeroIsize;
 
         let hwnd = unsafe { NonZeroIsize::new_unchecked(self.hwnd) };
-        let hinstance = unsafe { NonZeroIsize::new_unchecked(self.class.hinstance) };
+        let hinstance = NonZeroIsize::new(self.hinstance);
 
         let mut raw = rwh::Win32WindowHandle::new(hwnd);
-        raw.hinstance = Some(hinstance);
+        raw.hinstance = hinstance;
 
         // SAFETY:
         //  The `Hwnd` type guarantees that its inner window object remain valid for its own
@@ -249,9 +1196,51 @@ impl rwh::HasDisplayHandle for Hwnd {
     }
 }
 
-/// Represents a window class.
+/// The windows registered for raw input from the keyboard and from the mouse, in the order they
+/// have been registered.
 ///
-/// This type is mostly a way to have a destructor that unregisters the window class automatically.
+/// Raw input registrations are shared by the whole process, such that a window that stops
+/// receiving raw input must not unregister the device classes other windows still use.
+static RAW_INPUT_WINDOWS: Mutex<[Vec<HWND>; 2]> = Mutex::new([Vec::new(), Vec::new()]);
+
+/// Returns whether `hwnd` is the only window of the process registered for raw input, in which
+/// case it receives the raw input of every device class registered by this crate.
+pub fn is_sole_raw_input_target(hwnd: HWND) -> bool {
+    let registered = RAW_INPUT_WINDOWS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    registered
+        .iter()
+        .all(|windows| windows.iter().all(|&window| window == hwnd))
+}
+
+/// A window class registered by the process.
+struct RegisteredClass {
+    /// The name requested for the class.
+    name: Box<str>,
+    /// The name the class has been registered with.
+    ///
+    /// This differs from `name` when another class with the same name but different styles
+    /// was already registered.
+    registered_name: Box<str>,
+    /// The atom that identifies the class.
+    atom: u16,
+    /// The `CS_*` styles of the class.
+    style: u32,
+    /// The number of [`WindowClass`] instances that refer to the class.
+    refs: usize,
+}
+
+/// The window classes registered by the process.
+///
+/// Window classes are shared by all the threads of the process.
+static CLASSES: Mutex<Vec<RegisteredClass>> = Mutex::new(Vec::new());
+
+/// Represents a reference to a window class.
+///
+/// This type is mostly a way to have a destructor that unregisters the window class
+/// automatically, once no window uses it anymore.
 struct WindowClass {
     hinstance: HMODULE,
     atom: u16,
@@ -259,9 +1248,43 @@ struct WindowClass {
 
 impl WindowClass {
     /// Creates a new [`WindowClass`] instance.
-    pub fn new(wndproc: WndprocFn) -> Result<Self, Error> {
+    ///
+    /// The class is only registered if no other instance refers to a class with the same name
+    /// and `style`. Otherwise, that class is shared. The class is unregistered once the last
+    /// instance that refers to it is dropped.
+    ///
+    /// The class is registered with the provided name, unless that name is already taken by a
+    /// class with different styles. A name derived from `style` is used in that case.
+    pub fn new(name: &str, style: u32, wndproc: WndprocFn) -> Result<Self, Error> {
         let hinstance = get_current_hinstance()?;
 
+        let mut classes = CLASSES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(class) = classes
+            .iter_mut()
+            .find(|class| *class.name == *name && class.style == style)
+        {
+            class.refs += 1;
+            return Ok(Self {
+                hinstance,
+                atom: class.atom,
+            });
+        }
+
+        let registered_name = (0..)
+            .map(|i| match i {
+                0 => name.to_owned(),
+                1 => format!("{name}.{style:x}"),
+                _ => format!("{name}.{style:x}.{i}"),
+            })
+            .find(|candidate| {
+                classes
+                    .iter()
+                    .all(|class| *class.registered_name != **candidate)
+            })
+            .unwrap();
+
+        let utf16_name = make_utf16(&registered_name);
+
         let info = WNDCLASSEXW {
             cbSize: size_of::<WNDCLASSEXW>() as u32,
             cbClsExtra: 0,
@@ -270,9 +1293,9 @@ impl WindowClass {
             hIcon: 0,
             hInstance: hinstance,
             hbrBackground: 0,
-            style: CS_HREDRAW | CS_VREDRAW,
+            style,
             lpszMenuName: std::ptr::null(),
-            lpszClassName: windows_sys::w!("liwin_window_class"),
+            lpszClassName: utf16_name.as_ptr(),
             lpfnWndProc: Some(wndproc),
             hIconSm: 0,
         };
@@ -280,21 +1303,236 @@ impl WindowClass {
         let atom = unsafe { RegisterClassExW(&info) };
 
         if atom == 0 {
-            Err(Error::last())
-        } else {
-            Ok(Self { hinstance, atom })
+            return Err(Error::last());
+        }
+
+        classes.push(RegisteredClass {
+            name: name.into(),
+            registered_name: registered_name.into(),
+            atom,
+            style,
+            refs: 1,
+        });
+
+        Ok(Self { hinstance, atom })
+    }
+
+    /// Records that the `CS_*` styles of the class have been changed.
+    fn set_style(&self, style: u32) {
+        let mut classes = CLASSES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(class) = classes.iter_mut().find(|class| class.atom == self.atom) {
+            class.style = style;
         }
     }
 }
 
 impl Drop for WindowClass {
     fn drop(&mut self) {
-        unsafe { UnregisterClassW(self.atom as *const u16, self.hinstance) };
+        let mut classes = CLASSES.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(index) = classes.iter().position(|class| class.atom == self.atom) else {
+            return;
+        };
+
+        classes[index].refs -= 1;
+        if classes[index].refs == 0 {
+            classes.swap_remove(index);
+            unsafe { UnregisterClassW(self.atom as *const u16, self.hinstance) };
+        }
+    }
+}
+
+/// Returns the position of the cursor, in screen coordinates.
+pub fn get_cursor_pos() -> Result<(i32, i32), Error> {
+    let mut point = POINT { x: 0, y: 0 };
+    let ret = unsafe { GetCursorPos(&mut point) };
+
+    if ret == 0 {
+        Err(Error::last())
+    } else {
+        Ok((point.x, point.y))
+    }
+}
+
+/// Moves the cursor to the provided position, in screen coordinates.
+pub fn set_cursor_pos(x: i32, y: i32) -> Result<(), Error> {
+    let ret = unsafe { SetCursorPos(x, y) };
+
+    if ret == 0 {
+        Err(Error::last())
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the lock keys that are toggled on, as of the last keyboard message retrieved by the
+/// calling thread.
+pub fn get_lock_keys() -> crate::LockKeys {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::*;
+
+    let mut keys = crate::LockKeys::empty();
+
+    // The low-order bit of the state indicates whether the key is toggled.
+    for (vkey, key) in [
+        (VK_CAPITAL, crate::LockKeys::CAPS_LOCK),
+        (VK_NUMLOCK, crate::LockKeys::NUM_LOCK),
+        (VK_SCROLL, crate::LockKeys::SCROLL_LOCK),
+    ] {
+        if unsafe { GetKeyState(vkey as i32) } & 1 != 0 {
+            keys |= key;
+        }
+    }
+
+    keys
+}
+
+/// Enables or disables mouse and keyboard input to the provided window.
+pub fn set_window_enabled(hwnd: HWND, yes: bool) {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::EnableWindow;
+
+    // The return value indicates the previous state of the window, not an error.
+    unsafe { EnableWindow(hwnd, yes as i32) };
+}
+
+/// Returns whether the cursor is currently over the client area of the provided window.
+pub fn is_cursor_over_client_area(hwnd: HWND) -> bool {
+    let Ok((x, y)) = get_cursor_pos() else {
+        return false;
+    };
+
+    unsafe {
+        if WindowFromPoint(POINT { x, y }) != hwnd {
+            return false;
+        }
+
+        let lparam = (x as u16 as u32 | (y as u16 as u32) << 16) as LPARAM;
+        SendMessageW(hwnd, WM_NCHITTEST, 0, lparam) == HTCLIENT as isize
     }
 }
 
+/// Returns the rectangle of the client area of the provided window, in screen coordinates.
+pub fn get_client_screen_rect(hwnd: HWND) -> Result<RECT, Error> {
+    use windows_sys::Win32::Graphics::Gdi::ClientToScreen;
+
+    unsafe {
+        let mut rect: RECT = std::mem::zeroed();
+        if GetClientRect(hwnd, &mut rect) == 0 {
+            return Err(Error::last());
+        }
+
+        let mut origin = POINT { x: 0, y: 0 };
+        if ClientToScreen(hwnd, &mut origin) == 0 {
+            return Err(Error::last());
+        }
+
+        Ok(RECT {
+            left: rect.left + origin.x,
+            top: rect.top + origin.y,
+            right: rect.right + origin.x,
+            bottom: rect.bottom + origin.y,
+        })
+    }
+}
+
+/// Confines the cursor to the provided rectangle, in screen coordinates.
+///
+/// If `None`, the cursor is released and can move anywhere on the screen.
+pub fn clip_cursor(rect: Option<&RECT>) -> Result<(), Error> {
+    let rect = rect.map_or(std::ptr::null(), |rect| rect as *const RECT);
+    let ret = unsafe { ClipCursor(rect) };
+
+    if ret == 0 {
+        Err(Error::last())
+    } else {
+        Ok(())
+    }
+}
+
+/// Blocks until the desktop window manager has presented its next frame.
+pub fn dwm_flush() -> Result<(), Error> {
+    use windows_sys::Win32::Graphics::Dwm::DwmFlush;
+
+    let hr = unsafe { DwmFlush() };
+
+    if hr < 0 {
+        Err(Error::from_hresult(hr))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the thickness of the sizing border of windows, in pixels.
+///
+/// The returned tuple contains the horizontal thickness (of the left and right borders) and the
+/// vertical thickness (of the top and bottom borders).
+pub fn get_resize_border_thickness() -> (i32, i32) {
+    unsafe {
+        let padding = GetSystemMetrics(SM_CXPADDEDBORDER);
+        let x = GetSystemMetrics(SM_CXSIZEFRAME) + padding;
+        let y = GetSystemMetrics(SM_CYSIZEFRAME) + padding;
+        (x, y)
+    }
+}
+
+/// Sets whether the title bar of the provided window should be drawn using the dark theme.
+pub fn set_dark_mode(hwnd: HWND, yes: bool) -> Result<(), Error> {
+    use windows_sys::Win32::Graphics::Dwm::DWMWA_USE_IMMERSIVE_DARK_MODE;
+
+    // Versions of Windows 10 prior to 20H1 used an undocumented attribute.
+    const DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1: i32 = 19;
+
+    set_dwm_attribute(hwnd, DWMWA_USE_IMMERSIVE_DARK_MODE, yes as i32)
+        .or_else(|_| set_dwm_attribute(hwnd, DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1, yes as i32))
+}
+
+/// Sets a 32-bit attribute of the provided window through the Desktop Window Manager.
+fn set_dwm_attribute(hwnd: HWND, attribute: i32, value: i32) -> Result<(), Error> {
+    use windows_sys::Win32::Graphics::Dwm::DwmSetWindowAttribute;
+
+    let hr = unsafe {
+        DwmSetWindowAttribute(
+            hwnd,
+            attribute,
+            &value as *const i32 as *const _,
+            size_of::<i32>() as u32,
+        )
+    };
+
+    if hr < 0 {
+        Err(Error::from_hresult(hr))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns whether the user prefers applications to use the dark theme.
+///
+/// When the preference cannot be determined, `false` is returned.
+pub fn system_prefers_dark_mode() -> bool {
+    use windows_sys::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+    let subkey = make_utf16("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize");
+    let value = make_utf16("AppsUseLightTheme");
+
+    let mut data: u32 = 1;
+    let mut size = size_of::<u32>() as u32;
+
+    let ret = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_DWORD,
+            std::ptr::null_mut(),
+            &mut data as *mut u32 as *mut _,
+            &mut size,
+        )
+    };
+
+    ret == 0 && data == 0
+}
+
 /// Returns the `HMODULE` handle of the current executable.
-fn get_current_hinstance() -> Result<HMODULE, Error> {
+pub fn get_current_hinstance() -> Result<HMODULE, Error> {
     use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
 
     // I don't think `GetModuleHandleW` can fail when it is given a null, but the documentation
@@ -308,6 +1546,17 @@ fn get_current_hinstance() -> Result<HMODULE, Error> {
     }
 }
 
+/// Translates the keyboard messages of `msg` into character messages.
+///
+/// When `text` is not set, only system keys are translated. The `WM_SYSCHAR` messages they
+/// produce are needed by the default window procedure to open the system menu (Alt+Space) and
+/// to handle menu mnemonics, and they never produce text events.
+fn translate_message(msg: &MSG, text: bool) {
+    if text || matches!(msg.message, WM_SYSKEYDOWN | WM_SYSKEYUP) {
+        unsafe { TranslateMessage(msg) };
+    }
+}
+
 bitflags! {
     /// A set of window styles.
     ///
@@ -320,14 +1569,20 @@ bitflags! {
         /// The window should be visible.
         const VISIBLE = WS_VISIBLE as u64;
 
-        /// The window should always appear on top of other windows.
-        const TOPMOST = (WS_EX_TOPMOST as u64) >> 32;
+        /// The window is maximized.
+        const MAXIMIZED = WS_MAXIMIZE as u64;
+
+        /// The window is minimized.
+        const MINIMIZED = WS_MINIMIZE as u64;
 
         /// The window should have a title bar.
         ///
         /// This includes the [`BORDER`](WindowStyles::BORDER) style.
         const CAPTION = (WS_CAPTION | WS_BORDER) as u64;
 
+        /// The window is the child of another window.
+        const CHILD = WS_CHILD as u64;
+
         /// The window should have the default system menu.
         const SYSMENU = WS_SYSMENU as u64;
 
@@ -343,12 +1598,42 @@ bitflags! {
         /// The window has a minimize box.
         const MINIMIZE_BOX = WS_MINIMIZEBOX as u64;
 
+        /// The window is a tool window, which does not appear in the taskbar or in the task
+        /// switcher.
+        const TOOL_WINDOW = (WS_EX_TOOLWINDOW as u64) << 32;
+
+        /// The window does not become the foreground window when the user clicks on it.
+        const NO_ACTIVATE = (WS_EX_NOACTIVATE as u64) << 32;
+
+        /// The window is a layered window, which can be made translucent.
+        const LAYERED = (WS_EX_LAYERED as u64) << 32;
+
+        /// Mouse input goes through the window when it is also layered.
+        const CLICK_THROUGH = (WS_EX_TRANSPARENT as u64) << 32;
+
         /// The window accepts dropped files.
-        const ACCEPT_FILES = (WS_EX_ACCEPTFILES as u64) >> 32;
+        const ACCEPT_FILES = (WS_EX_ACCEPTFILES as u64) << 32;
     }
 }
 
 impl WindowStyles {
+    /// Gets the current styles of the provided window.
+    pub fn of(hwnd: HWND) -> Result<Self, Error> {
+        unsafe {
+            let style = get_window_long(hwnd, GWL_STYLE)?;
+            let ex_style = get_window_long(hwnd, GWL_EXSTYLE)?;
+
+            Ok(Self::from_raw_styles(style as u32, ex_style as u32))
+        }
+    }
+
+    /// Creates a [`WindowStyles`] instance from the provided raw styles.
+    ///
+    /// Bits that do not correspond to any known flag are preserved.
+    fn from_raw_styles(style: WINDOW_STYLE, ex_style: WINDOW_EX_STYLE) -> Self {
+        Self::from_bits_retain(style as u64 | (ex_style as u64) << 32)
+    }
+
     /// Converts this [`WindowStyles`] instance into the corresponding raw styles.
     fn to_raw_styles(self) -> (WINDOW_STYLE, WINDOW_EX_STYLE) {
         let bits = self.bits();
@@ -396,10 +1681,57 @@ impl WindowStyles {
 }
 
 /// Creates a null-terminated UTF-16 string from the given Rust string.
-fn make_utf16(s: &str) -> Vec<u16> {
+pub fn make_utf16(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(Some(0)).collect()
 }
 
+/// Makes the calling thread aware of the DPI of each monitor.
+///
+/// The windows the thread creates are then rendered at the resolution of their monitor instead
+/// of being stretched by the system, and the coordinates the thread works with are physical
+/// pixels. The awareness is kept for the lifetime of the thread, as it also applies to the
+/// functions that are later called on those windows.
+pub fn enable_dpi_awareness() {
+    use windows_sys::Win32::UI::HiDpi::{
+        SetThreadDpiAwarenessContext, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
+    };
+
+    // This fails on versions of Windows older than Windows 10, version 1703, in which case the
+    // awareness of the process is kept.
+    unsafe { SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) };
+}
+
+/// Returns the DPI of the provided window.
+pub fn get_window_dpi(hwnd: HWND) -> u32 {
+    use windows_sys::Win32::UI::HiDpi::GetDpiForWindow;
+
+    // This only fails when the window handle is invalid.
+    match unsafe { GetDpiForWindow(hwnd) } {
+        0 => USER_DEFAULT_SCREEN_DPI,
+        dpi => dpi,
+    }
+}
+
+/// Wraps the `GetWindowLongW` function and returns a standard result.
+///
+/// # Safety
+///
+/// Same as [`set_window_long`].
+unsafe fn get_window_long(hwnd: HWND, index: WINDOW_LONG_PTR_INDEX) -> Result<i32, Error> {
+    Error::SUCCESS.make_last();
+
+    let ret = unsafe { GetWindowLongW(hwnd, index) };
+
+    if ret == 0 {
+        let err = Error::last();
+        if err != Error::SUCCESS {
+            return Err(err);
+        }
+    }
+
+    Ok(ret)
+}
+
 /// Wraps the `SetWindowLongW` function and returns a standard result.
 ///
 /// # Safety
@@ -0,0 +1,128 @@
+use std::fmt;
+use std::sync::Arc;
+
+use windows_sys::Win32::Foundation::ERROR_INVALID_PARAMETER;
+use windows_sys::Win32::Graphics::Gdi::{CreateBitmap, DeleteObject};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    CreateIconIndirect, DestroyIcon, LoadImageW, HICON, ICONINFO, IMAGE_ICON, LR_DEFAULTSIZE,
+};
+
+use super::hwnd::get_current_hinstance;
+use super::Error;
+
+/// The [`crate::Icon`] implementation for Windows.
+#[derive(Clone)]
+pub struct Icon(Arc<IconHandle>);
+
+impl Icon {
+    /// See [`crate::Icon::from_rgba`].
+    pub fn from_rgba(rgba: &[u8], width: u32, height: u32) -> Result<Self, Error> {
+        let handle = create_icon(rgba, width, height, None)?;
+        Ok(Self(Arc::new(IconHandle(handle))))
+    }
+
+    /// See [`crate::Icon::from_resource`].
+    pub fn from_resource(id: u16) -> Result<Self, Error> {
+        let hinstance = get_current_hinstance()?;
+
+        // Icons loaded without `LR_SHARED` are owned by the caller.
+        let handle = unsafe {
+            LoadImageW(
+                hinstance,
+                id as usize as *const u16,
+                IMAGE_ICON,
+                0,
+                0,
+                LR_DEFAULTSIZE,
+            )
+        };
+
+        if handle == 0 {
+            Err(Error::last())
+        } else {
+            Ok(Self(Arc::new(IconHandle(handle))))
+        }
+    }
+
+    /// Returns the raw handle of the icon.
+    #[inline]
+    pub fn handle(&self) -> HICON {
+        self.0 .0
+    }
+}
+
+impl fmt::Debug for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Icon").field(&self.handle()).finish()
+    }
+}
+
+/// An owned `HICON` handle, destroyed when dropped.
+struct IconHandle(HICON);
+
+impl Drop for IconHandle {
+    fn drop(&mut self) {
+        unsafe { DestroyIcon(self.0) };
+    }
+}
+
+/// Creates an icon (or a cursor, if `hotspot` is provided) from the provided RGBA pixels.
+///
+/// The caller is responsible for destroying the returned handle.
+pub fn create_icon(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    hotspot: Option<(u32, u32)>,
+) -> Result<HICON, Error> {
+    if rgba.len() != width as usize * height as usize * 4 {
+        return Err(Error(ERROR_INVALID_PARAMETER));
+    }
+
+    // Windows expects BGRA pixels rather than RGBA.
+    let bgra: Vec<u8> = rgba
+        .chunks_exact(4)
+        .flat_map(|p| [p[2], p[1], p[0], p[3]])
+        .collect();
+
+    // The transparency of 32-bit images is entirely determined by their alpha channel, but the
+    // system still requires a mask. Its rows must be aligned to 16 bits.
+    let mask = vec![0u8; (width as usize).div_ceil(16) * 2 * height as usize];
+
+    unsafe {
+        let color = CreateBitmap(width as i32, height as i32, 1, 32, bgra.as_ptr() as _);
+        if color == 0 {
+            return Err(Error::last());
+        }
+
+        let mask = CreateBitmap(width as i32, height as i32, 1, 1, mask.as_ptr() as _);
+        if mask == 0 {
+            let err = Error::last();
+            DeleteObject(color);
+            return Err(err);
+        }
+
+        let (x_hotspot, y_hotspot) = hotspot.unwrap_or((0, 0));
+
+        let info = ICONINFO {
+            fIcon: hotspot.is_none() as i32,
+            xHotspot: x_hotspot,
+            yHotspot: y_hotspot,
+            hbmMask: mask,
+            hbmColor: color,
+        };
+
+        let ret = CreateIconIndirect(&info);
+        let err = Error::last();
+
+        // The bitmaps are copied by `CreateIconIndirect`.
+        DeleteObject(color);
+        DeleteObject(mask);
+
+        if ret == 0 {
+            Err(err)
+        } else {
+            Ok(ret)
+        }
+    }
+}
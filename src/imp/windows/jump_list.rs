@@ -0,0 +1,174 @@
+use std::ffi::c_void;
+use std::mem::size_of;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+
+use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::System::DataExchange::COPYDATASTRUCT;
+use windows_sys::Win32::UI::Shell::{SHAddToRecentDocs, SHARD_PATHW};
+use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+use super::com::{ComInit, DestinationList, ObjectCollection, ShellLink};
+use super::hwnd::make_utf16;
+use super::Error;
+use crate::JumpList;
+
+/// The value of the `dwData` field of the `WM_COPYDATA` messages that carry the command-line
+/// arguments of another instance of the application.
+pub const COPYDATA_INSTANCE_ARGUMENTS: usize = 0x6C69_7769;
+
+/// See [`crate::JumpList::apply`].
+pub fn apply_jump_list(list: &JumpList) -> Result<(), Error> {
+    let _com = ComInit::new()?;
+
+    let exe = std::env::current_exe().map_err(|err| {
+        use windows_sys::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+        Error(
+            err.raw_os_error()
+                .map_or(ERROR_FILE_NOT_FOUND, |code| code as u32),
+        )
+    })?;
+    let exe: Vec<u16> = exe.as_os_str().encode_wide().chain(Some(0)).collect();
+
+    let mut tasks = ObjectCollection::new()?;
+    for task in list.tasks {
+        let mut link = ShellLink::new(&exe, &make_utf16(task.arguments))?;
+        link.set_description(&make_utf16(task.description))?;
+        link.set_title(task.title)?;
+        tasks.add_link(&link)?;
+    }
+
+    let app_id = list.app_id.map(make_utf16);
+    DestinationList::new(app_id.as_deref())?.replace(&tasks, list.show_recent)
+}
+
+/// See [`crate::add_recent_document`].
+pub fn add_recent_document(path: &Path) {
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    unsafe { SHAddToRecentDocs(SHARD_PATHW as u32, path.as_ptr() as *const c_void) };
+}
+
+/// Returns the name of the window property that marks the windows of the instance named
+/// `name`, as a null-terminated string.
+fn instance_property(name: impl IntoIterator<Item = u16>) -> Vec<u16> {
+    "liwin_instance:"
+        .encode_utf16()
+        .chain(name)
+        .chain(Some(0))
+        .collect()
+}
+
+/// Marks the provided window as a window of the instance named `name`, such that it receives
+/// the arguments forwarded to that instance.
+///
+/// The returned property should be removed with [`unregister_instance_window`] before the
+/// window is destroyed.
+pub fn register_instance_window(hwnd: HWND, name: &str) -> Result<Vec<u16>, Error> {
+    let property = instance_property(name.encode_utf16());
+
+    if unsafe { SetPropW(hwnd, property.as_ptr(), 1) } == 0 {
+        Err(Error::last())
+    } else {
+        Ok(property)
+    }
+}
+
+/// Removes a property returned by [`register_instance_window`] from the provided window.
+pub fn unregister_instance_window(hwnd: HWND, property: &[u16]) {
+    unsafe { RemovePropW(hwnd, property.as_ptr()) };
+}
+
+/// See [`crate::forward_to_running_instance`].
+pub fn forward_to_running_instance(instance_name: &str) -> bool {
+    use windows_sys::Win32::System::Threading::GetCurrentProcessId;
+
+    let name: Vec<u16> = instance_name.encode_utf16().collect();
+    let property = instance_property(name.iter().copied());
+    let current_process = unsafe { GetCurrentProcessId() };
+
+    // Find a top-level window of the instance that belongs to another process.
+    let mut hwnd: HWND = 0;
+    let target = loop {
+        hwnd = unsafe { FindWindowExW(0, hwnd, std::ptr::null(), std::ptr::null()) };
+        if hwnd == 0 {
+            return false;
+        }
+
+        if unsafe { GetPropW(hwnd, property.as_ptr()) } == 0 {
+            continue;
+        }
+
+        let mut process = 0;
+        unsafe { GetWindowThreadProcessId(hwnd, &mut process) };
+        if process != current_process {
+            break (hwnd, process);
+        }
+    };
+
+    // The name of the instance and the arguments are sent as null-terminated UTF-16 strings,
+    // one after the other.
+    let mut payload = name;
+    payload.push(0);
+    for arg in std::env::args_os().skip(1) {
+        payload.extend(arg.encode_wide());
+        payload.push(0);
+    }
+
+    let data = COPYDATASTRUCT {
+        dwData: COPYDATA_INSTANCE_ARGUMENTS,
+        cbData: (payload.len() * size_of::<u16>()) as u32,
+        lpData: payload.as_mut_ptr() as *mut c_void,
+    };
+
+    unsafe {
+        // The running instance is usually expected to bring itself to the foreground.
+        AllowSetForegroundWindow(target.1);
+
+        let mut result = 0;
+        let ret = SendMessageTimeoutW(
+            target.0,
+            WM_COPYDATA,
+            0,
+            &data as *const COPYDATASTRUCT as isize,
+            SMTO_ABORTIFHUNG,
+            5000,
+            &mut result,
+        );
+
+        ret != 0 && result != 0
+    }
+}
+
+/// Decodes the command-line arguments carried by a `WM_COPYDATA` message sent by
+/// [`forward_to_running_instance`].
+///
+/// `None` is returned if the message does not name an instance that `hwnd` belongs to.
+///
+/// # Safety
+///
+/// `data` must be the valid parameter of a `WM_COPYDATA` message.
+pub unsafe fn decode_instance_arguments(hwnd: HWND, data: &COPYDATASTRUCT) -> Option<Vec<String>> {
+    if data.cbData == 0 || data.lpData.is_null() {
+        return None;
+    }
+
+    let payload = unsafe {
+        std::slice::from_raw_parts(
+            data.lpData as *const u16,
+            data.cbData as usize / size_of::<u16>(),
+        )
+    };
+
+    let mut strings = payload
+        .strip_suffix(&[0])
+        .unwrap_or(payload)
+        .split(|&c| c == 0);
+
+    let name = strings.next()?;
+    let property = instance_property(name.iter().copied());
+    if unsafe { GetPropW(hwnd, property.as_ptr()) } == 0 {
+        return None;
+    }
+
+    Some(strings.map(String::from_utf16_lossy).collect())
+}
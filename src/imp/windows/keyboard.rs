@@ -0,0 +1,181 @@
+use windows_sys::Win32::Globalization::{GetLocaleInfoEx, LCIDToLocaleName};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    GetKeyboardLayout, GetKeyboardLayoutList, GetKeyboardLayoutNameW,
+};
+use windows_sys::Win32::UI::TextServices::HKL;
+use windows_sys::Win32::UI::WindowsAndMessaging::KL_NAMELENGTH;
+
+use super::hwnd::make_utf16;
+
+/// The maximum length of a locale name, including the null terminator.
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+/// The [`crate::KeyboardLayout`] implementation for Windows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyboardLayout(HKL);
+
+impl KeyboardLayout {
+    /// Creates a [`KeyboardLayout`] from the provided raw handle.
+    #[inline]
+    pub fn from_raw(hkl: HKL) -> Self {
+        Self(hkl)
+    }
+
+    /// See [`crate::KeyboardLayout::current`].
+    pub fn current() -> Self {
+        Self(unsafe { GetKeyboardLayout(0) })
+    }
+
+    /// See [`crate::KeyboardLayout::installed`].
+    pub fn installed() -> Vec<Self> {
+        unsafe {
+            // The list may change between the two calls, in which case it is truncated.
+            let count = GetKeyboardLayoutList(0, std::ptr::null_mut());
+            let mut list = vec![0; count.max(0) as usize];
+            let count = GetKeyboardLayoutList(list.len() as i32, list.as_mut_ptr());
+            list.truncate(count.max(0) as usize);
+
+            list.into_iter().map(Self).collect()
+        }
+    }
+
+    /// Writes the null-terminated locale name of the language of the layout into the provided
+    /// buffer.
+    fn locale_name(&self, buf: &mut [u16; LOCALE_NAME_MAX_LENGTH]) -> Option<usize> {
+        // The low word of the handle is the language identifier of the layout.
+        let langid = self.0 as u32 & 0xFFFF;
+        let len = unsafe { LCIDToLocaleName(langid, buf.as_mut_ptr(), buf.len() as i32, 0) };
+
+        if len <= 0 {
+            None
+        } else {
+            Some(len as usize)
+        }
+    }
+
+    /// See [`crate::KeyboardLayout::language`].
+    pub fn language(&self) -> Option<String> {
+        let mut buf = [0; LOCALE_NAME_MAX_LENGTH];
+        let len = self.locale_name(&mut buf)?;
+
+        // The returned length includes the null terminator.
+        Some(String::from_utf16_lossy(&buf[..len - 1]))
+    }
+
+    /// See [`crate::KeyboardLayout::identifier`].
+    pub fn identifier(&self) -> Option<String> {
+        // The system only provides the identifier of the active layout.
+        if *self == Self::current() {
+            let mut buf = [0; KL_NAMELENGTH as usize];
+            if unsafe { GetKeyboardLayoutNameW(buf.as_mut_ptr()) } != 0 {
+                return Some(String::from_utf16_lossy(&buf[..buf.len() - 1]));
+            }
+        }
+
+        // The high word of the handle identifies the layout. It is either the language of the
+        // layout, or the "Layout Id" of a variant of the layout (such as Dvorak).
+        let hkl = self.0 as u32;
+        let device = hkl >> 16;
+        match device & 0xF000 {
+            // Input method editors are identified by their whole handle.
+            0xE000 => Some(format!("{hkl:08X}")),
+            0xF000 => find_layout_variant(device & 0x0FFF),
+            _ => Some(format!("{device:08X}")),
+        }
+    }
+
+    /// See [`crate::KeyboardLayout::display_name`].
+    pub fn display_name(&self) -> Option<String> {
+        use windows_sys::Win32::Globalization::LOCALE_SLOCALIZEDDISPLAYNAME;
+
+        let mut locale = [0; LOCALE_NAME_MAX_LENGTH];
+        self.locale_name(&mut locale)?;
+
+        let mut buf = [0u16; 128];
+        let len = unsafe {
+            GetLocaleInfoEx(
+                locale.as_ptr(),
+                LOCALE_SLOCALIZEDDISPLAYNAME,
+                buf.as_mut_ptr(),
+                buf.len() as i32,
+            )
+        };
+
+        if len <= 0 {
+            None
+        } else {
+            Some(String::from_utf16_lossy(&buf[..len as usize - 1]))
+        }
+    }
+}
+
+/// Returns the identifier of the keyboard layout whose "Layout Id" is `id`, as listed in the
+/// registry.
+fn find_layout_variant(id: u32) -> Option<String> {
+    use windows_sys::Win32::Foundation::ERROR_MORE_DATA;
+    use windows_sys::Win32::System::Registry::{
+        RegCloseKey, RegEnumKeyExW, RegGetValueW, RegOpenKeyExW, HKEY_LOCAL_MACHINE, KEY_READ,
+        RRF_RT_REG_SZ,
+    };
+
+    let path = make_utf16("SYSTEM\\CurrentControlSet\\Control\\Keyboard Layouts");
+    let value = make_utf16("Layout Id");
+
+    let mut key = 0;
+    if unsafe { RegOpenKeyExW(HKEY_LOCAL_MACHINE, path.as_ptr(), 0, KEY_READ, &mut key) } != 0 {
+        return None;
+    }
+
+    let mut found = None;
+    for index in 0.. {
+        let mut name = [0u16; KL_NAMELENGTH as usize];
+        let mut name_len = name.len() as u32;
+        let ret = unsafe {
+            RegEnumKeyExW(
+                key,
+                index,
+                name.as_mut_ptr(),
+                &mut name_len,
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+
+        match ret {
+            0 => (),
+            // Keyboard layout identifiers always fit, this is not one.
+            ERROR_MORE_DATA => continue,
+            _ => break,
+        }
+
+        let mut data = [0u16; 8];
+        let mut size = std::mem::size_of_val(&data) as u32;
+        let ret = unsafe {
+            RegGetValueW(
+                key,
+                name.as_ptr(),
+                value.as_ptr(),
+                RRF_RT_REG_SZ,
+                std::ptr::null_mut(),
+                data.as_mut_ptr().cast(),
+                &mut size,
+            )
+        };
+
+        if ret != 0 {
+            continue;
+        }
+
+        // The returned size includes the null terminator.
+        let data = String::from_utf16_lossy(&data[..(size as usize / 2).saturating_sub(1)]);
+        if u32::from_str_radix(&data, 16) == Ok(id) {
+            found = Some(String::from_utf16_lossy(&name[..name_len as usize]));
+            break;
+        }
+    }
+
+    unsafe { RegCloseKey(key) };
+    found
+}
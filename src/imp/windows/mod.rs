@@ -1,11 +1,34 @@
 //! The implementation of the [`liwin`](crate) crate for Windows.
 
+use std::fmt;
+
+mod clipboard;
+mod cursor;
+mod dialog;
+mod drag;
 mod error;
+mod icon;
+mod jump_list;
+mod keyboard;
+mod monitor;
+mod tray;
 mod window;
 
+pub use self::clipboard::*;
+pub use self::cursor::*;
+pub use self::dialog::*;
+pub use self::drag::DragData;
 pub use self::error::*;
+pub use self::icon::*;
+pub use self::jump_list::{add_recent_document, apply_jump_list, forward_to_running_instance};
+pub use self::keyboard::*;
+pub use self::monitor::*;
+pub use self::tray::TrayIcon;
 pub use self::window::*;
 
+#[cfg(feature = "automation")]
+mod automation;
+mod com;
 mod hwnd;
 mod wndproc;
 
@@ -26,6 +49,20 @@ pub struct KeyCode {
     pub extended_flags: u8,
 }
 
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.extended_flags, self.code) {
+            (0x00, 0x00..=0xFF) => write!(f, "0x{:02X}", self.code),
+            (0x01, 0x00..=0xFF) => write!(f, "0xE0{:02X}", self.code),
+            (0x02, 0x00..=0xFF) => write!(f, "0xE1{:02X}", self.code),
+            _ => write!(f, "0x{:02X}{:04X}", self.extended_flags, self.code),
+        }
+    }
+}
+
+/// The scan code of the PAUSE key, which comes with an E1 prefix.
+const PAUSE_CODE: u16 = 0x1D;
+
 impl KeyCode {
     /// See [`crate::KeyCode::from_code`].
     pub const fn from_code(code: u8) -> Self {
@@ -34,4 +71,163 @@ impl KeyCode {
             extended_flags: 0,
         }
     }
+
+    /// Parses a key code formatted with its [`Display`](fmt::Display) implementation.
+    pub fn parse(s: &str) -> Option<Self> {
+        let digits = s.strip_prefix("0x")?;
+        let value = u32::from_str_radix(digits, 16).ok()?;
+        let (prefix, code) = ((value >> 8) as u8, value as u8 as u16);
+
+        let (extended_flags, code) = match (digits.len(), prefix) {
+            (2, _) => (0x00, code),
+            (4, 0xE0) => (0x01, code),
+            (4, 0xE1) => (0x02, code),
+            (6, _) => ((value >> 16) as u8, value as u16),
+            _ => return None,
+        };
+
+        let this = Self {
+            code,
+            extended_flags,
+        };
+
+        // Only the canonical representation is accepted, such that each key code has exactly one
+        // representation.
+        if this.to_string()[2..] != *digits {
+            return None;
+        }
+
+        Some(this)
+    }
+
+    /// See [`crate::KeyCode::to_key`].
+    pub fn to_key(self) -> Option<crate::Key> {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::*;
+
+        let e0 = self.extended_flags & 0x01 != 0;
+
+        // The PAUSE key is the only one with an E1 prefix, which the system does not take into
+        // account when mapping scan codes.
+        let vkey = if self.extended_flags & 0x02 != 0 {
+            if self.code != PAUSE_CODE {
+                return None;
+            }
+
+            VK_PAUSE
+        } else {
+            let code = if e0 { 0xE000 } else { 0 } | self.code as u32;
+            unsafe { MapVirtualKeyW(code, MAPVK_VSC_TO_VK_EX) as u16 }
+        };
+
+        if vkey == 0 {
+            return None;
+        }
+
+        self::wndproc::compute_key(vkey, self.code, e0)
+    }
+
+    /// See [`crate::KeyCode::display_name`].
+    pub fn display_name(self) -> Option<String> {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetKeyNameTextW;
+
+        let mut buf = [0u16; 64];
+        let len =
+            unsafe { GetKeyNameTextW(self.key_name_lparam(), buf.as_mut_ptr(), buf.len() as i32) };
+
+        if len <= 0 {
+            None
+        } else {
+            Some(String::from_utf16_lossy(&buf[..len as usize]))
+        }
+    }
+
+    /// Returns the `lparam` that `GetKeyNameTextW` expects for this key.
+    ///
+    /// The scan code is expected in bits 16-23, and the extended flag in bit 24. The system
+    /// names the PAUSE key after the scan code of NUM LOCK without the extended flag, as the
+    /// E1 prefix cannot be represented.
+    fn key_name_lparam(self) -> i32 {
+        let (code, extended) = if self.code == PAUSE_CODE && self.extended_flags & 0x02 != 0 {
+            (0x45, false)
+        } else {
+            (self.code, self.extended_flags & 0x01 != 0)
+        };
+
+        (code as i32 & 0xFF) << 16 | (extended as i32) << 24
+    }
+
+    /// See [`crate::Key::to_key_code`].
+    pub fn from_key(key: crate::Key) -> Option<Self> {
+        if key == crate::Key::Pause {
+            return Some(Self {
+                code: PAUSE_CODE,
+                extended_flags: 0x02,
+            });
+        }
+
+        // The mapping from virtual-key codes to keys is not trivially reversible, so every scan
+        // code is tried instead.
+        [0x00, 0x01]
+            .into_iter()
+            .flat_map(|extended_flags| {
+                (1..0x80).map(move |code| Self {
+                    code,
+                    extended_flags,
+                })
+            })
+            .find(|code| code.to_key() == Some(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_code_round_trip() {
+        for extended_flags in 0x00..=0x03 {
+            for code in [0x00, 0x1D, 0xFF, 0x100, 0xE01D, 0xFFFF] {
+                let key_code = KeyCode {
+                    code,
+                    extended_flags,
+                };
+                assert_eq!(KeyCode::parse(&key_code.to_string()), Some(key_code));
+            }
+        }
+    }
+
+    #[test]
+    fn key_code_format() {
+        assert_eq!(KeyCode::from_code(0x1E).to_string(), "0x1E");
+        assert_eq!(
+            KeyCode::parse("0xE01D").map(|c| c.extended_flags),
+            Some(0x01)
+        );
+        assert_eq!(
+            KeyCode::parse("0xE11D").map(|c| c.extended_flags),
+            Some(0x02)
+        );
+
+        for s in [
+            "0x1e", "0x1", "0x01E", "0x001E", "0x12AB", "0x00001E", "0x+1E", "1E",
+        ] {
+            assert_eq!(KeyCode::parse(s), None, "{s}");
+        }
+    }
+
+    #[test]
+    fn key_code_name_lparam() {
+        let pause = KeyCode {
+            code: PAUSE_CODE,
+            extended_flags: 0x02,
+        };
+        assert_eq!(pause.key_name_lparam(), 0x45 << 16);
+
+        let right_ctrl = KeyCode {
+            code: 0x1D,
+            extended_flags: 0x01,
+        };
+        assert_eq!(right_ctrl.key_name_lparam(), 0x1D << 16 | 1 << 24);
+        assert_eq!(KeyCode::from_code(0x1E).key_name_lparam(), 0x1E << 16);
+    }
 }
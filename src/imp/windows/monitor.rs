@@ -0,0 +1,403 @@
+use std::mem::size_of;
+
+use windows_sys::Win32::Devices::Display::*;
+use windows_sys::Win32::Foundation::{ERROR_GEN_FAILURE, ERROR_INVALID_PARAMETER, HWND};
+use windows_sys::Win32::Graphics::Gdi::*;
+use windows_sys::Win32::UI::WindowsAndMessaging::USER_DEFAULT_SCREEN_DPI;
+
+use super::Error;
+
+/// The [`crate::Monitor`] implementation for Windows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Monitor(HMONITOR);
+
+impl Monitor {
+    /// Returns the monitor that has the largest area of intersection with the provided window.
+    pub fn from_window(hwnd: HWND) -> Self {
+        Self(unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) })
+    }
+
+    /// Returns the monitor that contains the provided point, or the nearest one.
+    pub fn from_point(x: i32, y: i32) -> Self {
+        use windows_sys::Win32::Foundation::POINT;
+
+        Self(unsafe { MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST) })
+    }
+
+    /// See [`crate::primary_monitor`].
+    pub fn primary() -> Self {
+        use windows_sys::Win32::Foundation::POINT;
+
+        // The monitor would otherwise report the DPI of an unaware application.
+        super::hwnd::enable_dpi_awareness();
+
+        // The primary monitor is the one whose top-left corner is the origin of the virtual
+        // screen.
+        Self(unsafe { MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY) })
+    }
+
+    /// Returns information about the monitor, including the name of its display device.
+    fn info(&self) -> Result<MONITORINFOEXW, Error> {
+        let mut info: MONITORINFOEXW = unsafe { std::mem::zeroed() };
+        info.monitorInfo.cbSize = size_of::<MONITORINFOEXW>() as u32;
+
+        let ret = unsafe { GetMonitorInfoW(self.0, &mut info as *mut _ as *mut MONITORINFO) };
+
+        if ret == 0 {
+            Err(Error::last())
+        } else {
+            Ok(info)
+        }
+    }
+
+    /// Returns the rectangle of the monitor, in the virtual screen.
+    pub fn rect(&self) -> Result<(i32, i32, i32, i32), Error> {
+        let rect = self.info()?.monitorInfo.rcMonitor;
+        Ok((rect.left, rect.top, rect.right, rect.bottom))
+    }
+
+    /// See [`crate::Monitor::position`].
+    pub fn position(&self) -> (i32, i32) {
+        self.rect().map_or((0, 0), |(left, top, _, _)| (left, top))
+    }
+
+    /// See [`crate::Monitor::size`].
+    pub fn size(&self) -> (u32, u32) {
+        self.rect().map_or((0, 0), |(left, top, right, bottom)| {
+            ((right - left) as u32, (bottom - top) as u32)
+        })
+    }
+
+    /// Returns one of the video modes of the monitor.
+    ///
+    /// `index` is either the index of a supported video mode, or `ENUM_CURRENT_SETTINGS`.
+    fn video_mode(&self, device: &[u16; 32], index: u32) -> Option<VideoMode> {
+        let mut mode: DEVMODEW = unsafe { std::mem::zeroed() };
+        mode.dmSize = size_of::<DEVMODEW>() as u16;
+
+        let ret = unsafe { EnumDisplaySettingsExW(device.as_ptr(), index, &mut mode, 0) };
+
+        if ret == 0 {
+            return None;
+        }
+
+        Some(VideoMode {
+            monitor: self.clone(),
+            size: (mode.dmPelsWidth, mode.dmPelsHeight),
+            bit_depth: mode.dmBitsPerPel as u16,
+            refresh_rate: mode.dmDisplayFrequency,
+        })
+    }
+
+    /// Returns the rectangle of the work area of the monitor, in the virtual screen.
+    pub fn work_rect(&self) -> Result<(i32, i32, i32, i32), Error> {
+        let rect = self.info()?.monitorInfo.rcWork;
+        Ok((rect.left, rect.top, rect.right, rect.bottom))
+    }
+
+    /// See [`crate::Monitor::work_area_position`].
+    pub fn work_area_position(&self) -> (i32, i32) {
+        self.work_rect()
+            .map_or((0, 0), |(left, top, _, _)| (left, top))
+    }
+
+    /// See [`crate::Monitor::work_area_size`].
+    pub fn work_area_size(&self) -> (u32, u32) {
+        self.work_rect()
+            .map_or((0, 0), |(left, top, right, bottom)| {
+                ((right - left) as u32, (bottom - top) as u32)
+            })
+    }
+
+    /// Returns the effective DPI of the monitor.
+    pub fn dpi(&self) -> u32 {
+        use windows_sys::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+
+        let mut dpi_x = 0;
+        let mut dpi_y = 0;
+        let hr = unsafe { GetDpiForMonitor(self.0, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) };
+
+        // This only fails when the monitor has been disconnected. Both values are always the
+        // same.
+        if hr < 0 || dpi_x == 0 {
+            USER_DEFAULT_SCREEN_DPI
+        } else {
+            dpi_x
+        }
+    }
+
+    /// See [`crate::Monitor::scale_factor`].
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.dpi() as f64 / USER_DEFAULT_SCREEN_DPI as f64
+    }
+
+    /// Returns the display path that connects the monitor to its display device.
+    ///
+    /// Each path connects a source (known to GDI by the name of the monitor) to a target (the
+    /// physical display).
+    fn display_path(&self) -> Option<DISPLAYCONFIG_PATH_INFO> {
+        use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+
+        let device = self.info().ok()?.szDevice;
+
+        let mut path_count = 0;
+        let mut mode_count = 0;
+        let ret = unsafe {
+            GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count)
+        };
+        if ret != ERROR_SUCCESS {
+            return None;
+        }
+
+        let mut paths: Vec<DISPLAYCONFIG_PATH_INFO> =
+            vec![unsafe { std::mem::zeroed() }; path_count as usize];
+        let mut modes: Vec<DISPLAYCONFIG_MODE_INFO> =
+            vec![unsafe { std::mem::zeroed() }; mode_count as usize];
+        let ret = unsafe {
+            QueryDisplayConfig(
+                QDC_ONLY_ACTIVE_PATHS,
+                &mut path_count,
+                paths.as_mut_ptr(),
+                &mut mode_count,
+                modes.as_mut_ptr(),
+                std::ptr::null_mut(),
+            )
+        };
+        if ret != ERROR_SUCCESS {
+            return None;
+        }
+
+        paths.truncate(path_count as usize);
+        paths.into_iter().find(|path| {
+            let source: Option<DISPLAYCONFIG_SOURCE_DEVICE_NAME> = unsafe {
+                device_info(
+                    DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+                    path.sourceInfo.adapterId,
+                    path.sourceInfo.id,
+                )
+            };
+            source.is_some_and(|source| source.viewGdiDeviceName == device)
+        })
+    }
+
+    /// Queries information about the display device connected to the monitor.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`device_info`].
+    unsafe fn target_info<T>(&self, kind: DISPLAYCONFIG_DEVICE_INFO_TYPE) -> Option<T> {
+        let path = self.display_path()?;
+        unsafe { device_info(kind, path.targetInfo.adapterId, path.targetInfo.id) }
+    }
+
+    /// Returns the names of the display device connected to the monitor.
+    fn target_name(&self) -> Option<DISPLAYCONFIG_TARGET_DEVICE_NAME> {
+        unsafe { self.target_info(DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME) }
+    }
+
+    /// Returns the advanced color information of the display device connected to the monitor.
+    fn advanced_color_info(&self) -> Option<DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO> {
+        unsafe { self.target_info(DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO) }
+    }
+
+    /// See [`crate::Monitor::name`].
+    pub fn name(&self) -> Option<String> {
+        let target = self.target_name()?;
+        non_empty_string(&target.monitorFriendlyDeviceName)
+    }
+
+    /// See [`crate::Monitor::id`].
+    pub fn id(&self) -> Option<String> {
+        let target = self.target_name()?;
+        non_empty_string(&target.monitorDevicePath)
+    }
+
+    /// See [`crate::Monitor::bits_per_color`].
+    pub fn bits_per_color(&self) -> Option<u32> {
+        let info = self.advanced_color_info()?;
+        Some(info.bitsPerColorChannel).filter(|&bits| bits != 0)
+    }
+
+    /// See [`crate::Monitor::is_hdr_supported`].
+    pub fn is_hdr_supported(&self) -> bool {
+        self.advanced_color_info()
+            .is_some_and(|info| unsafe { info.Anonymous.value } & ADVANCED_COLOR_SUPPORTED != 0)
+    }
+
+    /// See [`crate::Monitor::is_hdr_enabled`].
+    pub fn is_hdr_enabled(&self) -> bool {
+        self.advanced_color_info()
+            .is_some_and(|info| unsafe { info.Anonymous.value } & ADVANCED_COLOR_ENABLED != 0)
+    }
+
+    /// See [`crate::Monitor::sdr_white_level`].
+    pub fn sdr_white_level(&self) -> Option<f32> {
+        let level: DISPLAYCONFIG_SDR_WHITE_LEVEL =
+            unsafe { self.target_info(DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL)? };
+
+        // The level is a multiplier of 80 nits, scaled by 1000.
+        Some(level.SDRWhiteLevel as f32 * 80.0 / 1000.0)
+    }
+
+    /// See [`crate::Monitor::current_video_mode`].
+    pub fn current_video_mode(&self) -> Option<VideoMode> {
+        let device = self.info().ok()?.szDevice;
+        self.video_mode(&device, ENUM_CURRENT_SETTINGS)
+    }
+
+    /// See [`crate::Monitor::video_modes`].
+    pub fn video_modes(&self) -> Vec<VideoMode> {
+        let Ok(info) = self.info() else {
+            return Vec::new();
+        };
+
+        let mut modes: Vec<VideoMode> = Vec::new();
+
+        // The same mode is listed multiple times when it is supported with different scaling
+        // and orientation settings, which are not exposed.
+        for index in 0.. {
+            let Some(mode) = self.video_mode(&info.szDevice, index) else {
+                break;
+            };
+
+            if !modes.contains(&mode) {
+                modes.push(mode);
+            }
+        }
+
+        modes
+    }
+}
+
+/// Whether the display device supports advanced color, in the bit field of
+/// `DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO`.
+const ADVANCED_COLOR_SUPPORTED: u32 = 1 << 0;
+/// Whether advanced color is enabled on the display device.
+const ADVANCED_COLOR_ENABLED: u32 = 1 << 1;
+
+/// Wraps the `DisplayConfigGetDeviceInfo` function.
+///
+/// # Safety
+///
+/// `T` must be the structure associated with `kind`, starting with a
+/// `DISPLAYCONFIG_DEVICE_INFO_HEADER`.
+unsafe fn device_info<T>(
+    kind: DISPLAYCONFIG_DEVICE_INFO_TYPE,
+    adapter: windows_sys::Win32::Foundation::LUID,
+    id: u32,
+) -> Option<T> {
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+
+    let mut info: T = unsafe { std::mem::zeroed() };
+    let header = &mut info as *mut T as *mut DISPLAYCONFIG_DEVICE_INFO_HEADER;
+
+    unsafe {
+        (*header).r#type = kind;
+        (*header).size = size_of::<T>() as u32;
+        (*header).adapterId = adapter;
+        (*header).id = id;
+
+        if DisplayConfigGetDeviceInfo(header) != ERROR_SUCCESS as i32 {
+            return None;
+        }
+    }
+
+    Some(info)
+}
+
+/// The [`crate::VideoMode`] implementation for Windows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    monitor: Monitor,
+    size: (u32, u32),
+    bit_depth: u16,
+    refresh_rate: u32,
+}
+
+impl VideoMode {
+    /// See [`crate::VideoMode::monitor`].
+    #[inline]
+    pub fn monitor(&self) -> &Monitor {
+        &self.monitor
+    }
+
+    /// See [`crate::VideoMode::size`].
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// See [`crate::VideoMode::bit_depth`].
+    #[inline]
+    pub fn bit_depth(&self) -> u16 {
+        self.bit_depth
+    }
+
+    /// See [`crate::VideoMode::refresh_rate`].
+    #[inline]
+    pub fn refresh_rate(&self) -> u32 {
+        self.refresh_rate
+    }
+
+    /// Switches the monitor of this video mode to it.
+    ///
+    /// The change is temporary: the desktop video mode is restored when the returned guard is
+    /// dropped, or by the system when the process exits.
+    pub fn apply(&self) -> Result<DisplayModeGuard, Error> {
+        let device = self.monitor.info()?.szDevice;
+
+        let mut mode: DEVMODEW = unsafe { std::mem::zeroed() };
+        mode.dmSize = size_of::<DEVMODEW>() as u16;
+        mode.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT | DM_BITSPERPEL | DM_DISPLAYFREQUENCY;
+        mode.dmPelsWidth = self.size.0;
+        mode.dmPelsHeight = self.size.1;
+        mode.dmBitsPerPel = self.bit_depth as u32;
+        mode.dmDisplayFrequency = self.refresh_rate;
+
+        let ret = unsafe {
+            ChangeDisplaySettingsExW(device.as_ptr(), &mode, 0, CDS_FULLSCREEN, std::ptr::null())
+        };
+
+        match ret {
+            DISP_CHANGE_SUCCESSFUL => Ok(DisplayModeGuard { device }),
+            DISP_CHANGE_BADMODE | DISP_CHANGE_BADFLAGS | DISP_CHANGE_BADPARAM => {
+                Err(Error(ERROR_INVALID_PARAMETER))
+            }
+            _ => Err(Error(ERROR_GEN_FAILURE)),
+        }
+    }
+}
+
+/// Restores the video mode of a display device to the one stored in the registry when dropped.
+pub struct DisplayModeGuard {
+    /// The null-terminated name of the display device.
+    device: [u16; 32],
+}
+
+impl Drop for DisplayModeGuard {
+    fn drop(&mut self) {
+        unsafe {
+            ChangeDisplaySettingsExW(
+                self.device.as_ptr(),
+                std::ptr::null(),
+                0,
+                0,
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+/// Converts the provided null-terminated UTF-16 string into a [`String`].
+///
+/// If the string is empty, `None` is returned.
+fn non_empty_string(buf: &[u16]) -> Option<String> {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+
+    if len == 0 {
+        None
+    } else {
+        Some(String::from_utf16_lossy(&buf[..len]))
+    }
+}
@@ -0,0 +1,340 @@
+use std::mem::size_of;
+use std::sync::Mutex;
+
+use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::UI::Shell::*;
+use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+use super::hwnd::make_utf16;
+use super::{Error, Icon};
+use crate::{MenuItem, MouseButton, TrayIconEvent};
+
+/// The message sent to the window that owns a tray icon when the icon is interacted with.
+pub const WM_TRAY_ICON: u32 = WM_APP + 1;
+
+/// The notification sent when a tray icon is selected with the keyboard.
+const NIN_KEYSELECT: u32 = NIN_SELECT | NINF_KEY;
+
+/// A popup menu attached to a tray icon.
+struct TrayMenu {
+    /// The identifier of the icon the menu is attached to.
+    icon: u16,
+    menu: HMENU,
+    /// The identifiers of the items of the menu, as provided by the application.
+    ///
+    /// The items of the menu are identified by their index in this list, plus one, as `0`
+    /// means that no item has been selected.
+    ids: Vec<u32>,
+}
+
+/// The menus attached to the tray icons of the process.
+///
+/// The window procedure only knows the identifier of the icon that has been interacted with.
+static MENUS: Mutex<Vec<TrayMenu>> = Mutex::new(Vec::new());
+
+/// The tray icons of the process, as they were last sent to the shell.
+///
+/// The icons are lost when the taskbar is re-created, and must be added back from here.
+static ICONS: Mutex<Vec<NOTIFYICONDATAW>> = Mutex::new(Vec::new());
+
+/// The [`crate::TrayIcon`] implementation for Windows.
+#[derive(Debug)]
+pub struct TrayIcon {
+    hwnd: HWND,
+    /// The identifier of the icon.
+    ///
+    /// Notifications report it in the high word of their `lParam`, so it cannot exceed
+    /// 16 bits. Identifiers are reused once their icon is dropped.
+    id: u16,
+    /// The icon displayed in the notification area, kept alive for as long as it is displayed.
+    icon: Icon,
+}
+
+impl TrayIcon {
+    /// See [`crate::TrayIcon::new`].
+    pub fn new(hwnd: HWND, icon: Icon, tooltip: &str) -> Result<Self, Error> {
+        use windows_sys::Win32::Foundation::ERROR_NO_MORE_ITEMS;
+
+        let mut icons = ICONS.lock().unwrap_or_else(|err| err.into_inner());
+
+        let id = (1..=u16::MAX)
+            .find(|&id| icons.iter().all(|data| data.uID != id as u32))
+            .ok_or(Error(ERROR_NO_MORE_ITEMS))?;
+
+        let this = Self { hwnd, id, icon };
+
+        let mut data = this.data();
+        data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP | NIF_SHOWTIP;
+        data.uCallbackMessage = WM_TRAY_ICON;
+        data.hIcon = this.icon.handle();
+        write_tooltip(&mut data, tooltip);
+        // Version 4 reports the position of the icon with the notifications, and sends
+        // `WM_CONTEXTMENU` when the icon is right-clicked or selected with the keyboard.
+        data.Anonymous.uVersion = NOTIFYICON_VERSION_4;
+
+        // The identifier is reserved before the lock is released, as the shell may send
+        // messages to the window while the icon is being added.
+        icons.push(data);
+        drop(icons);
+
+        // On failure, dropping `this` releases the identifier.
+        add(&data)?;
+        Ok(this)
+    }
+
+    /// Returns a [`NOTIFYICONDATAW`] structure that identifies the icon.
+    fn data(&self) -> NOTIFYICONDATAW {
+        let mut data: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+        data.cbSize = size_of::<NOTIFYICONDATAW>() as u32;
+        data.hWnd = self.hwnd;
+        data.uID = self.id as u32;
+        data
+    }
+
+    /// Sends the provided fields of the icon to the shell, and records them such that the icon
+    /// can be added back with them.
+    fn modify(&self, flags: u32, set: impl Fn(&mut NOTIFYICONDATAW)) -> Result<(), Error> {
+        let mut data = self.data();
+        data.uFlags = flags;
+        set(&mut data);
+        notify(NIM_MODIFY, &data)?;
+
+        let mut icons = ICONS.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(data) = icons.iter_mut().find(|data| data.uID == self.id as u32) {
+            set(data);
+        }
+
+        Ok(())
+    }
+
+    /// See [`crate::TrayIcon::id`].
+    #[inline]
+    pub fn id(&self) -> crate::TrayIconId {
+        crate::TrayIconId(self.id)
+    }
+
+    /// See [`crate::TrayIcon::set_icon`].
+    pub fn set_icon(&mut self, icon: Icon) -> Result<(), Error> {
+        self.modify(NIF_ICON, |data| data.hIcon = icon.handle())?;
+        self.icon = icon;
+        Ok(())
+    }
+
+    /// See [`crate::TrayIcon::set_tooltip`].
+    pub fn set_tooltip(&mut self, tooltip: &str) -> Result<(), Error> {
+        self.modify(NIF_TIP | NIF_SHOWTIP, |data| write_tooltip(data, tooltip))
+    }
+
+    /// See [`crate::TrayIcon::set_menu`].
+    pub fn set_menu(&mut self, items: Option<&[MenuItem]>) -> Result<(), Error> {
+        let new = match items {
+            Some(items) => {
+                let mut ids = Vec::new();
+                let menu = create_menu(items, &mut ids)?;
+                Some(TrayMenu {
+                    icon: self.id,
+                    menu,
+                    ids,
+                })
+            }
+            None => None,
+        };
+
+        let mut menus = MENUS.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(index) = menus.iter().position(|m| m.icon == self.id) {
+            unsafe { DestroyMenu(menus.swap_remove(index).menu) };
+        }
+        menus.extend(new);
+
+        Ok(())
+    }
+}
+
+impl Drop for TrayIcon {
+    fn drop(&mut self) {
+        let _ = self.set_menu(None);
+        let _ = notify(NIM_DELETE, &self.data());
+
+        let mut icons = ICONS.lock().unwrap_or_else(|err| err.into_inner());
+        icons.retain(|data| data.uID != self.id as u32);
+    }
+}
+
+/// Adds the tray icons of the provided window back to the notification area.
+///
+/// This must be called when the taskbar has been re-created (for example when Windows Explorer
+/// restarts), as the icons it displayed are lost.
+pub fn restore_tray_icons(hwnd: HWND) {
+    let icons: Vec<NOTIFYICONDATAW> = {
+        let icons = ICONS.lock().unwrap_or_else(|err| err.into_inner());
+        icons
+            .iter()
+            .filter(|data| data.hWnd == hwnd)
+            .copied()
+            .collect()
+    };
+
+    for data in &icons {
+        let _ = add(data);
+    }
+}
+
+/// Adds an icon to the notification area, and selects the version of its notifications.
+fn add(data: &NOTIFYICONDATAW) -> Result<(), Error> {
+    notify(NIM_ADD, data)?;
+    notify(NIM_SETVERSION, data)
+}
+
+/// Creates a popup menu containing the provided items.
+///
+/// The identifiers of the items are pushed to `ids`, such that the identifier of an item of the
+/// menu is its index in `ids` plus one.
+fn create_menu(items: &[MenuItem], ids: &mut Vec<u32>) -> Result<HMENU, Error> {
+    let menu = unsafe { CreatePopupMenu() };
+    if menu == 0 {
+        return Err(Error::last());
+    }
+
+    for item in items {
+        if let Err(err) = append_menu_item(menu, item, ids) {
+            // This also destroys the submenus that have already been appended.
+            unsafe { DestroyMenu(menu) };
+            return Err(err);
+        }
+    }
+
+    Ok(menu)
+}
+
+/// Appends the provided item to a menu.
+fn append_menu_item(menu: HMENU, item: &MenuItem, ids: &mut Vec<u32>) -> Result<(), Error> {
+    let ret = match item {
+        MenuItem::Item {
+            id,
+            label,
+            checked,
+            enabled,
+        } => {
+            let mut flags = MF_STRING;
+            if *checked {
+                flags |= MF_CHECKED;
+            }
+            if !*enabled {
+                flags |= MF_GRAYED;
+            }
+
+            ids.push(*id);
+            let label = make_utf16(label);
+            unsafe { AppendMenuW(menu, flags, ids.len(), label.as_ptr()) }
+        }
+        MenuItem::Separator => unsafe { AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null()) },
+        MenuItem::Submenu { label, items } => {
+            let submenu = create_menu(items, ids)?;
+            let label = make_utf16(label);
+            let ret = unsafe { AppendMenuW(menu, MF_POPUP, submenu as usize, label.as_ptr()) };
+            if ret == 0 {
+                let err = Error::last();
+                unsafe { DestroyMenu(submenu) };
+                return Err(err);
+            }
+            ret
+        }
+    };
+
+    if ret == 0 {
+        Err(Error::last())
+    } else {
+        Ok(())
+    }
+}
+
+/// Shows the menu attached to the provided icon, if any, and returns the identifier of the item
+/// selected by the user.
+///
+/// `Some(None)` is returned if the menu has been closed without selecting an item.
+fn show_menu(hwnd: HWND, icon: u16, x: i32, y: i32) -> Option<Option<u32>> {
+    // The lock must not be held while the menu is open, as the menu runs its own message loop.
+    let (menu, ids) = {
+        let menus = MENUS.lock().unwrap_or_else(|err| err.into_inner());
+        let menu = menus.iter().find(|m| m.icon == icon)?;
+        (menu.menu, menu.ids.clone())
+    };
+
+    unsafe {
+        // The menu would not close when clicking outside of it if the window was not in the
+        // foreground.
+        SetForegroundWindow(hwnd);
+
+        // Respect the alignment preferred by the user (right-handed or left-handed).
+        let align = if GetSystemMetrics(SM_MENUDROPALIGNMENT) != 0 {
+            TPM_RIGHTALIGN
+        } else {
+            TPM_LEFTALIGN
+        };
+
+        let cmd = TrackPopupMenuEx(
+            menu,
+            TPM_RETURNCMD | TPM_NONOTIFY | TPM_BOTTOMALIGN | align,
+            x,
+            y,
+            hwnd,
+            std::ptr::null(),
+        );
+
+        // Makes the menu close correctly the next time it is shown.
+        PostMessageW(hwnd, WM_NULL, 0, 0);
+
+        Some(ids.get((cmd as usize).checked_sub(1)?).copied())
+    }
+}
+
+/// Wraps the `Shell_NotifyIconW` function.
+fn notify(message: NOTIFY_ICON_MESSAGE, data: &NOTIFYICONDATAW) -> Result<(), Error> {
+    use windows_sys::Win32::Foundation::ERROR_GEN_FAILURE;
+
+    // The function does not set the last error code.
+    if unsafe { Shell_NotifyIconW(message, data) } == 0 {
+        Err(Error(ERROR_GEN_FAILURE))
+    } else {
+        Ok(())
+    }
+}
+
+/// Writes the provided tooltip into the structure, truncating it if it is too long.
+fn write_tooltip(data: &mut NOTIFYICONDATAW, tooltip: &str) {
+    let mut tip = [0u16; 128];
+    for (dst, src) in tip[..127].iter_mut().zip(tooltip.encode_utf16()) {
+        *dst = src;
+    }
+    data.szTip = tip;
+}
+
+/// Converts the parameters of a [`WM_TRAY_ICON`] message into an event.
+///
+/// Notifications that are not exposed return `None`.
+pub fn tray_icon_event(hwnd: HWND, wparam: usize, lparam: isize) -> Option<crate::Event> {
+    let id = (lparam as u32 >> 16) as u16;
+    let x = wparam as u16 as i16 as i32;
+    let y = (wparam >> 16) as u16 as i16 as i32;
+
+    let event = match lparam as u32 & 0xFFFF {
+        WM_CONTEXTMENU => match show_menu(hwnd, id, x, y) {
+            Some(Some(item)) => TrayIconEvent::MenuItemSelected(item),
+            Some(None) => return None,
+            None => TrayIconEvent::ContextMenu,
+        },
+        WM_LBUTTONUP | NIN_KEYSELECT => TrayIconEvent::Clicked(MouseButton::LEFT),
+        WM_MBUTTONUP => TrayIconEvent::Clicked(MouseButton::MIDDLE),
+        WM_RBUTTONUP => TrayIconEvent::Clicked(MouseButton::RIGHT),
+        WM_LBUTTONDBLCLK => TrayIconEvent::DoubleClicked(MouseButton::LEFT),
+        WM_MBUTTONDBLCLK => TrayIconEvent::DoubleClicked(MouseButton::MIDDLE),
+        WM_RBUTTONDBLCLK => TrayIconEvent::DoubleClicked(MouseButton::RIGHT),
+        _ => return None,
+    };
+
+    Some(crate::Event::TrayIcon {
+        id: crate::TrayIconId(id),
+        event,
+        position: crate::PhysicalPosition::new(x, y),
+    })
+}
@@ -1,44 +1,421 @@
-use super::hwnd::{Hwnd, ShowWindow, WindowStyles};
-use super::wndproc::State;
-use super::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use windows_sys::Win32::Foundation::{ERROR_INVALID_WINDOW_HANDLE, HWND};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    CS_DROPSHADOW, CS_HREDRAW, CS_VREDRAW, HCURSOR, USER_DEFAULT_SCREEN_DPI, WINDOWPLACEMENT,
+};
+
+use super::drag::{register_drop_target, revoke_drop_target};
+use super::hwnd::{
+    dwm_flush, enable_dpi_awareness, get_cursor_pos, get_lock_keys, make_utf16, set_cursor_pos,
+    set_window_enabled, system_prefers_dark_mode, DisplayAffinity, Flash, HitTest, Hwnd, IconKind,
+    InsertAfter, ShowWindow, SystemBackdrop, WindowStyles,
+};
+use super::jump_list::{register_instance_window, unregister_instance_window};
+use super::wndproc::{virtual_key_of, HitTestFn, State};
+use super::{system_cursor, Cursor, DisplayModeGuard, Error, Icon};
+use crate::{
+    Backdrop, CornerPreference, CursorIcon, Fullscreen, InputBackend, Position, RawInputDevices,
+    ResizeDirection, Theme, UserAttentionType, WindowLevel,
+};
 
 /// The [`crate::Window`] implementation for Windows.
 pub struct Window {
-    state: Box<super::wndproc::State>,
+    /// The identifier of the window.
+    id: u64,
+    /// The window itself.
+    ///
+    /// It must be dropped before `state`, as destroying the window sends messages to the
+    /// window procedure.
     hwnd: Hwnd,
+    state: Box<super::wndproc::State>,
+    attributes: Attributes,
+    /// The icon of the window, kept alive for as long as the window uses it.
+    icon: Option<Icon>,
+    /// The icon displayed in the taskbar, if different from `icon`.
+    taskbar_icon: Option<Icon>,
+    /// The overlay icon displayed over the taskbar button, kept alive for as long as it is used.
+    overlay_icon: Option<Icon>,
+    /// The custom cursor of the window, kept alive for as long as the window uses it.
+    cursor: Option<Cursor>,
+    /// The state of the window in fullscreen mode, if it is in fullscreen mode.
+    fullscreen: Option<FullscreenState>,
+    /// The theme explicitly requested for the window, if any.
+    theme: Option<Theme>,
+    /// The owner of the window, if it has been disabled while the window is open.
+    modal_owner: Option<HWND>,
+    /// The device classes the window is registered for raw input from.
+    raw_input: RawInputDevices,
+    /// Whether keyboard messages are translated into text.
+    text_input: bool,
+    /// The global hotkeys registered for the window.
+    hotkeys: Vec<u16>,
+    /// The window property that marks the window as a window of a named instance of the
+    /// application, if any.
+    instance_property: Option<Vec<u16>>,
 }
 
 impl Window {
     /// Creates a new [`Window`] instance.
     pub fn new(config: crate::Config) -> Result<Self, Error> {
-        let styles = make_window_styles(&config);
+        enable_dpi_awareness();
+
+        let parent = parent_hwnd(&config)?;
+        let owner = owner_hwnd(&config)?;
+        let attributes = Attributes::from_config(&config, parent != 0);
+
+        // Fullscreen windows are shown once they cover their monitor, such that they never
+        // appear in their windowed placement.
+        let fullscreen = config.fullscreen.filter(|_| parent == 0);
+
+        // Maximized and minimized windows are shown directly in their state for the same
+        // reason.
+        let show_state = config.attributes.visible
+            && fullscreen.is_none()
+            && (config.attributes.maximized || config.attributes.start_minimized);
+
+        // Windows that must not be activated are shown explicitly once created.
+        let mut styles = make_window_styles(&attributes);
+        if config.attributes.visible
+            && fullscreen.is_none()
+            && !show_state
+            && !config.attributes.no_activate
+        {
+            styles |= WindowStyles::VISIBLE;
+        }
+
+        // Logical sizes and positions are converted using the scale factor of the monitor the
+        // window is placed on.
+        let monitor = config.monitor.filter(|_| parent == 0);
+        let scale = match (monitor, config.attributes.position) {
+            _ if parent != 0 => super::Monitor::from_window(parent).scale_factor(),
+            (Some(monitor), _) => monitor.0.scale_factor(),
+            (None, Some(Position::Physical(p))) => {
+                super::Monitor::from_point(p.x, p.y).scale_factor()
+            }
+            (None, _) => super::Monitor::primary().scale_factor(),
+        };
+
+        // The state is created first, such that the window is always dropped before it.
+        let mut state: Box<super::wndproc::State> = Box::default();
+        state.set_follow_system_theme(config.attributes.theme.is_none());
+        state.set_cursor(system_cursor(CursorIcon::Arrow));
+        state.set_min_size(config.attributes.min_size);
+        state.set_max_size(config.attributes.max_size);
+        state.set_custom_frame(attributes.custom_frame());
+        state.set_lock_keys(get_lock_keys());
+        state.set_input_backend(config.attributes.input_backend);
 
-        let window_size = match config.size {
-            Some((width, height)) => Some(styles.client_to_window_size(width, height)?),
+        // The window is created with a size that respects its constraints, such that it is
+        // never displayed with another one.
+        let window_size = match config.attributes.size {
+            Some(size) => {
+                let (width, height) = size.to_physical(scale).into();
+                let (width, height) = state.clamp_client_size(width, height, scale);
+                Some(attributes.client_to_window_size(styles, width, height)?)
+            }
             None => None,
         };
+        let position = config
+            .attributes
+            .position
+            .map(|p| p.to_physical(scale).into());
+
+        // Child windows are created as regular windows and attached to their parent once their
+        // styles have been set.
+        let owner = if parent == 0 { owner } else { 0 };
+
+        // Windows placed on a specific monitor without an explicit position are centered on it
+        // once their size is known.
+        let mut center_in = None;
+        let position = match monitor {
+            Some(monitor) => {
+                let work = monitor.0.work_rect()?;
+                let (left, top, _, _) = work;
+
+                match (position, window_size) {
+                    (Some((x, y)), _) => Some((left + x, top + y)),
+                    (None, Some(size)) => Some(centered_position(work, size)),
+                    (None, None) => {
+                        center_in = Some(work);
+                        Some((left, top))
+                    }
+                }
+            }
+            None => position,
+        };
 
         let mut hwnd = Hwnd::new(
-            config.title,
-            config.position,
+            &config.attributes.title,
+            position,
             window_size,
+            owner,
+            &config.attributes.class_name,
+            config.platform.class_style,
             super::wndproc::wndproc,
         )?;
 
+        if let Some(id) = config.attributes.app_id.as_deref() {
+            hwnd.set_app_user_model_id(id)?;
+        }
+
+        // The theme is applied before the window is shown, such that its title bar is never
+        // drawn with the wrong colors. Windows that do not support dark title bars are always
+        // drawn using the light theme.
+        let dark = match config.attributes.theme {
+            Some(theme) => theme == Theme::Dark,
+            None => system_prefers_dark_mode(),
+        };
+        let _ = hwnd.set_dark_mode(dark);
+
+        // The icon is set before the window is shown, such that the system never displays the
+        // default one.
+        let icon = config.icon.map(|icon| icon.0);
+        if let Some(icon) = &icon {
+            hwnd.set_icon(IconKind::Small, icon.handle());
+            hwnd.set_icon(IconKind::Big, icon.handle());
+        }
+
+        // The size chosen by the system is only known once the window exists.
+        if let Some(work) = center_in {
+            let (left, top, right, bottom) = hwnd.get_window_rect()?;
+            let size = ((right - left) as u32, (bottom - top) as u32);
+            let (x, y) = centered_position(work, size);
+            hwnd.set_position(x, y)?;
+        }
+
         // Set the window styles separately as the `CreateWindowExW` function seems
         // to imply some styles.
+        //
+        // Changing the styles of the window does not change its outer size, but makes it
+        // visible.
         hwnd.set_styles(styles)?;
 
+        if parent != 0 {
+            hwnd.set_parent(parent)?;
+
+            let (x, y) = position.unwrap_or((0, 0));
+            hwnd.set_position(x, y)?;
+        }
+
+        // Layered windows are not rendered until their attributes are set.
+        if styles.contains(WindowStyles::LAYERED) {
+            hwnd.set_layered_alpha(255)?;
+        }
+
+        if config.attributes.transparent {
+            hwnd.enable_transparency()?;
+        }
+
         // Enable the WM_INPUT message.
-        hwnd.enable_raw_input()?;
+        let raw_input =
+            backend_raw_input(config.attributes.input_backend, config.attributes.raw_input);
+        hwnd.register_raw_input(raw_input, RawInputDevices::empty())?;
 
-        let mut state: Box<super::wndproc::State> = Box::default();
         hwnd.set_userdata(&mut *state as *mut super::wndproc::State as usize)?;
 
-        Ok(Self { hwnd, state })
+        // The window procedure now refers to the state. From this point, the window must be
+        // dropped before the state on every path, which the fields of `Self` guarantee.
+        let mut window = Self {
+            id: next_window_id(),
+            hwnd,
+            state,
+            attributes,
+            icon,
+            taskbar_icon: None,
+            overlay_icon: None,
+            cursor: None,
+            fullscreen: None,
+            theme: config.attributes.theme,
+            modal_owner: None,
+            raw_input: config.attributes.raw_input,
+            text_input: true,
+            hotkeys: Vec::new(),
+            instance_property: None,
+        };
+
+        // Drag-and-drop is not available on threads that use the multithreaded COM apartment,
+        // which should not prevent the window from being created. The window then refuses
+        // dropped files, as it only receives them through its drop target.
+        let state = &mut *window.state as *mut super::wndproc::State;
+        if config.attributes.accept_drops {
+            if unsafe { register_drop_target(window.hwnd.raw(), state) }.is_ok() {
+                window.state.set_drop_target(true);
+            } else {
+                window.attributes.accept_drops = false;
+                window.apply_styles()?;
+            }
+        }
+
+        if window.attributes.custom_frame() {
+            // The frame of the window has already been computed when the styles were set, but
+            // it depends on the state that was not available at that time.
+            let (left, right, top, bottom) = window.attributes.frame_margins();
+            window.hwnd.extend_frame(left, right, top, bottom)?;
+            window.hwnd.refresh_frame()?;
+        }
+
+        // The default size chosen by the system is only known once the window exists.
+        if config.attributes.size.is_none()
+            && (config.attributes.min_size.is_some() || config.attributes.max_size.is_some())
+        {
+            window.enforce_size_constraints();
+        }
+
+        if let Some(name) = config.attributes.instance_name.as_deref() {
+            let property = register_instance_window(window.hwnd.raw(), name)?;
+            window.instance_property = Some(property);
+        }
+
+        // The owner is only disabled once the window exists, as dropping the window is what
+        // enables the owner again.
+        if config.attributes.modal && owner != 0 {
+            set_window_enabled(owner, false);
+            window.modal_owner = Some(owner);
+        }
+
+        if config.attributes.level != WindowLevel::Normal {
+            window.try_set_level(config.attributes.level)?;
+        }
+
+        if show_state {
+            if config.attributes.start_minimized {
+                window
+                    .hwnd
+                    .show_minimized(config.attributes.maximized, !config.attributes.no_activate)?;
+            } else {
+                window.hwnd.show_window(ShowWindow::Maximize);
+            }
+        } else if config.attributes.visible && fullscreen.is_none() && config.attributes.no_activate
+        {
+            window.set_visible_without_activation(true);
+        }
+
+        if let Some(mode) = fullscreen {
+            window.enter_fullscreen(mode)?;
+
+            if config.attributes.visible && config.attributes.no_activate {
+                window.set_visible_without_activation(true);
+            } else if config.attributes.visible {
+                window.set_visible(true);
+            }
+        }
+
+        Ok(window)
+    }
+
+    /// Returns the handle of the window.
+    #[inline]
+    pub fn hwnd(&self) -> HWND {
+        self.hwnd.raw()
     }
 
-    /// See [`crate::Window::set_visible`]
+    /// See [`crate::Window::id`].
+    #[inline]
+    pub fn id(&self) -> crate::WindowId {
+        crate::WindowId(self.id)
+    }
+
+    /// See [`crate::platform::windows::WindowExtWindows::class_style`].
+    #[inline]
+    pub fn class_style(&self) -> u32 {
+        self.hwnd.class_style()
+    }
+
+    /// See [`crate::platform::windows::WindowExtWindows::set_class_style`].
+    #[inline]
+    pub fn set_class_style(&mut self, style: u32) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        self.hwnd.set_class_style(style)
+    }
+
+    /// See [`crate::platform::windows::WindowExtWindows::set_drop_shadow`].
+    pub fn set_drop_shadow(&mut self, yes: bool) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        let style = self.hwnd.class_style();
+        self.hwnd.set_class_style(with_drop_shadow(style, yes))
+    }
+
+    /// See [`crate::Window::from_raw_handle`].
+    #[cfg(feature = "raw-window-handle")]
+    pub unsafe fn from_raw_handle(handle: rwh::RawWindowHandle) -> Result<Self, Error> {
+        use windows_sys::Win32::Foundation::ERROR_INVALID_WINDOW_HANDLE;
+
+        let rwh::RawWindowHandle::Win32(handle) = handle else {
+            return Err(Error(ERROR_INVALID_WINDOW_HANDLE));
+        };
+
+        let mut state: Box<State> = Box::default();
+
+        // The window procedure of the window keeps its own USERDATA field. The state is passed
+        // to the subclass procedure instead.
+        let mut hwnd = unsafe {
+            Hwnd::adopt(
+                handle.hwnd.get(),
+                super::wndproc::subclass_proc,
+                &mut *state as *mut State as usize,
+            )?
+        };
+
+        let attributes = Attributes::from_styles(hwnd.get_styles()?);
+        state.set_custom_frame(attributes.custom_frame());
+        state.set_lock_keys(get_lock_keys());
+
+        // Enable the WM_INPUT message.
+        hwnd.register_raw_input(RawInputDevices::all(), RawInputDevices::empty())?;
+
+        Ok(Self {
+            id: next_window_id(),
+            hwnd,
+            state,
+            attributes,
+            icon: None,
+            taskbar_icon: None,
+            overlay_icon: None,
+            cursor: None,
+            fullscreen: None,
+            theme: None,
+            modal_owner: None,
+            raw_input: RawInputDevices::all(),
+            text_input: true,
+            hotkeys: Vec::new(),
+            instance_property: None,
+        })
+    }
+
+    /// See [`crate::Window::is_open`].
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        !self.state.is_destroyed()
+    }
+
+    /// Returns an error if the window has been destroyed.
+    ///
+    /// The system destroys owned and child windows along with their owner or parent, while their
+    /// [`Window`] is still around. Their handle must then be left alone, as it may have been
+    /// reused by another window.
+    fn ensure_open(&self) -> Result<(), Error> {
+        if self.is_open() {
+            Ok(())
+        } else {
+            Err(Error(ERROR_INVALID_WINDOW_HANDLE))
+        }
+    }
+
+    /// See [`crate::Window::request_close`].
+    pub fn request_close(&mut self) {
+        if !self.is_open() {
+            return;
+        }
+
+        self.hwnd
+            .post_close()
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// See [`crate::Window::set_visible`].
     pub fn set_visible(&mut self, yes: bool) {
         let cmd = if yes {
             ShowWindow::ShowNormal
@@ -49,8 +426,703 @@ impl Window {
         self.hwnd.show_window(cmd);
     }
 
-    /// See [`crate::Window::client_size`]
+    /// See [`crate::Window::set_visible_without_activation`].
+    pub fn set_visible_without_activation(&mut self, yes: bool) {
+        let cmd = if yes {
+            ShowWindow::ShowNoActivate
+        } else {
+            ShowWindow::Hide
+        };
+
+        self.hwnd.show_window(cmd);
+    }
+
+    /// See [`crate::Window::minimize`].
+    pub fn minimize(&mut self) {
+        self.hwnd.show_window(ShowWindow::Minimize);
+    }
+
+    /// See [`crate::Window::maximize`].
+    pub fn maximize(&mut self) {
+        self.hwnd.show_window(ShowWindow::Maximize);
+    }
+
+    /// See [`crate::Window::restore`].
+    pub fn restore(&mut self) {
+        self.hwnd.show_window(ShowWindow::Restore);
+    }
+
+    /// See [`crate::Window::is_minimized`].
+    pub fn is_minimized(&self) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.hwnd
+            .is_minimized()
+            .unwrap_or_else(|err| unexpected_windows_error(err))
+    }
+
+    /// See [`crate::Window::is_maximized`].
+    pub fn is_maximized(&self) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.hwnd
+            .is_maximized()
+            .unwrap_or_else(|err| unexpected_windows_error(err))
+    }
+
+    /// See [`crate::Window::focus`].
+    pub fn focus(&mut self) {
+        if self.is_minimized() {
+            self.restore();
+        }
+
+        self.hwnd.set_foreground();
+    }
+
+    /// See [`crate::Window::has_focus`].
+    pub fn has_focus(&self) -> bool {
+        self.hwnd.is_foreground()
+    }
+
+    /// See [`crate::Window::request_user_attention`].
+    pub fn request_user_attention(&mut self, ty: Option<UserAttentionType>) {
+        let flash = match ty {
+            None => Flash::Stop,
+            Some(UserAttentionType::Informational) => Flash::Tray,
+            Some(UserAttentionType::Critical) => Flash::All,
+        };
+
+        self.hwnd.flash(flash);
+    }
+
+    /// See [`crate::Window::register_hotkey`].
+    pub fn register_hotkey(
+        &mut self,
+        modifiers: crate::Modifiers,
+        key: crate::Key,
+    ) -> Result<crate::HotkeyId, Error> {
+        self.ensure_open()?;
+
+        use windows_sys::Win32::Foundation::ERROR_INVALID_PARAMETER;
+
+        let vkey = virtual_key_of(key).ok_or(Error(ERROR_INVALID_PARAMETER))?;
+
+        // Applications may only use identifiers up to 0xBFFF.
+        let id = (1..=0xBFFF)
+            .find(|id| !self.hotkeys.contains(id))
+            .ok_or(Error(ERROR_INVALID_PARAMETER))?;
+
+        self.hwnd.register_hotkey(id, modifiers, vkey)?;
+        self.hotkeys.push(id);
+        Ok(crate::HotkeyId(id))
+    }
+
+    /// See [`crate::Window::unregister_hotkey`].
+    pub fn unregister_hotkey(&mut self, id: crate::HotkeyId) {
+        if let Some(index) = self.hotkeys.iter().position(|&x| x == id.0) {
+            self.hotkeys.swap_remove(index);
+            self.hwnd.unregister_hotkey(id.0);
+        }
+    }
+
+    /// See [`crate::Window::set_text_input_allowed`].
+    pub fn set_text_input_allowed(&mut self, yes: bool) {
+        self.text_input = yes;
+        self.hwnd.set_ime_allowed(yes);
+    }
+
+    /// See [`crate::Window::lock_keys`].
+    #[inline]
+    pub fn lock_keys(&self) -> crate::LockKeys {
+        get_lock_keys()
+    }
+
+    /// See [`crate::Window::keyboard_state`].
+    #[inline]
+    pub fn keyboard_state(&self) -> crate::KeyboardState {
+        *self.state.keyboard_state()
+    }
+
+    /// See [`crate::Window::set_mouse_capture`].
+    #[inline]
+    pub fn set_mouse_capture(&mut self, yes: bool) {
+        if yes {
+            self.state.set_mouse_captured();
+        }
+
+        self.hwnd.set_mouse_capture(yes);
+    }
+
+    /// See [`crate::Window::has_mouse_capture`].
+    #[inline]
+    pub fn has_mouse_capture(&self) -> bool {
+        self.hwnd.has_mouse_capture()
+    }
+
+    /// See [`crate::Window::set_enabled`].
+    pub fn set_enabled(&mut self, yes: bool) {
+        self.hwnd.set_enabled(yes);
+    }
+
+    /// See [`crate::Window::is_enabled`].
+    pub fn is_enabled(&self) -> bool {
+        self.hwnd.is_enabled()
+    }
+
+    /// See [`crate::Window::set_content_protected`].
+    pub fn set_content_protected(&mut self, yes: bool) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        if !yes {
+            return self.hwnd.set_display_affinity(DisplayAffinity::None);
+        }
+
+        // Older versions of Windows do not support excluding the window from captures, in
+        // which case it is at least replaced by a black rectangle.
+        self.hwnd
+            .set_display_affinity(DisplayAffinity::ExcludeFromCapture)
+            .or_else(|_| self.hwnd.set_display_affinity(DisplayAffinity::Monitor))
+    }
+
+    /// See [`crate::Window::drag_window`].
+    pub fn drag_window(&mut self) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        self.hwnd.begin_system_drag(HitTest::Caption)
+    }
+
+    /// See [`crate::Window::drag_resize_window`].
+    pub fn drag_resize_window(&mut self, direction: ResizeDirection) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        self.hwnd.begin_system_drag(HitTest::from(direction))
+    }
+
+    /// See [`crate::Window::set_hit_test`].
+    pub fn set_hit_test(&mut self, hit_test: Option<HitTestFn>) {
+        self.state.set_hit_test(hit_test);
+    }
+
+    /// See [`crate::Window::set_clipboard_renderer`].
+    pub fn set_clipboard_renderer(&mut self, renderer: Option<crate::ClipboardRenderer>) {
+        self.state.set_clipboard_renderer(renderer);
+    }
+
+    /// See [`crate::Window::set_drop_effect`].
+    #[inline]
+    pub fn set_drop_effect(&mut self, effect: crate::DropEffect) {
+        self.state.set_drop_effect(effect);
+    }
+
+    /// See [`crate::Window::set_accept_drops`].
+    pub fn set_accept_drops(&mut self, yes: bool) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        if yes && !self.state.is_drop_target() {
+            unsafe { register_drop_target(self.hwnd.raw(), &mut *self.state)? };
+            self.state.set_drop_target(true);
+        } else if !yes && self.state.is_drop_target() {
+            revoke_drop_target(self.hwnd.raw());
+            self.state.set_drop_target(false);
+        }
+
+        if self.attributes.accept_drops != yes {
+            self.attributes.accept_drops = yes;
+            self.update_styles()?;
+        }
+
+        Ok(())
+    }
+
+    /// See [`crate::Window::show_window_menu`].
+    pub fn show_window_menu(&mut self, x: i32, y: i32) {
+        if !self.is_open() {
+            return;
+        }
+
+        let (x, y) = self
+            .hwnd
+            .client_to_screen(x, y)
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+
+        self.hwnd.show_system_menu(x, y, self.attributes.resizable);
+    }
+
+    /// See [`crate::Window::set_icon`].
+    pub fn set_icon(&mut self, icon: Icon) {
+        self.hwnd.set_icon(IconKind::Small, icon.handle());
+        if self.taskbar_icon.is_none() {
+            self.hwnd.set_icon(IconKind::Big, icon.handle());
+        }
+        self.icon = Some(icon);
+    }
+
+    /// See [`crate::Window::set_taskbar_icon`].
+    pub fn set_taskbar_icon(&mut self, icon: Option<Icon>) {
+        // When no specific taskbar icon is requested, fall back to the regular icon.
+        let handle = icon.as_ref().or(self.icon.as_ref()).map_or(0, Icon::handle);
+        self.hwnd.set_icon(IconKind::Big, handle);
+        self.taskbar_icon = icon;
+    }
+
+    /// See [`crate::Window::set_overlay_icon`].
+    pub fn set_overlay_icon(&mut self, icon: Option<Icon>, description: &str) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        let overlay = icon
+            .as_ref()
+            .map(|icon| (icon.handle(), make_utf16(description)));
+        let ret = self.state.set_taskbar_overlay(self.hwnd.raw(), overlay);
+        self.overlay_icon = icon;
+        ret
+    }
+
+    /// See [`crate::Window::set_cursor_icon`].
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.apply_cursor(system_cursor(icon));
+        self.cursor = None;
+    }
+
+    /// See [`crate::Window::set_cursor`].
+    pub fn set_cursor(&mut self, cursor: Cursor) {
+        self.apply_cursor(cursor.handle());
+        self.cursor = Some(cursor);
+    }
+
+    /// See [`crate::Window::set_cursor_visible`].
+    pub fn set_cursor_visible(&mut self, yes: bool) {
+        self.state.set_cursor_hidden(!yes);
+        self.refresh_cursor();
+    }
+
+    /// See [`crate::Window::set_raw_input_devices`].
+    pub fn set_raw_input_devices(&mut self, devices: RawInputDevices) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        let old = self.registered_raw_input();
+        let previous = std::mem::replace(&mut self.raw_input, devices);
+
+        if let Err(err) = self.update_raw_input(old) {
+            self.raw_input = previous;
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// See [`crate::Window::set_relative_mouse_mode`].
+    pub fn set_relative_mouse_mode(&mut self, yes: bool) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        let was = self.state.is_relative_mouse();
+        let old = self.registered_raw_input();
+        self.state.set_relative_mouse(self.hwnd.raw(), yes)?;
+
+        if let Err(err) = self.update_raw_input(old) {
+            // The cursor would otherwise stay locked without the raw mouse motion that replaces
+            // its own.
+            let new = self.registered_raw_input();
+            let _ = self.state.set_relative_mouse(self.hwnd.raw(), was);
+            let _ = self.update_raw_input(new);
+            return Err(err);
+        }
+
+        self.refresh_cursor();
+        Ok(())
+    }
+
+    /// Returns the device classes the window should currently be registered for raw input
+    /// from.
+    fn registered_raw_input(&self) -> RawInputDevices {
+        let devices = backend_raw_input(self.state.input_backend(), self.raw_input);
+
+        // Relative mouse mode relies on raw mouse motion.
+        if self.state.is_relative_mouse() {
+            devices | RawInputDevices::MOUSE
+        } else {
+            devices
+        }
+    }
+
+    /// Updates the raw input registration of the window, given the device classes it was
+    /// previously registered for.
+    fn update_raw_input(&mut self, old: RawInputDevices) -> Result<(), Error> {
+        // Registering again a device class that is already registered is harmless, but removing
+        // one that is not registered fails.
+        let new = self.registered_raw_input();
+        self.hwnd.register_raw_input(new, old - new)
+    }
+
+    /// See [`crate::Window::cursor_position`].
+    pub fn cursor_position(&self) -> Option<(i32, i32)> {
+        // The position of the cursor is unavailable while another desktop is active, such as
+        // the one of the lock screen.
+        let (x, y) = get_cursor_pos().ok()?;
+        let (x, y) = self.hwnd.screen_to_client(x, y).ok()?;
+        let (left, top, right, bottom) = self.hwnd.get_client_rect().ok()?;
+
+        if (left..right).contains(&x) && (top..bottom).contains(&y) {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// See [`crate::Window::set_cursor_position`].
+    pub fn set_cursor_position(&mut self, x: i32, y: i32) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        let (x, y) = self.hwnd.client_to_screen(x, y)?;
+        set_cursor_pos(x, y)
+    }
+
+    /// See [`crate::Window::set_cursor_grab`].
+    #[inline]
+    pub fn set_cursor_grab(&mut self, mode: crate::CursorGrabMode) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        self.state.set_cursor_grab(self.hwnd.raw(), mode)
+    }
+
+    /// Makes the provided cursor the one displayed over the client area of the window.
+    fn apply_cursor(&mut self, cursor: HCURSOR) {
+        self.state.set_cursor(cursor);
+        self.refresh_cursor();
+    }
+
+    /// See [`State::refresh_cursor`].
+    #[inline]
+    fn refresh_cursor(&mut self) {
+        self.state.refresh_cursor(self.hwnd.raw());
+    }
+
+    /// See [`crate::Window::set_decorations`].
+    pub fn set_decorations(&mut self, yes: bool) {
+        if !self.is_open() {
+            return;
+        }
+
+        if self.attributes.decorations == yes {
+            return;
+        }
+
+        self.attributes.decorations = yes;
+        self.update_styles()
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// See [`crate::Window::set_tool_window`].
+    pub fn set_tool_window(&mut self, yes: bool) {
+        if self.attributes.tool_window == yes {
+            return;
+        }
+
+        self.attributes.tool_window = yes;
+        self.update_taskbar_styles();
+    }
+
+    /// See [`crate::Window::set_skip_taskbar`].
+    pub fn set_skip_taskbar(&mut self, yes: bool) {
+        if self.attributes.skip_taskbar == yes {
+            return;
+        }
+
+        self.attributes.skip_taskbar = yes;
+        self.update_taskbar_styles();
+    }
+
+    /// Re-computes the styles of the window after a change that affects its taskbar button.
+    fn update_taskbar_styles(&mut self) {
+        if !self.is_open() {
+            return;
+        }
+
+        // The taskbar only notices the change when the window is shown again.
+        let visible = self.hwnd.is_visible();
+        if visible {
+            self.hwnd.show_window(ShowWindow::Hide);
+        }
+
+        self.update_styles()
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+
+        if visible {
+            self.hwnd.show_window(ShowWindow::ShowNoActivate);
+        }
+    }
+
+    /// See [`crate::Window::set_cursor_hittest`].
+    pub fn set_cursor_hittest(&mut self, yes: bool) {
+        if !self.is_open() {
+            return;
+        }
+
+        if self.attributes.cursor_hittest == yes {
+            return;
+        }
+
+        self.attributes.cursor_hittest = yes;
+        self.update_styles()
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// See [`crate::Window::set_opacity`].
+    pub fn set_opacity(&mut self, opacity: f32) {
+        if !self.is_open() {
+            return;
+        }
+
+        let opacity = opacity.clamp(0.0, 1.0);
+        if self.attributes.opacity == opacity {
+            return;
+        }
+
+        self.attributes.opacity = opacity;
+        self.update_styles()
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// See [`crate::Window::set_fullscreen`].
+    pub fn set_fullscreen(&mut self, fullscreen: Option<Fullscreen>) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        match fullscreen {
+            Some(mode) => self.enter_fullscreen(mode),
+            None => self.leave_fullscreen(),
+        }
+    }
+
+    /// See [`crate::Window::fullscreen`].
+    pub fn fullscreen(&self) -> Option<Fullscreen> {
+        self.fullscreen.as_ref().map(|state| state.mode.clone())
+    }
+
+    /// Makes the window enter the provided fullscreen mode.
+    fn enter_fullscreen(&mut self, mode: Fullscreen) -> Result<(), Error> {
+        // When switching between two fullscreen modes, the placement to restore is the one the
+        // window had before entering the first one. Dropping the previous state restores the
+        // desktop video mode if it had been changed.
+        let (placement, was_fullscreen) = match self.fullscreen.take() {
+            Some(state) => (state.placement, true),
+            None => (self.hwnd.get_placement()?, false),
+        };
+
+        let (monitor, display_mode) = match &mode {
+            Fullscreen::Borderless(Some(monitor)) => (monitor.0.clone(), None),
+            Fullscreen::Borderless(None) => (self.hwnd.monitor(), None),
+            Fullscreen::Exclusive(video_mode) => match video_mode.0.apply() {
+                Ok(guard) => (video_mode.0.monitor().clone(), Some(guard)),
+                Err(err) => {
+                    if was_fullscreen {
+                        self.restore_placement(&placement)
+                            .unwrap_or_else(|err| unexpected_windows_error(err));
+                    }
+                    return Err(err);
+                }
+            },
+        };
+
+        // The monitor may have been disconnected since it was queried. The rectangle is queried
+        // after the video mode has changed as it depends on it.
+        let (left, top, right, bottom) = monitor.rect().or_else(|_| self.hwnd.monitor().rect())?;
+
+        self.fullscreen = Some(FullscreenState {
+            mode,
+            placement,
+            display_mode,
+        });
+
+        // A maximized or minimized window would be placed by the system rather than covering
+        // the monitor.
+        if self.hwnd.is_maximized()? || self.hwnd.is_minimized()? {
+            self.hwnd.show_window(ShowWindow::Restore);
+        }
+
+        self.attributes.fullscreen = true;
+        self.apply_styles()?;
+        self.hwnd.set_outer_rect(left, top, right, bottom)
+    }
+
+    /// Makes the window leave fullscreen mode, restoring its previous placement.
+    fn leave_fullscreen(&mut self) -> Result<(), Error> {
+        let Some(state) = self.fullscreen.take() else {
+            return Ok(());
+        };
+
+        // The desktop video mode must be restored before the placement, which is expressed in
+        // its coordinates.
+        drop(state.display_mode);
+
+        self.restore_placement(&state.placement)
+    }
+
+    /// Restores the decorations of the window and the provided placement after it left
+    /// fullscreen mode.
+    fn restore_placement(&mut self, placement: &WINDOWPLACEMENT) -> Result<(), Error> {
+        self.attributes.fullscreen = false;
+        self.apply_styles()?;
+        self.hwnd.set_placement(placement)?;
+        self.hwnd.refresh_frame()
+    }
+
+    /// Re-computes the styles of the window from its attributes and applies them, without
+    /// moving or resizing the window.
+    ///
+    /// The applied styles are returned.
+    fn apply_styles(&mut self) -> Result<WindowStyles, Error> {
+        // The state of the window is not part of its attributes and must be preserved.
+        let preserved = WindowStyles::VISIBLE | WindowStyles::MAXIMIZED | WindowStyles::MINIMIZED;
+        let styles = make_window_styles(&self.attributes) | (self.hwnd.get_styles()? & preserved);
+
+        self.hwnd.set_styles(styles)?;
+
+        let custom_frame = self.attributes.custom_frame();
+        self.state.set_custom_frame(custom_frame);
+
+        let (left, right, top, bottom) = self.attributes.frame_margins();
+        self.hwnd.extend_frame(left, right, top, bottom)?;
+
+        // Layered windows are not rendered until their attributes are set.
+        if styles.contains(WindowStyles::LAYERED) {
+            let alpha = (self.attributes.opacity * 255.0).round() as u8;
+            self.hwnd.set_layered_alpha(alpha)?;
+        }
+
+        Ok(styles)
+    }
+
+    /// Re-computes the styles of the window from its attributes and applies them.
+    ///
+    /// The window is resized such that its client area remains at the same location on the
+    /// screen.
+    fn update_styles(&mut self) -> Result<(), Error> {
+        let (x, y) = self.hwnd.client_to_screen(0, 0)?;
+        let (left, top, right, bottom) = self.hwnd.get_client_rect()?;
+
+        let styles = self.apply_styles()?;
+
+        if self.attributes.fullscreen
+            || styles.intersects(WindowStyles::MAXIMIZED | WindowStyles::MINIMIZED)
+        {
+            // The system (or the fullscreen mode) is in charge of the placement of the window,
+            // only notify it that the frame has changed.
+            return self.hwnd.refresh_frame();
+        }
+
+        let (left, top, right, bottom) = self.attributes.client_to_window_rect(
+            styles,
+            x,
+            y,
+            x + right - left,
+            y + bottom - top,
+        )?;
+
+        self.hwnd.set_outer_rect(left, top, right, bottom)
+    }
+
+    /// See [`crate::Window::set_theme`].
+    pub fn set_theme(&mut self, theme: Option<Theme>) {
+        self.theme = theme;
+        self.state.set_follow_system_theme(theme.is_none());
+
+        let dark = match theme {
+            Some(theme) => theme == Theme::Dark,
+            None => system_prefers_dark_mode(),
+        };
+
+        // Windows that do not support dark title bars are always drawn using the light theme.
+        let _ = self.hwnd.set_dark_mode(dark);
+    }
+
+    /// See [`crate::Window::theme`].
+    pub fn theme(&self) -> Theme {
+        match self.theme {
+            Some(theme) => theme,
+            None if system_prefers_dark_mode() => Theme::Dark,
+            None => Theme::Light,
+        }
+    }
+
+    /// See [`crate::Window::set_backdrop`].
+    pub fn set_backdrop(&mut self, backdrop: Backdrop) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        self.hwnd
+            .set_system_backdrop(SystemBackdrop::from(backdrop))?;
+
+        self.attributes.backdrop = backdrop;
+        let (left, right, top, bottom) = self.attributes.frame_margins();
+        self.hwnd.extend_frame(left, right, top, bottom)
+    }
+
+    /// See [`crate::Window::set_corner_preference`].
+    pub fn set_corner_preference(&mut self, preference: CornerPreference) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        self.hwnd
+            .set_corner_preference(super::hwnd::CornerPreference::from(preference))
+    }
+
+    /// See [`crate::Window::set_level`].
+    pub fn set_level(&mut self, level: WindowLevel) {
+        if !self.is_open() {
+            return;
+        }
+
+        self.try_set_level(level)
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// Changes the level of the window, returning an eventual error.
+    fn try_set_level(&mut self, level: WindowLevel) -> Result<(), Error> {
+        self.state
+            .set_always_on_bottom(level == WindowLevel::AlwaysOnBottom);
+
+        let insert_after = match level {
+            WindowLevel::Normal => InsertAfter::NoTopmost,
+            WindowLevel::AlwaysOnTop => InsertAfter::Topmost,
+            WindowLevel::AlwaysOnBottom => InsertAfter::Bottom,
+        };
+
+        // A topmost window moved to the bottom of the Z order loses its topmost status.
+        self.hwnd.set_z_order(insert_after)
+    }
+
+    /// See [`crate::Window::current_monitor`].
+    #[inline]
+    pub fn current_monitor(&self) -> super::Monitor {
+        self.hwnd.monitor()
+    }
+
+    /// See [`crate::Window::refresh_rate`].
+    pub fn refresh_rate(&self) -> Option<u32> {
+        // A refresh rate of 0 or 1 means that the default rate of the hardware is used, which
+        // is not known.
+        let mode = self.hwnd.monitor().current_video_mode()?;
+        Some(mode.refresh_rate()).filter(|&rate| rate > 1)
+    }
+
+    /// See [`crate::Window::wait_for_vblank`].
+    #[inline]
+    pub fn wait_for_vblank(&self) -> Result<(), Error> {
+        dwm_flush()
+    }
+
+    /// See [`crate::Window::scale_factor`].
+    pub fn scale_factor(&self) -> f64 {
+        self.hwnd.dpi() as f64 / USER_DEFAULT_SCREEN_DPI as f64
+    }
+
+    /// See [`crate::Window::client_size`].
     pub fn client_size(&self) -> (u32, u32) {
+        if !self.is_open() {
+            return (0, 0);
+        }
+
         let (left, top, right, bottom) = self
             .hwnd
             .get_client_rect()
@@ -59,25 +1131,242 @@ impl Window {
         ((right - left) as u32, (bottom - top) as u32)
     }
 
-    /// See [`crate::Window::poll_events`]
+    /// See [`crate::Window::outer_size`].
+    pub fn outer_size(&self) -> (u32, u32) {
+        if !self.is_open() {
+            return (0, 0);
+        }
+
+        let (left, top, right, bottom) = self
+            .hwnd
+            .get_window_rect()
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+
+        ((right - left) as u32, (bottom - top) as u32)
+    }
+
+    /// See [`crate::Window::request_client_size`].
+    pub fn request_client_size(&mut self, width: u32, height: u32) {
+        if !self.is_open() {
+            return;
+        }
+
+        let (width, height) = self
+            .hwnd
+            .get_styles()
+            .and_then(|styles| self.attributes.client_to_window_size(styles, width, height))
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+
+        self.hwnd
+            .set_size(width, height)
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// See [`crate::Window::set_min_size`].
+    pub fn set_min_size(&mut self, size: Option<crate::Size>) {
+        self.state.set_min_size(size);
+        self.enforce_size_constraints();
+    }
+
+    /// See [`crate::Window::set_max_size`].
+    pub fn set_max_size(&mut self, size: Option<crate::Size>) {
+        self.state.set_max_size(size);
+        self.enforce_size_constraints();
+    }
+
+    /// See [`crate::Window::set_aspect_ratio`].
+    pub fn set_aspect_ratio(&mut self, ratio: Option<(u32, u32)>) {
+        self.state.set_aspect_ratio(ratio);
+    }
+
+    /// Makes sure that the current size of the window respects its size constraints.
+    ///
+    /// Re-applying the current size of the window forces the system to query the constraints
+    /// again through `WM_GETMINMAXINFO`.
+    fn enforce_size_constraints(&mut self) {
+        if !self.is_open() {
+            return;
+        }
+
+        let (width, height) = self.outer_size();
+
+        self.hwnd
+            .set_size(width, height)
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// See [`crate::Window::position`].
+    pub fn position(&self) -> (i32, i32) {
+        if !self.is_open() {
+            return (0, 0);
+        }
+
+        self.hwnd
+            .client_to_screen(0, 0)
+            .unwrap_or_else(|err| unexpected_windows_error(err))
+    }
+
+    /// See [`crate::Window::outer_position`].
+    pub fn outer_position(&self) -> (i32, i32) {
+        if !self.is_open() {
+            return (0, 0);
+        }
+
+        let (left, top, _, _) = self
+            .hwnd
+            .get_window_rect()
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+
+        (left, top)
+    }
+
+    /// See [`crate::Window::set_position`].
+    pub fn set_position(&mut self, x: i32, y: i32) {
+        if !self.is_open() {
+            return;
+        }
+
+        self.hwnd
+            .set_position(x, y)
+            .unwrap_or_else(|err| unexpected_windows_error(err));
+    }
+
+    /// See [`crate::Window::center_on`].
+    pub fn center_on(&mut self, monitor: &super::Monitor) {
+        let Ok(work) = monitor.work_rect() else {
+            return;
+        };
+
+        // Moving the window to another monitor may change its size when the DPI of the two
+        // monitors differ, so the window is moved first and centered afterwards.
+        if self.hwnd.monitor() != *monitor {
+            self.set_position(work.0, work.1);
+        }
+
+        let (x, y) = centered_position(work, self.outer_size());
+        self.set_position(x, y);
+    }
+
+    /// See [`crate::Window::inject_key`].
+    #[cfg(feature = "automation")]
+    #[inline]
+    pub fn inject_key(&mut self, code: super::KeyCode, pressed: bool) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        super::automation::inject_key(code, pressed)
+    }
+
+    /// See [`crate::Window::inject_mouse_button`].
+    #[cfg(feature = "automation")]
+    #[inline]
+    pub fn inject_mouse_button(
+        &mut self,
+        button: crate::MouseButton,
+        pressed: bool,
+    ) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        super::automation::inject_mouse_button(button, pressed)
+    }
+
+    /// See [`crate::Window::inject_cursor_position`].
+    #[cfg(feature = "automation")]
+    pub fn inject_cursor_position(&mut self, x: i32, y: i32) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        let (x, y) = self.hwnd.client_to_screen(x, y)?;
+        super::automation::inject_cursor_position(x, y)
+    }
+
+    /// See [`crate::Window::inject_mouse_wheel`].
+    #[cfg(feature = "automation")]
+    #[inline]
+    pub fn inject_mouse_wheel(&mut self, dx: f64, dy: f64) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        super::automation::inject_mouse_wheel(dx, dy)
+    }
+
+    /// See [`crate::Window::inject_text`].
+    #[cfg(feature = "automation")]
+    #[inline]
+    pub fn inject_text(&mut self, text: &str) -> Result<(), Error> {
+        self.ensure_open()?;
+
+        super::automation::post_text(self.hwnd.raw(), text)
+    }
+
+    /// See [`crate::Window::poll_events`].
     pub fn poll_events<F>(&mut self, mut handler: F)
     where
         F: Send + FnMut(crate::Event),
     {
         let guard = HandlerGuard(&mut self.state);
         unsafe { guard.0.set_handler(&mut handler) };
-        while self.hwnd.peek_messages() {}
+        while self.hwnd.peek_messages(self.text_input) {}
     }
 
-    /// See [`crate::Window::blocking_poll_events`]
+    /// See [`crate::Window::blocking_poll_events`].
     pub fn blocking_poll_events<F>(&mut self, mut handler: F)
     where
         F: Send + FnMut(crate::Event),
     {
         let guard = HandlerGuard(&mut self.state);
         unsafe { guard.0.set_handler(&mut handler) };
-        let _ = self.hwnd.get_messages();
-        while self.hwnd.peek_messages() {}
+        let _ = self.hwnd.get_messages(self.text_input);
+        while self.hwnd.peek_messages(self.text_input) {}
+    }
+
+    /// Sets the handler that receives the events of the window until the returned guard is
+    /// dropped.
+    ///
+    /// See [`crate::Window::run_modal`].
+    ///
+    /// # Safety
+    ///
+    /// The guard must be dropped before the handler and the window.
+    pub unsafe fn set_modal_handler<F>(&mut self, handler: &mut F) -> ModalHandlerGuard
+    where
+        F: Send + FnMut(crate::Event),
+    {
+        unsafe { self.state.set_handler(handler) };
+
+        // The window procedure accesses the state through the user data of the window, such
+        // that the window itself does not need to be borrowed.
+        ModalHandlerGuard(&mut *self.state)
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        // The handle of a destroyed window may have been reused by another window since.
+        if self.state.is_destroyed() {
+            self.hwnd.forget();
+        } else {
+            // Windows created by someone else outlive this instance.
+            let _ = self.state.set_relative_mouse(self.hwnd.raw(), false);
+            let _ = self.set_cursor_grab(crate::CursorGrabMode::None);
+
+            for id in std::mem::take(&mut self.hotkeys) {
+                self.hwnd.unregister_hotkey(id);
+            }
+
+            if let Some(property) = &self.instance_property {
+                unregister_instance_window(self.hwnd.raw(), property);
+            }
+        }
+
+        // The other windows of the process keep receiving the raw input of the device classes
+        // they are registered for.
+        let _ = self
+            .hwnd
+            .register_raw_input(RawInputDevices::empty(), RawInputDevices::all());
+
+        // The owner must be enabled before the window is destroyed, otherwise the system would
+        // activate another application instead of it.
+        if let Some(owner) = self.modal_owner {
+            set_window_enabled(owner, true);
+        }
     }
 }
 
@@ -97,6 +1386,33 @@ impl rwh::HasDisplayHandle for Window {
     }
 }
 
+/// The Windows-specific part of a [`crate::Config`].
+///
+/// See [`crate::platform::windows::ConfigExtWindows`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlatformConfig {
+    /// The `CS_*` styles the class of the window is registered with.
+    pub class_style: u32,
+}
+
+impl Default for PlatformConfig {
+    fn default() -> Self {
+        Self {
+            class_style: CS_HREDRAW | CS_VREDRAW,
+        }
+    }
+}
+
+/// Adds or removes the `CS_DROPSHADOW` style from the provided class styles.
+#[inline]
+pub fn with_drop_shadow(style: u32, yes: bool) -> u32 {
+    if yes {
+        style | CS_DROPSHADOW
+    } else {
+        style & !CS_DROPSHADOW
+    }
+}
+
 /// A guard that automatically removes user-defined handlers when dropped to avoid
 /// calling into a dangling function pointer.
 struct HandlerGuard<'a>(&'a mut State);
@@ -107,26 +1423,201 @@ impl<'a> Drop for HandlerGuard<'a> {
     }
 }
 
-/// Converts the window config [`crate::Config`] into the corresponding Windows styles.
+/// Removes the handler set by [`Window::set_modal_handler`] when dropped.
+pub struct ModalHandlerGuard(*mut State);
+
+impl Drop for ModalHandlerGuard {
+    fn drop(&mut self) {
+        unsafe { (*self.0).remove_handler() };
+    }
+}
+
+/// The state of a window in fullscreen mode.
+struct FullscreenState {
+    /// The fullscreen mode of the window.
+    mode: Fullscreen,
+    /// The placement of the window before it entered fullscreen mode.
+    placement: WINDOWPLACEMENT,
+    /// Restores the desktop video mode when the window leaves exclusive fullscreen mode.
+    display_mode: Option<DisplayModeGuard>,
+}
+
+/// The attributes of a window that determine its styles.
+#[derive(Debug, Clone, Copy)]
+struct Attributes {
+    /// Whether the window is the child of another window.
+    child: bool,
+    /// Whether the window can be resized by the user.
+    resizable: bool,
+    /// Whether the window is a tool window.
+    tool_window: bool,
+    /// Whether the window has no taskbar button.
+    skip_taskbar: bool,
+    /// Whether data can be dropped on the window.
+    ///
+    /// This is only set when the drop target of the window is registered, as the
+    /// `WM_DROPFILES` message sent to windows that only accept files is not handled.
+    accept_drops: bool,
+    /// Whether the window has the system's default decorations.
+    decorations: bool,
+    /// Whether the window keeps its resize borders and shadow when it has no decorations.
+    undecorated_shadow: bool,
+    /// The opacity of the window, between `0.0` and `1.0`.
+    opacity: f32,
+    /// Whether the window receives mouse input.
+    cursor_hittest: bool,
+    /// Whether the window is in fullscreen mode, in which case it has no decorations
+    /// regardless of `decorations`.
+    fullscreen: bool,
+    /// The material drawn by the system behind the window.
+    backdrop: Backdrop,
+}
+
+impl Attributes {
+    /// Extracts the attributes of the window from the provided [`crate::Config`].
+    ///
+    /// Whether the window has a parent is provided separately, as the parent may be specified
+    /// in ways that the [`crate::Config`] does not know about.
+    fn from_config(config: &crate::Config, child: bool) -> Self {
+        Self {
+            child,
+            resizable: config.attributes.resizable,
+            tool_window: config.attributes.tool_window,
+            skip_taskbar: config.attributes.skip_taskbar,
+            accept_drops: config.attributes.accept_drops,
+            decorations: config.attributes.decorations,
+            undecorated_shadow: config.attributes.undecorated_shadow,
+            opacity: 1.0,
+            cursor_hittest: config.attributes.cursor_hittest,
+            fullscreen: false,
+            backdrop: Backdrop::Auto,
+        }
+    }
+}
+
+impl Attributes {
+    /// Deduces the attributes of a window created by someone else from its current styles.
+    #[cfg(feature = "raw-window-handle")]
+    fn from_styles(styles: WindowStyles) -> Self {
+        Self {
+            child: styles.contains(WindowStyles::CHILD),
+            resizable: styles.contains(WindowStyles::SIZE_BOX),
+            tool_window: styles.contains(WindowStyles::TOOL_WINDOW),
+            skip_taskbar: false,
+            accept_drops: styles.contains(WindowStyles::ACCEPT_FILES),
+            decorations: styles.contains(WindowStyles::CAPTION),
+            undecorated_shadow: false,
+            opacity: 1.0,
+            cursor_hittest: !styles.contains(WindowStyles::CLICK_THROUGH),
+            fullscreen: false,
+            backdrop: Backdrop::Auto,
+        }
+    }
+
+    /// Returns whether the window draws its own frame.
+    ///
+    /// Such windows keep the styles of a decorated window (and the behavior that comes with
+    /// them), but their client area extends over the whole window.
+    fn custom_frame(&self) -> bool {
+        !self.decorations && self.undecorated_shadow && !self.fullscreen
+    }
+
+    /// Returns the margins by which the frame of the window is extended into its client area,
+    /// in the order expected by [`Hwnd::extend_frame`].
+    fn frame_margins(&self) -> (i32, i32, i32, i32) {
+        if self.backdrop != Backdrop::Auto && self.backdrop != Backdrop::None {
+            // The backdrop is only drawn behind the frame, which must cover the whole window.
+            (-1, -1, -1, -1)
+        } else if self.custom_frame() {
+            // Extending the frame of the window by a single pixel is enough for the system to
+            // draw its shadow.
+            (0, 0, 1, 0)
+        } else {
+            (0, 0, 0, 0)
+        }
+    }
+
+    /// Converts the given client rectangle to the corresponding window rectangle, for a window
+    /// with these attributes and the provided styles.
+    fn client_to_window_rect(
+        &self,
+        styles: WindowStyles,
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    ) -> Result<(i32, i32, i32, i32), Error> {
+        if self.custom_frame() {
+            Ok((left, top, right, bottom))
+        } else {
+            styles.client_to_window_rect(left, top, right, bottom)
+        }
+    }
+
+    /// Converts the given client size to the corresponding window size, for a window with these
+    /// attributes and the provided styles.
+    fn client_to_window_size(
+        &self,
+        styles: WindowStyles,
+        width: u32,
+        height: u32,
+    ) -> Result<(u32, u32), Error> {
+        if self.custom_frame() {
+            Ok((width, height))
+        } else {
+            styles.client_to_window_size(width, height)
+        }
+    }
+}
+
+/// Returns the position of a window of the provided outer size, centered in `rect`.
+fn centered_position(
+    (left, top, right, bottom): (i32, i32, i32, i32),
+    size: (u32, u32),
+) -> (i32, i32) {
+    (
+        left + (right - left - size.0 as i32) / 2,
+        top + (bottom - top - size.1 as i32) / 2,
+    )
+}
+
+/// Converts the window attributes into the corresponding Windows styles.
 ///
-/// The first element of the tuple is the window style, the second is the extended window style.
-fn make_window_styles(config: &crate::Config) -> WindowStyles {
+/// Note that the visibility of the window is not part of its attributes. The
+/// [`VISIBLE`](WindowStyles::VISIBLE) style must be added separately.
+fn make_window_styles(attributes: &Attributes) -> WindowStyles {
     let mut styles = WindowStyles::empty();
 
-    styles |= WindowStyles::ACCEPT_FILES;
+    if attributes.accept_drops {
+        styles |= WindowStyles::ACCEPT_FILES;
+    }
+
+    if attributes.opacity < 1.0 {
+        styles |= WindowStyles::LAYERED;
+    }
+
+    // Only layered windows let mouse input go through them.
+    if !attributes.cursor_hittest {
+        styles |= WindowStyles::CLICK_THROUGH | WindowStyles::LAYERED;
+    }
 
-    if config.visible {
-        styles |= WindowStyles::VISIBLE;
+    if attributes.tool_window {
+        styles |= WindowStyles::TOOL_WINDOW | WindowStyles::NO_ACTIVATE;
     }
 
-    if config.always_on_top {
-        styles |= WindowStyles::TOPMOST;
+    // Tool windows are the only top-level windows that never have a taskbar button.
+    if attributes.skip_taskbar {
+        styles |= WindowStyles::TOOL_WINDOW;
     }
 
-    if config.decorations {
+    if attributes.child {
+        // Child windows are part of the user interface of their parent and never have
+        // decorations.
+        styles |= WindowStyles::CHILD;
+    } else if (attributes.decorations && !attributes.fullscreen) || attributes.custom_frame() {
         styles |= WindowStyles::CAPTION | WindowStyles::SYSMENU | WindowStyles::MINIMIZE_BOX;
 
-        if config.resizable {
+        if attributes.resizable {
             styles |= WindowStyles::MAXIMIZE_BOX | WindowStyles::SIZE_BOX;
         }
     } else {
@@ -136,7 +1627,72 @@ fn make_window_styles(config: &crate::Config) -> WindowStyles {
     styles
 }
 
+/// Returns a new window identifier, which has never been returned before.
+fn next_window_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Returns the device classes a window must be registered for raw input from, given the source
+/// of its input events and the device classes selected by the application.
+fn backend_raw_input(backend: InputBackend, devices: RawInputDevices) -> RawInputDevices {
+    match backend {
+        InputBackend::RawInput => devices,
+        InputBackend::WindowMessages => RawInputDevices::empty(),
+        // Only mouse motion is read from raw input.
+        InputBackend::Both => devices & RawInputDevices::MOUSE,
+    }
+}
+
+/// Returns the `HWND` of the provided window handle, or `0` if it is `None`.
+///
+/// Handles that do not belong to the Win32 platform are rejected.
+#[cfg(feature = "raw-window-handle")]
+fn raw_hwnd(handle: Option<rwh::WindowHandle>) -> Result<HWND, Error> {
+    use windows_sys::Win32::Foundation::ERROR_INVALID_WINDOW_HANDLE;
+
+    match handle.map(|handle| handle.as_raw()) {
+        Some(rwh::RawWindowHandle::Win32(handle)) => Ok(handle.hwnd.get()),
+        Some(_) => Err(Error(ERROR_INVALID_WINDOW_HANDLE)),
+        None => Ok(0),
+    }
+}
+
+/// Returns the handle of the parent requested by the provided [`crate::Config`], or `0` if the
+/// window has no parent.
+#[cfg(feature = "raw-window-handle")]
+fn parent_hwnd(config: &crate::Config) -> Result<HWND, Error> {
+    raw_hwnd(config.parent)
+}
+
+/// Returns the handle of the parent requested by the provided [`crate::Config`], or `0` if the
+/// window has no parent.
+#[cfg(not(feature = "raw-window-handle"))]
+fn parent_hwnd(_config: &crate::Config) -> Result<HWND, Error> {
+    Ok(0)
+}
+
+/// Returns the handle of the owner requested by the provided [`crate::Config`], or `0` if the
+/// window has no owner.
+#[cfg(feature = "raw-window-handle")]
+fn owner_hwnd(config: &crate::Config) -> Result<HWND, Error> {
+    match config.owner_handle {
+        Some(handle) => raw_hwnd(Some(handle)),
+        None => Ok(config.owner.map_or(0, |owner| owner.0.hwnd.raw())),
+    }
+}
+
+/// Returns the handle of the owner requested by the provided [`crate::Config`], or `0` if the
+/// window has no owner.
+#[cfg(not(feature = "raw-window-handle"))]
+fn owner_hwnd(config: &crate::Config) -> Result<HWND, Error> {
+    Ok(config.owner.map_or(0, |owner| owner.0.hwnd.raw()))
+}
+
 /// Windows unexpectedly returned an error.
+///
+/// This must only be used once the window is known to still exist, as every function fails on a
+/// destroyed window.
 #[track_caller]
 #[cold]
 fn unexpected_windows_error(err: Error) -> ! {
@@ -1,10 +1,22 @@
 use std::mem::size_of;
 
-use windows_sys::Win32::Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM};
+use windows_sys::Win32::Foundation::{HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use windows_sys::Win32::Graphics::Gdi::ScreenToClient;
 use windows_sys::Win32::UI::Input::*;
+use windows_sys::Win32::UI::TextServices::HKL;
 use windows_sys::Win32::UI::WindowsAndMessaging::*;
 
-use super::KeyCode;
+use super::clipboard::{forget_offered_formats, format_from_id, offered_formats, Clipboard};
+use super::com::TaskbarList;
+use super::drag::revoke_drop_target;
+use super::hwnd::{
+    clip_cursor, get_client_screen_rect, get_cursor_pos, get_lock_keys,
+    get_resize_border_thickness, get_window_dpi, is_cursor_over_client_area,
+    is_sole_raw_input_target, set_dark_mode, system_prefers_dark_mode, HitTest, WindowStyles,
+};
+use super::jump_list::{decode_instance_arguments, COPYDATA_INSTANCE_ARGUMENTS};
+use super::tray::{restore_tray_icons, tray_icon_event, WM_TRAY_ICON};
+use super::{Error, KeyCode, KeyboardLayout};
 
 /// The default window procedure for windows created by this crate.
 ///
@@ -78,19 +90,98 @@ impl Default for DynHandler {
     }
 }
 
+/// A user-provided function that determines the area of the window under a point.
+pub type HitTestFn = Box<dyn Send + FnMut(i32, i32) -> crate::HitTestArea>;
+
 /// Stores some state that is required to transform window events into [`crate::Event`]s.
 #[derive(Default)]
 pub struct State {
     /// The handler used to receive events.
     handler: DynHandler,
 
-    /// An eventual low surrogate of a UTF-16 character.
+    /// An eventual high surrogate of a UTF-16 character.
     ///
     /// This is kept until the next `WM_CHAR` message is received, at which point it is combined
-    /// with the high surrogate to form a full UTF-32 code point.
+    /// with the low surrogate to form a full UTF-32 code point.
     ///
     /// 0 means that no surrogate is stored.
-    low_surrogate: u16,
+    high_surrogate: u16,
+    /// The character of a dead key waiting to be composed with the next character.
+    ///
+    /// `TranslateMessage` reports it through a `WM_DEADCHAR` message, and the following
+    /// `WM_CHAR` message carries the composed character. When the two cannot be composed, the
+    /// dead character is reported by its own `WM_CHAR` message first.
+    dead_char: Option<char>,
+
+    /// The minimum size of the client area of the window.
+    min_size: Option<crate::Size>,
+    /// The maximum size of the client area of the window.
+    max_size: Option<crate::Size>,
+    /// The aspect ratio that the client area of the window must keep while being resized.
+    aspect_ratio: Option<(u32, u32)>,
+    /// Whether the window must be kept at the bottom of the Z order.
+    always_on_bottom: bool,
+    /// The function used to determine the area of the client area under the cursor.
+    hit_test: Option<HitTestFn>,
+    /// The function used to provide the contents of the clipboard offered by the window.
+    clipboard_renderer: Option<crate::ClipboardRenderer>,
+    /// Whether the window draws its own frame, making its client area extend over the whole
+    /// window.
+    custom_frame: bool,
+    /// Whether the theme of the window follows the preference of the user.
+    follow_system_theme: bool,
+    /// Whether the window has been destroyed.
+    ///
+    /// No events are sent once this is set.
+    destroyed: bool,
+    /// The cursor displayed over the client area of the window.
+    ///
+    /// 0 means that the cursor is not managed by this crate, which is the case for windows
+    /// created by someone else until a cursor is set.
+    cursor: HCURSOR,
+    /// Whether the cursor is hidden while it is over the client area of the window and the
+    /// window has the focus.
+    cursor_hidden: bool,
+    /// Whether the window currently has the keyboard focus.
+    focused: bool,
+    /// The way the cursor is restricted to the window.
+    ///
+    /// The restriction is only applied while the window has the focus.
+    cursor_grab: crate::CursorGrabMode,
+    /// Whether the window is in relative mouse mode.
+    ///
+    /// In this mode, the cursor is hidden and locked while the window has the focus, and only
+    /// raw mouse motion is reported. This overrides `cursor_hidden` and `cursor_grab` without
+    /// modifying them, so that they are restored when the mode is left.
+    relative_mouse: bool,
+    /// Whether the mouse has been captured through [`crate::Window::set_mouse_capture`], and
+    /// the capture has not ended since.
+    ///
+    /// The system also captures the mouse on its own, such as while the window is moved or
+    /// resized, which must not be reported to the application.
+    mouse_captured: bool,
+    /// The keys that are currently pressed.
+    ///
+    /// This is cleared when the window loses the focus, as the corresponding release events
+    /// would be sent to another window.
+    keyboard: crate::KeyboardState,
+    /// The lock keys that were toggled on when last checked.
+    lock_keys: crate::LockKeys,
+    /// The effect of dropping data on the window.
+    drop_effect: crate::DropEffect,
+    /// Whether the window is registered as a drop target.
+    drop_target: bool,
+    /// The source from which the window produces its input events.
+    input_backend: crate::InputBackend,
+    /// The buffer raw input packets are read into, kept between reads to avoid reallocating it.
+    ///
+    /// See [`read_raw_input_buffer`].
+    raw_input_buffer: Vec<u64>,
+    /// The overlay icon displayed over the taskbar button of the window, and its description as
+    /// a null-terminated UTF-16 string.
+    ///
+    /// The overlay must be applied again when the taskbar button is re-created.
+    taskbar_overlay: Option<(HICON, Vec<u16>)>,
 }
 
 impl State {
@@ -116,27 +207,461 @@ impl State {
     /// Sends an event to the handler function.
     #[inline]
     pub fn send_event(&mut self, event: crate::Event) {
-        self.handler.send_event(event)
+        if !self.destroyed {
+            self.handler.send_event(event)
+        }
+    }
+
+    /// Returns whether the window has been destroyed.
+    #[inline]
+    pub fn is_destroyed(&self) -> bool {
+        self.destroyed
+    }
+
+    /// Sets the minimum size of the client area of the window.
+    #[inline]
+    pub fn set_min_size(&mut self, size: Option<crate::Size>) {
+        self.min_size = size;
+    }
+
+    /// Sets the maximum size of the client area of the window.
+    #[inline]
+    pub fn set_max_size(&mut self, size: Option<crate::Size>) {
+        self.max_size = size;
+    }
+
+    /// Returns the minimum size of the client area of the window, in physical pixels, given
+    /// its scale factor.
+    ///
+    /// Logical sizes follow the scale factor of the window.
+    fn physical_min_size(&self, scale: f64) -> Option<(u32, u32)> {
+        self.min_size.map(|size| size.to_physical(scale).into())
+    }
+
+    /// Returns the maximum size of the client area of the window, in physical pixels, given
+    /// its scale factor.
+    ///
+    /// Logical sizes follow the scale factor of the window.
+    fn physical_max_size(&self, scale: f64) -> Option<(u32, u32)> {
+        self.max_size.map(|size| size.to_physical(scale).into())
+    }
+
+    /// Clamps the provided client size to the size constraints of the window, given its scale
+    /// factor.
+    pub fn clamp_client_size(&self, width: u32, height: u32, scale: f64) -> (u32, u32) {
+        let (mut width, mut height) = (width, height);
+
+        if let Some((max_width, max_height)) = self.physical_max_size(scale) {
+            width = width.min(max_width);
+            height = height.min(max_height);
+        }
+
+        // The minimum size wins over the maximum size, as the system does.
+        if let Some((min_width, min_height)) = self.physical_min_size(scale) {
+            width = width.max(min_width);
+            height = height.max(min_height);
+        }
+
+        (width, height)
+    }
+
+    /// Writes the size constraints of the window into the provided [`MINMAXINFO`] structure.
+    fn apply_size_constraints(&self, hwnd: HWND, info: &mut MINMAXINFO) {
+        if self.min_size.is_none() && self.max_size.is_none() {
+            return;
+        }
+
+        let scale = get_window_dpi(hwnd) as f64 / USER_DEFAULT_SCREEN_DPI as f64;
+
+        // The constraints are expressed in terms of client area, but the system expects
+        // window sizes.
+        if let Some((width, height)) = self.physical_min_size(scale) {
+            if let Some((width, height)) = self.client_to_window_size(hwnd, width, height) {
+                info.ptMinTrackSize.x = width as i32;
+                info.ptMinTrackSize.y = height as i32;
+            }
+        }
+
+        if let Some((width, height)) = self.physical_max_size(scale) {
+            if let Some((width, height)) = self.client_to_window_size(hwnd, width, height) {
+                info.ptMaxTrackSize.x = width as i32;
+                info.ptMaxTrackSize.y = height as i32;
+            }
+        }
+    }
+
+    /// Sets the aspect ratio that the client area of the window must keep while being resized.
+    #[inline]
+    pub fn set_aspect_ratio(&mut self, ratio: Option<(u32, u32)>) {
+        self.aspect_ratio = ratio;
+    }
+
+    /// Adjusts the provided sizing rectangle to respect the aspect ratio of the window.
+    ///
+    /// `edge` is the edge of the window that is being dragged by the user.
+    ///
+    /// # Returns
+    ///
+    /// This function returns whether the rectangle has been modified.
+    fn apply_aspect_ratio(&self, hwnd: HWND, edge: u32, rect: &mut RECT) -> bool {
+        let Some((ratio_w, ratio_h)) = self.aspect_ratio else {
+            return false;
+        };
+
+        if ratio_w == 0 || ratio_h == 0 {
+            return false;
+        }
+
+        // The ratio applies to the client area, so the size of the decorations must be
+        // removed from the sizing rectangle before doing any computation.
+        let Some((extra_w, extra_h)) = self.client_to_window_size(hwnd, 0, 0) else {
+            return false;
+        };
+
+        let extra_w = extra_w as i32;
+        let extra_h = extra_h as i32;
+
+        let client_w = (rect.right - rect.left - extra_w).max(0) as u64;
+        let client_h = (rect.bottom - rect.top - extra_h).max(0) as u64;
+
+        match edge {
+            WMSZ_TOP | WMSZ_BOTTOM => {
+                let client_w = client_h * ratio_w as u64 / ratio_h as u64;
+                rect.right = rect.left + client_w as i32 + extra_w;
+            }
+            _ => {
+                let client_h = client_w * ratio_h as u64 / ratio_w as u64;
+                let height = client_h as i32 + extra_h;
+
+                match edge {
+                    WMSZ_TOPLEFT | WMSZ_TOPRIGHT => rect.top = rect.bottom - height,
+                    _ => rect.bottom = rect.top + height,
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Sets whether the window must be kept at the bottom of the Z order.
+    #[inline]
+    pub fn set_always_on_bottom(&mut self, yes: bool) {
+        self.always_on_bottom = yes;
+    }
+
+    /// Sets whether the theme of the window follows the preference of the user.
+    #[inline]
+    pub fn set_follow_system_theme(&mut self, yes: bool) {
+        self.follow_system_theme = yes;
+    }
+
+    /// Sets the cursor displayed over the client area of the window.
+    #[inline]
+    pub fn set_cursor(&mut self, cursor: HCURSOR) {
+        self.cursor = cursor;
+    }
+
+    /// Sets whether the cursor is hidden while it is over the client area of the window.
+    #[inline]
+    pub fn set_cursor_hidden(&mut self, yes: bool) {
+        self.cursor_hidden = yes;
+    }
+
+    /// Returns whether the cursor is hidden while it is over the client area of the window.
+    #[inline]
+    fn is_cursor_hidden(&self) -> bool {
+        self.cursor_hidden || self.relative_mouse
+    }
+
+    /// Returns the cursor that should currently be displayed over the client area of the window.
+    ///
+    /// A hidden cursor is only hidden while the window has the focus, ensuring that the user
+    /// is able to see it when interacting with other windows.
+    #[inline]
+    pub fn current_cursor(&self) -> HCURSOR {
+        if self.is_cursor_hidden() && self.focused {
+            0
+        } else {
+            self.cursor
+        }
+    }
+
+    /// Returns whether the cursor currently displayed over the client area of the window is
+    /// chosen by this crate, rather than by the window class or the original window procedure.
+    #[inline]
+    fn manages_cursor(&self) -> bool {
+        self.cursor != 0 || (self.is_cursor_hidden() && self.focused)
+    }
+
+    /// Updates the cursor if it is currently over the client area of the window, as it would
+    /// otherwise only be updated when it moves.
+    ///
+    /// When the cursor is not managed by this crate, the window is asked to display its own
+    /// cursor. The arrow is displayed until then, because windows created by someone else may
+    /// have no class cursor, and a hidden cursor would otherwise remain hidden.
+    pub fn refresh_cursor(&self, hwnd: HWND) {
+        if !is_cursor_over_client_area(hwnd) {
+            return;
+        }
+
+        unsafe {
+            if self.manages_cursor() {
+                SetCursor(self.current_cursor());
+            } else {
+                SetCursor(LoadCursorW(0, IDC_ARROW));
+                let lparam = HTCLIENT | (WM_MOUSEMOVE << 16);
+                SendMessageW(hwnd, WM_SETCURSOR, hwnd as WPARAM, lparam as LPARAM);
+            }
+        }
+    }
+
+    /// Sets the way the cursor is restricted to the window, and applies it immediately.
+    pub fn set_cursor_grab(
+        &mut self,
+        hwnd: HWND,
+        mode: crate::CursorGrabMode,
+    ) -> Result<(), Error> {
+        self.cursor_grab = mode;
+        self.apply_cursor_grab(hwnd)
+    }
+
+    /// Sets whether the window is in relative mouse mode, and applies the restriction of the
+    /// cursor immediately.
+    pub fn set_relative_mouse(&mut self, hwnd: HWND, yes: bool) -> Result<(), Error> {
+        let was = std::mem::replace(&mut self.relative_mouse, yes);
+
+        let ret = self.apply_cursor_grab(hwnd);
+        if ret.is_err() {
+            // The restriction of the cursor must keep matching the mode of the window.
+            self.relative_mouse = was;
+            let _ = self.apply_cursor_grab(hwnd);
+        }
+
+        ret
+    }
+
+    /// Records that the application has captured the mouse.
+    #[inline]
+    pub fn set_mouse_captured(&mut self) {
+        self.mouse_captured = true;
+    }
+
+    /// Returns whether the window is in relative mouse mode.
+    #[inline]
+    pub fn is_relative_mouse(&self) -> bool {
+        self.relative_mouse
+    }
+
+    /// Returns the restriction of the cursor currently in effect while the window has the focus.
+    #[inline]
+    fn cursor_grab(&self) -> crate::CursorGrabMode {
+        if self.relative_mouse {
+            crate::CursorGrabMode::Locked
+        } else {
+            self.cursor_grab
+        }
+    }
+
+    /// Applies the restriction of the cursor.
+    ///
+    /// This must be called every time the client area of the window moves, as the system does
+    /// not do it automatically.
+    fn apply_cursor_grab(&self, hwnd: HWND) -> Result<(), Error> {
+        if !self.focused {
+            // The restriction is process-wide and would affect other windows.
+            return Ok(());
+        }
+
+        match self.cursor_grab() {
+            crate::CursorGrabMode::None => clip_cursor(None),
+            crate::CursorGrabMode::Confined => clip_cursor(Some(&get_client_screen_rect(hwnd)?)),
+            crate::CursorGrabMode::Locked => {
+                let rect = get_client_screen_rect(hwnd)?;
+                let (x, y) = get_cursor_pos()?;
+                let x = x.clamp(rect.left, (rect.right - 1).max(rect.left));
+                let y = y.clamp(rect.top, (rect.bottom - 1).max(rect.top));
+
+                clip_cursor(Some(&RECT {
+                    left: x,
+                    top: y,
+                    right: x + 1,
+                    bottom: y + 1,
+                }))
+            }
+        }
+    }
+
+    /// Releases the cursor if it is currently restricted to the window.
+    fn release_cursor_grab(&self) {
+        if self.cursor_grab() != crate::CursorGrabMode::None {
+            let _ = clip_cursor(None);
+        }
+    }
+
+    /// Returns the keys that are currently pressed.
+    #[inline]
+    pub fn keyboard_state(&self) -> &crate::KeyboardState {
+        &self.keyboard
+    }
+
+    /// Sets the overlay icon displayed over the taskbar button of the window, and applies it.
+    ///
+    /// The icon must remain valid for as long as it is used.
+    pub fn set_taskbar_overlay(
+        &mut self,
+        hwnd: HWND,
+        overlay: Option<(HICON, Vec<u16>)>,
+    ) -> Result<(), Error> {
+        self.taskbar_overlay = overlay;
+        self.apply_taskbar_overlay(hwnd)
+    }
+
+    /// Applies the overlay icon of the taskbar button of the window.
+    fn apply_taskbar_overlay(&self, hwnd: HWND) -> Result<(), Error> {
+        let (icon, description) = match &self.taskbar_overlay {
+            Some((icon, description)) => (*icon, description.as_slice()),
+            None => (0, &[0][..]),
+        };
+
+        TaskbarList::new()?.set_overlay_icon(hwnd, icon, description)
+    }
+
+    /// Returns the effect of dropping data on the window.
+    #[inline]
+    pub fn drop_effect(&self) -> crate::DropEffect {
+        self.drop_effect
+    }
+
+    /// Sets the effect of dropping data on the window.
+    #[inline]
+    pub fn set_drop_effect(&mut self, effect: crate::DropEffect) {
+        self.drop_effect = effect;
+    }
+
+    /// Sets whether the window is registered as a drop target.
+    ///
+    /// The registration is revoked when the window is destroyed.
+    #[inline]
+    pub fn set_drop_target(&mut self, yes: bool) {
+        self.drop_target = yes;
+    }
+
+    /// Sets the source from which the window produces its input events.
+    #[inline]
+    pub fn set_input_backend(&mut self, backend: crate::InputBackend) {
+        self.input_backend = backend;
+    }
+
+    /// Returns the source from which the window produces its input events.
+    #[inline]
+    pub fn input_backend(&self) -> crate::InputBackend {
+        self.input_backend
+    }
+
+    /// Returns whether the window is registered as a drop target.
+    #[inline]
+    pub fn is_drop_target(&self) -> bool {
+        self.drop_target
+    }
+
+    /// Sets the lock keys that are currently toggled on.
+    #[inline]
+    pub fn set_lock_keys(&mut self, keys: crate::LockKeys) {
+        self.lock_keys = keys;
+    }
+
+    /// Checks whether the lock keys have been toggled since the last check, sending an event if
+    /// they have.
+    fn update_lock_keys(&mut self) {
+        let keys = get_lock_keys();
+        if keys != self.lock_keys {
+            self.lock_keys = keys;
+            self.send_event(crate::Event::LockKeysChanged(keys));
+        }
+    }
+
+    /// Sets whether the window draws its own frame.
+    #[inline]
+    pub fn set_custom_frame(&mut self, yes: bool) {
+        self.custom_frame = yes;
+    }
+
+    /// Converts the given client size to the corresponding window size.
+    fn client_to_window_size(&self, hwnd: HWND, width: u32, height: u32) -> Option<(u32, u32)> {
+        if self.custom_frame {
+            return Some((width, height));
+        }
+
+        WindowStyles::of(hwnd)
+            .and_then(|styles| styles.client_to_window_size(width, height))
+            .ok()
+    }
+
+    /// Sets the function used to determine the area of the client area under the cursor.
+    #[inline]
+    pub fn set_hit_test(&mut self, hit_test: Option<HitTestFn>) {
+        self.hit_test = hit_test;
+    }
+
+    /// Determines the area of the window under the provided point, in screen coordinates.
+    ///
+    /// `default` is the result computed by the system. The user-provided function is only
+    /// asked about points that the system considers part of the client area.
+    fn hit_test(&mut self, hwnd: HWND, x: i32, y: i32, default: HitTest) -> HitTest {
+        if default != HitTest::Client {
+            return default;
+        }
+
+        let Some(hit_test) = self.hit_test.as_mut() else {
+            return default;
+        };
+
+        let mut point = POINT { x, y };
+        if unsafe { ScreenToClient(hwnd, &mut point) } == 0 {
+            return default;
+        }
+
+        HitTest::from(hit_test(point.x, point.y))
+    }
+
+    /// Sets the function used to provide the contents of the clipboard offered by the window.
+    #[inline]
+    pub fn set_clipboard_renderer(&mut self, renderer: Option<crate::ClipboardRenderer>) {
+        self.clipboard_renderer = renderer;
+    }
+
+    /// Provides the contents of the clipboard offered by the window in the provided format.
+    fn render_clipboard(&mut self, hwnd: HWND, format: u32) {
+        let Some(renderer) = self.clipboard_renderer.as_mut() else {
+            return;
+        };
+
+        if let Some(format) = format_from_id(format) {
+            renderer(
+                &format,
+                &mut crate::Clipboard(Clipboard::for_rendering(hwnd)),
+            );
+        }
     }
 
     /// Take an UTF-16 code point.
     pub fn take_u16_code_point(&mut self, code: u16) {
-        if is_low_surrogate(code) {
-            self.low_surrogate = code;
-        } else if is_high_surrogate(code) {
-            if self.low_surrogate == 0 {
+        if is_high_surrogate(code) {
+            self.high_surrogate = code;
+        } else if is_low_surrogate(code) {
+            if self.high_surrogate == 0 {
                 return;
             }
 
-            if let Some(c) = decode_utf16(self.low_surrogate, code) {
+            if let Some(c) = decode_utf16(self.high_surrogate, code) {
                 self.send_event(crate::Event::Text(c));
             }
 
-            self.low_surrogate = 0;
+            self.high_surrogate = 0;
         } else if let Some(c) = char::from_u32(code as u32) {
             self.send_event(crate::Event::Text(c));
 
-            self.low_surrogate = 0;
+            self.high_surrogate = 0;
         }
     }
 }
@@ -161,6 +686,42 @@ unsafe fn get_handler_in_userdata<'a, T>(hwnd: HWND) -> Option<&'a mut T> {
     }
 }
 
+/// Returns the message sent to windows when their taskbar button is created.
+///
+/// The message is sent again when the taskbar itself is re-created, for example when Windows
+/// Explorer restarts.
+fn taskbar_button_created_message() -> u32 {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static MESSAGE: AtomicU32 = AtomicU32::new(0);
+
+    let mut msg = MESSAGE.load(Ordering::Relaxed);
+    if msg == 0 {
+        let name = super::hwnd::make_utf16("TaskbarButtonCreated");
+        msg = unsafe { RegisterWindowMessageW(name.as_ptr()) };
+        MESSAGE.store(msg, Ordering::Relaxed);
+    }
+
+    msg
+}
+
+/// Returns the message broadcast to top-level windows when the taskbar is re-created, for
+/// example when Windows Explorer restarts.
+fn taskbar_created_message() -> u32 {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static MESSAGE: AtomicU32 = AtomicU32::new(0);
+
+    let mut msg = MESSAGE.load(Ordering::Relaxed);
+    if msg == 0 {
+        let name = super::hwnd::make_utf16("TaskbarCreated");
+        msg = unsafe { RegisterWindowMessageW(name.as_ptr()) };
+        MESSAGE.store(msg, Ordering::Relaxed);
+    }
+
+    msg
+}
+
 /// The window procedure for windows created by this crate.
 ///
 /// This function expects the USERDATA field of the window to be either 0, or a pointer to an
@@ -176,48 +737,413 @@ pub unsafe extern "system" fn wndproc(
             return default_wndproc(hwnd, msg, wparam, lparam);
         };
 
+        // This is the last message received by the window. The state may be dropped as soon
+        // as the window is destroyed.
+        if msg == WM_NCDESTROY {
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0);
+        }
+
+        handle_message(state, hwnd, msg, wparam, lparam, default_wndproc)
+    }
+}
+
+/// The subclass procedure for windows created by someone else.
+///
+/// `data` is expected to be a pointer to an instance of [`State`]. Unhandled messages are
+/// forwarded to the original window procedure of the window, except for `WM_CLOSE`, which is
+/// ignored just like for the windows created by this crate.
+#[cfg(feature = "raw-window-handle")]
+pub unsafe extern "system" fn subclass_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    id: usize,
+    data: usize,
+) -> LRESULT {
+    use windows_sys::Win32::UI::Shell::RemoveWindowSubclass;
+
+    /// Forwards a message to the next procedure in the subclass chain.
+    fn next_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        use windows_sys::Win32::UI::Shell::DefSubclassProc;
+
+        match msg {
+            // The window is only closed when the application decides to.
+            WM_CLOSE => 0,
+            _ => unsafe { DefSubclassProc(hwnd, msg, wparam, lparam) },
+        }
+    }
+
+    unsafe {
+        // The subclass must be removed before the window is destroyed.
+        if msg == WM_NCDESTROY {
+            RemoveWindowSubclass(hwnd, Some(subclass_proc), id);
+            return next_proc(hwnd, msg, wparam, lparam);
+        }
+
+        let state = &mut *(data as *mut State);
+        handle_message(state, hwnd, msg, wparam, lparam, next_proc)
+    }
+}
+
+/// Handles a message sent to a window, eventually converting it to a [`crate::Event`].
+///
+/// Messages that are not fully handled are passed to `next`.
+///
+/// # Safety
+///
+/// `wparam` and `lparam` must be valid for `msg`.
+unsafe fn handle_message(
+    state: &mut State,
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    next: fn(HWND, u32, WPARAM, LPARAM) -> LRESULT,
+) -> LRESULT {
+    unsafe {
         match msg {
             WM_CLOSE => {
                 state.send_event(crate::Event::CloseRequested);
             }
+            WM_DESTROY => {
+                if state.focused {
+                    state.release_cursor_grab();
+                }
+
+                if state.drop_target {
+                    revoke_drop_target(hwnd);
+                    state.drop_target = false;
+                }
+
+                state.destroyed = true;
+            }
             WM_SIZE => {
+                let _ = state.apply_cursor_grab(hwnd);
+
                 let width = lparam as u16 as u32;
                 let height = (lparam >> 16) as u16 as u32;
-                state.send_event(crate::Event::Resized { width, height });
+                state.send_event(crate::Event::Resized(crate::PhysicalSize::new(
+                    width, height,
+                )));
             }
             WM_MOVE => {
+                let _ = state.apply_cursor_grab(hwnd);
+
                 let x = lparam as i16 as i32;
                 let y = (lparam >> 16) as i16 as i32;
-                state.send_event(crate::Event::Moved { x, y });
+                state.send_event(crate::Event::Moved(crate::PhysicalPosition::new(x, y)));
             }
-            WM_MOUSEMOVE => {
+            WM_DPICHANGED => {
+                let dpi = wparam as u16 as u32;
+                state.send_event(crate::Event::ScaleFactorChanged(
+                    dpi as f64 / USER_DEFAULT_SCREEN_DPI as f64,
+                ));
+
+                // The system suggests a rectangle that keeps the physical size of the window on
+                // the new monitor.
+                let rect = &*(lparam as *const RECT);
+                SetWindowPos(
+                    hwnd,
+                    0,
+                    rect.left,
+                    rect.top,
+                    rect.right - rect.left,
+                    rect.bottom - rect.top,
+                    SWP_NOZORDER | SWP_NOACTIVATE,
+                );
+                return 0;
+            }
+            WM_MOUSEMOVE if !state.relative_mouse => {
                 let x = lparam as i16 as i32;
                 let y = (lparam >> 16) as i16 as i32;
-                state.send_event(crate::Event::CursorMoved { x, y });
+                state.send_event(crate::Event::CursorMoved(crate::PhysicalPosition::new(
+                    x, y,
+                )));
+            }
+            WM_CAPTURECHANGED if state.mouse_captured => {
+                state.mouse_captured = false;
+                state.send_event(crate::Event::MouseCaptureLost);
+            }
+            WM_NCMOUSEMOVE if wparam == HitTest::MaxButton as WPARAM && !state.relative_mouse => {
+                // The cursor is in the client area from the point of view of the application,
+                // even though the system reports it as being over the maximize button.
+                let mut point = POINT {
+                    x: lparam as i16 as i32,
+                    y: (lparam >> 16) as i16 as i32,
+                };
+
+                if ScreenToClient(hwnd, &mut point) != 0 {
+                    state.send_event(crate::Event::CursorMoved(crate::PhysicalPosition::new(
+                        point.x, point.y,
+                    )));
+                }
+            }
+            WM_NCLBUTTONDOWN if wparam == HitTest::MaxButton as WPARAM => {
+                // The default window procedure would draw the legacy maximize button over the
+                // client area.
+                return 0;
+            }
+            WM_NCLBUTTONUP if wparam == HitTest::MaxButton as WPARAM => {
+                let cmd = if IsZoomed(hwnd) != 0 {
+                    SW_RESTORE
+                } else {
+                    SW_MAXIMIZE
+                };
+                ShowWindow(hwnd, cmd);
+                return 0;
+            }
+            WM_SETCURSOR if state.manages_cursor() && lparam as u16 as u32 == HTCLIENT => {
+                // The system would otherwise use the cursor of the window class.
+                SetCursor(state.current_cursor());
+                return 1;
+            }
+            WM_SETFOCUS | WM_KILLFOCUS => {
+                state.focused = msg == WM_SETFOCUS;
+
+                if state.focused {
+                    let _ = state.apply_cursor_grab(hwnd);
+                    state.update_lock_keys();
+                } else {
+                    state.release_cursor_grab();
+                    state.keyboard.clear();
+                    state.dead_char = None;
+                }
+
+                if state.is_cursor_hidden() {
+                    state.refresh_cursor(hwnd);
+                }
+            }
+            WM_GETMINMAXINFO => {
+                state.apply_size_constraints(hwnd, &mut *(lparam as *mut MINMAXINFO));
+                return 0;
+            }
+            WM_WINDOWPOSCHANGING => {
+                let pos = &mut *(lparam as *mut WINDOWPOS);
+
+                // The system would otherwise bring the window back to the top when it is
+                // activated.
+                if state.always_on_bottom && pos.flags & SWP_NOZORDER == 0 {
+                    pos.hwndInsertAfter = HWND_BOTTOM;
+                }
+            }
+            WM_NCCALCSIZE if state.custom_frame && wparam != 0 => {
+                let params = &mut *(lparam as *mut NCCALCSIZE_PARAMS);
+
+                // Maximized windows extend beyond the edges of the monitor by the thickness of
+                // their resize borders, which must be removed from the client area.
+                if IsZoomed(hwnd) != 0 {
+                    let (x, y) = get_resize_border_thickness();
+                    let rect = &mut params.rgrc[0];
+                    rect.left += x;
+                    rect.top += y;
+                    rect.right -= x;
+                    rect.bottom -= y;
+                }
+
+                // Leaving the proposed rectangle untouched makes the client area cover the
+                // whole window.
+                return 0;
+            }
+            WM_NCHITTEST => {
+                let x = lparam as i16 as i32;
+                let y = (lparam >> 16) as i16 as i32;
+
+                let default = if state.custom_frame {
+                    custom_frame_hit_test(hwnd, x, y) as LRESULT
+                } else {
+                    next(hwnd, msg, wparam, lparam)
+                };
+
+                if default != HitTest::Client as LRESULT {
+                    return default;
+                }
+
+                return state.hit_test(hwnd, x, y, HitTest::Client) as LRESULT;
+            }
+            WM_SIZING => {
+                let rect = &mut *(lparam as *mut RECT);
+                if state.apply_aspect_ratio(hwnd, wparam as u32, rect) {
+                    return 1;
+                }
+            }
+            WM_SETTINGCHANGE if state.follow_system_theme && lparam != 0 => {
+                let area = lparam as *const u16;
+                let len = (0..).take_while(|&i| *area.add(i) != 0).count();
+
+                // This parameter is sent when the theme preference of the user changes.
+                let area = std::slice::from_raw_parts(area, len);
+                if "ImmersiveColorSet".encode_utf16().eq(area.iter().copied()) {
+                    let _ = set_dark_mode(hwnd, system_prefers_dark_mode());
+                }
             }
             WM_INPUT => {
-                handle_raw_input(lparam as HRAWINPUT, state);
+                handle_raw_input(hwnd, lparam as HRAWINPUT, state);
+            }
+            WM_KEYDOWN | WM_KEYUP | WM_SYSKEYDOWN | WM_SYSKEYUP => {
+                // The toggle state of the lock keys is updated when these messages are
+                // retrieved, which happens after the corresponding raw input is received.
+                state.update_lock_keys();
+
+                if state.input_backend != crate::InputBackend::RawInput {
+                    handle_key_message(hwnd, msg, wparam, lparam, state);
+                }
+            }
+            WM_LBUTTONDOWN | WM_LBUTTONDBLCLK | WM_LBUTTONUP | WM_MBUTTONDOWN
+            | WM_MBUTTONDBLCLK | WM_MBUTTONUP | WM_RBUTTONDOWN | WM_RBUTTONDBLCLK
+            | WM_RBUTTONUP | WM_XBUTTONDOWN | WM_XBUTTONDBLCLK | WM_XBUTTONUP
+                if state.input_backend != crate::InputBackend::RawInput =>
+            {
+                handle_button_message(msg, wparam, state);
+            }
+            WM_MOUSEWHEEL | WM_MOUSEHWHEEL
+                if state.input_backend != crate::InputBackend::RawInput =>
+            {
+                // The delta is a multiple of `WHEEL_DELTA` for one notch of the wheel.
+                let delta = (wparam >> 16) as i16 as f64 / WHEEL_DELTA as f64;
+                let (dx, dy) = if msg == WM_MOUSEWHEEL {
+                    (0.0, delta)
+                } else {
+                    (delta, 0.0)
+                };
+
+                state.send_event(crate::Event::MouseWheel {
+                    device: crate::Device(0),
+                    dx,
+                    dy,
+                });
+            }
+            WM_DEADCHAR | WM_SYSDEADCHAR => {
+                // Nothing is entered until the dead key is composed with the next key.
+                state.dead_char = char::from_u32(wparam as u32);
+                return 0;
             }
             WM_CHAR => {
+                state.dead_char = None;
                 state.take_u16_code_point(wparam as u16);
             }
+            WM_INPUTLANGCHANGE => {
+                let layout = crate::KeyboardLayout(KeyboardLayout::from_raw(lparam as HKL));
+                state.send_event(crate::Event::KeyboardLayoutChanged(layout));
+            }
+            // The system uses negative identifiers for its own hotkeys (`IDHOT_SNAPDESKTOP` and
+            // `IDHOT_SNAPWINDOW`), which would otherwise collide with the ones of the application.
+            WM_HOTKEY if (wparam as isize) >= 0 => {
+                state.send_event(crate::Event::Hotkey(crate::HotkeyId(wparam as u16)));
+            }
+            WM_TRAY_ICON => {
+                if let Some(event) = tray_icon_event(hwnd, wparam, lparam) {
+                    state.send_event(event);
+                }
+                return 0;
+            }
+            WM_RENDERFORMAT => {
+                state.render_clipboard(hwnd, wparam as u32);
+                return 0;
+            }
+            WM_RENDERALLFORMATS => {
+                use windows_sys::Win32::System::DataExchange::GetClipboardOwner;
+
+                // The window is being destroyed, and must provide all the formats it has
+                // offered, unless another application has taken the clipboard over in the
+                // meantime.
+                if let Ok(_clipboard) = Clipboard::open(hwnd) {
+                    if GetClipboardOwner() == hwnd {
+                        for format in offered_formats() {
+                            state.render_clipboard(hwnd, format);
+                        }
+                    }
+                }
+                return 0;
+            }
+            WM_DESTROYCLIPBOARD => forget_offered_formats(),
+            WM_COPYDATA => {
+                use windows_sys::Win32::System::DataExchange::COPYDATASTRUCT;
+
+                let data = &*(lparam as *const COPYDATASTRUCT);
+                if data.dwData == COPYDATA_INSTANCE_ARGUMENTS {
+                    if let Some(arguments) = decode_instance_arguments(hwnd, data) {
+                        state.send_event(crate::Event::InstanceActivated { arguments });
+                        return 1;
+                    }
+                }
+            }
+            _ if msg == taskbar_button_created_message() && state.taskbar_overlay.is_some() => {
+                let _ = state.apply_taskbar_overlay(hwnd);
+            }
+            _ if msg == taskbar_created_message() => restore_tray_icons(hwnd),
             _ => (),
         }
 
-        default_wndproc(hwnd, msg, wparam, lparam)
+        next(hwnd, msg, wparam, lparam)
+    }
+}
+
+/// Determines the area of a window that draws its own frame under the provided point, in screen
+/// coordinates.
+///
+/// Such windows have no non-client area, so the resize borders must be detected manually. They
+/// are located within the edges of the client area.
+fn custom_frame_hit_test(hwnd: HWND, x: i32, y: i32) -> HitTest {
+    let Ok(styles) = WindowStyles::of(hwnd) else {
+        return HitTest::Client;
+    };
+
+    if !styles.contains(WindowStyles::SIZE_BOX) || styles.contains(WindowStyles::MAXIMIZED) {
+        return HitTest::Client;
+    }
+
+    let mut rect: RECT = unsafe { std::mem::zeroed() };
+    if unsafe { GetWindowRect(hwnd, &mut rect) } == 0 {
+        return HitTest::Client;
+    }
+
+    let (border_x, border_y) = get_resize_border_thickness();
+
+    let left = x < rect.left + border_x;
+    let right = x >= rect.right - border_x;
+    let top = y < rect.top + border_y;
+    let bottom = y >= rect.bottom - border_y;
+
+    match (left, right, top, bottom) {
+        (true, _, true, _) => HitTest::TopLeft,
+        (_, true, true, _) => HitTest::TopRight,
+        (true, _, _, true) => HitTest::BottomLeft,
+        (_, true, _, true) => HitTest::BottomRight,
+        (true, _, _, _) => HitTest::Left,
+        (_, true, _, _) => HitTest::Right,
+        (_, _, true, _) => HitTest::Top,
+        (_, _, _, true) => HitTest::Bottom,
+        _ => HitTest::Client,
     }
 }
 
 /// Handles a raw input event, eventually converting it to a [`crate::Event`].
-fn handle_raw_input(handle: HRAWINPUT, state: &mut State) {
+fn handle_raw_input(hwnd: HWND, handle: HRAWINPUT, state: &mut State) {
     let Some(rawinput) = read_rawinput(handle) else {
         return;
     };
 
+    handle_rawinput_packet(hwnd, &rawinput, None, state);
+
+    // The packets queued after this one are read at once.
+    read_raw_input_buffer(hwnd, state);
+}
+
+/// Handles a raw input packet.
+///
+/// `next` is the packet that follows it, if it is known. Otherwise, it is looked up in the
+/// message queue when needed.
+fn handle_rawinput_packet(
+    hwnd: HWND,
+    rawinput: &RAWINPUT,
+    next: Option<&RAWINPUT>,
+    state: &mut State,
+) {
     match rawinput.header.dwType {
-        RIM_TYPEKEYBOARD => {
+        RIM_TYPEKEYBOARD if state.input_backend == crate::InputBackend::RawInput => {
             let keyboard = unsafe { &rawinput.data.keyboard };
-            handle_keyboard_event(rawinput.header.hDevice, keyboard, state);
+            handle_keyboard_event(hwnd, rawinput.header.hDevice, keyboard, next, state);
         }
         RIM_TYPEMOUSE => {
             let mouse = unsafe { &rawinput.data.mouse };
@@ -227,6 +1153,116 @@ fn handle_raw_input(handle: HRAWINPUT, state: &mut State) {
     }
 }
 
+/// Reads the raw input packets queued for the calling thread, and handles them in order.
+///
+/// At high polling rates, reading each packet from its own `WM_INPUT` message costs a system
+/// call per packet. The packets read here are removed from the message queue, such that their
+/// messages are never received.
+///
+/// Packets do not identify the window they are sent to. They are only read this way when the
+/// window is the only one registered for raw input, such that they all belong to it.
+fn read_raw_input_buffer(hwnd: HWND, state: &mut State) {
+    /// The number of `u64`s in the buffer, which fits 64 mouse packets.
+    const BUFFER_LEN: usize = 64 * size_of::<RAWINPUT>() / size_of::<u64>();
+    const HEADER_SIZE: u32 = size_of::<RAWINPUTHEADER>() as u32;
+
+    // The packets are laid out with 64-bit headers for 32-bit processes running on 64-bit
+    // systems, which keep reading them one message at a time.
+    if cfg!(target_pointer_width = "32") && is_wow64() {
+        return;
+    }
+
+    if !is_sole_raw_input_target(hwnd) {
+        return;
+    }
+
+    // The buffer is taken out of the state, which is borrowed mutably to handle the packets.
+    let mut buffer = std::mem::take(&mut state.raw_input_buffer);
+    buffer.resize(BUFFER_LEN, 0);
+
+    loop {
+        let mut size = (buffer.len() * size_of::<u64>()) as u32;
+        let data = buffer.as_mut_ptr() as *mut RAWINPUT;
+        let count = unsafe { GetRawInputBuffer(data, &mut size, HEADER_SIZE) };
+
+        // An error is returned when the next packet does not fit in the buffer, in which case
+        // it is read from its message.
+        if count == 0 || count == u32::MAX {
+            break;
+        }
+
+        // Each packet is handled knowing the one that follows it.
+        let mut ptr = data as *const u8;
+        let mut packet = unsafe { read_buffered_rawinput(ptr) };
+        for i in 1..=count {
+            let next = (i < count).then(|| unsafe {
+                ptr = next_rawinput_block(ptr);
+                read_buffered_rawinput(ptr)
+            });
+
+            handle_rawinput_packet(hwnd, &packet, next.as_ref(), state);
+
+            match next {
+                Some(next) => packet = next,
+                None => break,
+            }
+        }
+
+        unsafe { DefRawInputProc(&(data as *const RAWINPUT), count as i32, HEADER_SIZE) };
+    }
+
+    state.raw_input_buffer = buffer;
+}
+
+/// Returns whether the process is a 32-bit process running on a 64-bit system.
+fn is_wow64() -> bool {
+    use std::sync::OnceLock;
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, IsWow64Process};
+
+    static WOW64: OnceLock<bool> = OnceLock::new();
+
+    *WOW64.get_or_init(|| {
+        let mut wow64 = 0;
+        let ret = unsafe { IsWow64Process(GetCurrentProcess(), &mut wow64) };
+        ret != 0 && wow64 != 0
+    })
+}
+
+/// Copies the raw input packet that starts at `ptr` in a buffer filled by `GetRawInputBuffer`.
+///
+/// # Safety
+///
+/// `ptr` must point to the start of a packet of the buffer.
+unsafe fn read_buffered_rawinput(ptr: *const u8) -> RAWINPUT {
+    unsafe {
+        let size = (*(ptr as *const RAWINPUTHEADER)).dwSize as usize;
+
+        // Packets of other devices may be larger than a `RAWINPUT`, and smaller packets may end
+        // at the end of the buffer.
+        let mut rawinput: RAWINPUT = std::mem::zeroed();
+        let len = size.min(size_of::<RAWINPUT>());
+        std::ptr::copy_nonoverlapping(ptr, &mut rawinput as *mut RAWINPUT as *mut u8, len);
+        rawinput
+    }
+}
+
+/// Returns a pointer to the packet that follows the one that starts at `ptr`, in a buffer
+/// filled by `GetRawInputBuffer`.
+///
+/// This is the `NEXTRAWINPUTBLOCK` macro.
+///
+/// # Safety
+///
+/// `ptr` must point to the start of a packet of the buffer.
+unsafe fn next_rawinput_block(ptr: *const u8) -> *const u8 {
+    unsafe {
+        let size = (*(ptr as *const RAWINPUTHEADER)).dwSize as usize;
+        let next = ptr as usize + size;
+        let aligned = (next + size_of::<u64>() - 1) & !(size_of::<u64>() - 1);
+        ptr.add(aligned - ptr as usize)
+    }
+}
+
 /// Reads a [`RAWINPUT`] from the given handle.
 fn read_rawinput(handle: HRAWINPUT) -> Option<RAWINPUT> {
     unsafe {
@@ -250,22 +1286,238 @@ fn read_rawinput(handle: HRAWINPUT) -> Option<RAWINPUT> {
 }
 
 /// Handles a raw keyboard event.
-fn handle_keyboard_event(device: HANDLE, keyboard: &RAWKEYBOARD, state: &mut State) {
+///
+/// `next` is the raw input packet that follows the event, if it is known.
+fn handle_keyboard_event(
+    hwnd: HWND,
+    device: HANDLE,
+    keyboard: &RAWKEYBOARD,
+    next: Option<&RAWINPUT>,
+    state: &mut State,
+) {
     // Skip "fake" keys.
-    if keyboard.VKey == 255 {
+    if keyboard.VKey == 255 || is_altgr_control(hwnd, keyboard, next) {
         return;
     }
 
+    let pressed = (keyboard.Flags as u32 & RI_KEY_BREAK) == RI_KEY_MAKE;
+    let e0 = (keyboard.Flags as u32 & RI_KEY_E0) != 0;
+    let key = compute_key(keyboard.VKey, keyboard.MakeCode, e0);
+
+    if let Some(key) = key {
+        state.keyboard.set_pressed(key, pressed);
+    }
+
     state.send_event(crate::Event::KeyboardKey {
         device: crate::Device(device),
         code: crate::KeyCode(make_keycode(keyboard)),
-        pressed: (keyboard.Flags as u32 & RI_KEY_BREAK) == RI_KEY_MAKE,
-        key: compute_key(keyboard),
+        virtual_key: keyboard.VKey as u32,
+        key_without_modifiers: char_without_modifiers(keyboard.VKey, keyboard.MakeCode),
+        pressed,
+        key,
+    });
+}
+
+/// Handles a keyboard message, for windows that do not produce their keyboard events from raw
+/// input.
+fn handle_key_message(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM, state: &mut State) {
+    let vkey = wparam as u16;
+    let make_code = ((lparam >> 16) & 0xFF) as u16;
+    let e0 = (lparam >> 24) & 1 != 0;
+
+    // Skip "fake" keys.
+    if vkey == 255 || is_altgr_control_message(hwnd, vkey, lparam) {
+        return;
+    }
+
+    let pressed = msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN;
+    let key = compute_key(vkey, make_code, e0);
+
+    if let Some(key) = key {
+        state.keyboard.set_pressed(key, pressed);
+    }
+
+    state.send_event(crate::Event::KeyboardKey {
+        device: crate::Device(0),
+        code: crate::KeyCode(KeyCode {
+            code: make_code,
+            extended_flags: e0 as u8,
+        }),
+        virtual_key: vkey as u32,
+        key_without_modifiers: char_without_modifiers(vkey, make_code),
+        pressed,
+        key,
+    });
+}
+
+/// Handles a mouse button message, for windows that do not produce their mouse button events
+/// from raw input.
+fn handle_button_message(msg: u32, wparam: WPARAM, state: &mut State) {
+    let (button, pressed) = match msg {
+        WM_LBUTTONDOWN | WM_LBUTTONDBLCLK => (crate::MouseButton::LEFT, true),
+        WM_LBUTTONUP => (crate::MouseButton::LEFT, false),
+        WM_MBUTTONDOWN | WM_MBUTTONDBLCLK => (crate::MouseButton::MIDDLE, true),
+        WM_MBUTTONUP => (crate::MouseButton::MIDDLE, false),
+        WM_RBUTTONDOWN | WM_RBUTTONDBLCLK => (crate::MouseButton::RIGHT, true),
+        WM_RBUTTONUP => (crate::MouseButton::RIGHT, false),
+        _ => {
+            // The extra buttons are numbered like their raw input counterparts.
+            let button = match (wparam >> 16) as u16 {
+                XBUTTON1 => crate::MouseButton(4),
+                XBUTTON2 => crate::MouseButton(5),
+                _ => return,
+            };
+            (button, msg != WM_XBUTTONUP)
+        }
+    };
+
+    state.send_event(crate::Event::MouseButton {
+        device: crate::Device(0),
+        button,
+        pressed,
     });
 }
 
-/// Computes the [`crate::Key`] associated with the provided event.
-fn compute_key(event: &RAWKEYBOARD) -> Option<crate::Key> {
+/// Returns the character produced by the provided key when no modifier is held.
+fn char_without_modifiers(vkey: u16, make_code: u16) -> Option<char> {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::ToUnicode;
+
+    /// Prevents `ToUnicode` from modifying the state of the keyboard, which would break dead
+    /// keys typed by the user.
+    const DONT_CHANGE_KEYBOARD_STATE: u32 = 0x04;
+
+    let keys = [0u8; 256];
+    let mut buf = [0u16; 4];
+    let ret = unsafe {
+        ToUnicode(
+            vkey as u32,
+            make_code as u32,
+            keys.as_ptr(),
+            buf.as_mut_ptr(),
+            buf.len() as i32,
+            DONT_CHANGE_KEYBOARD_STATE,
+        )
+    };
+
+    // A negative value indicates a dead key, whose character is still written to the buffer.
+    let len = match ret {
+        0 => return None,
+        ret if ret < 0 => 1,
+        ret => ret as usize,
+    };
+
+    let c = std::char::decode_utf16(buf[..len].iter().copied())
+        .next()?
+        .ok()?;
+
+    // Keys such as ENTER or ESCAPE produce control characters.
+    if c.is_control() {
+        None
+    } else {
+        Some(c)
+    }
+}
+
+/// Returns whether the provided event is the fake **CONTROL** key event that the system sends
+/// along with the **ALTGR** key.
+///
+/// Layouts with an **ALTGR** key implement it as **CONTROL** + **RIGHT ALT**. Both events are
+/// sent at the same time, the left control one first.
+///
+/// `next` is the raw input packet that follows the event, if it is known. Otherwise, it is
+/// looked up in the message queue.
+fn is_altgr_control(hwnd: HWND, keyboard: &RAWKEYBOARD, next: Option<&RAWINPUT>) -> bool {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{VK_CONTROL, VK_MENU};
+
+    if keyboard.VKey != VK_CONTROL || keyboard.Flags as u32 & RI_KEY_E0 != 0 {
+        return false;
+    }
+
+    let next = match next {
+        Some(next) => *next,
+        None => match peek_next_rawinput(hwnd) {
+            Some(next) => next,
+            None => return false,
+        },
+    };
+
+    if next.header.dwType != RIM_TYPEKEYBOARD {
+        return false;
+    }
+
+    let next = unsafe { &next.data.keyboard };
+    next.VKey == VK_MENU
+        && next.Flags as u32 & RI_KEY_E0 != 0
+        && next.Flags as u32 & RI_KEY_BREAK == keyboard.Flags as u32 & RI_KEY_BREAK
+}
+
+/// Returns the raw input packet of the next `WM_INPUT` message in the queue, if it has been
+/// sent at the same time as the message being handled.
+fn peek_next_rawinput(hwnd: HWND) -> Option<RAWINPUT> {
+    unsafe {
+        // Only input messages are looked at. Processing sent messages here would call the
+        // window procedure again while the state is borrowed.
+        let mut next: MSG = std::mem::zeroed();
+        let flags = PM_NOREMOVE | PM_QS_INPUT;
+        if PeekMessageW(&mut next, hwnd, WM_INPUT, WM_INPUT, flags) == 0 {
+            return None;
+        }
+
+        if next.time != GetMessageTime() as u32 {
+            return None;
+        }
+
+        read_rawinput(next.lParam as HRAWINPUT)
+    }
+}
+
+/// Returns whether the provided keyboard message is the fake **CONTROL** key message that the
+/// system sends along with the **ALTGR** key.
+///
+/// See [`is_altgr_control`].
+fn is_altgr_control_message(hwnd: HWND, vkey: u16, lparam: LPARAM) -> bool {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{VK_CONTROL, VK_MENU};
+
+    const EXTENDED: LPARAM = 1 << 24;
+    const RELEASED: LPARAM = 1 << 31;
+
+    if vkey != VK_CONTROL || lparam & EXTENDED != 0 {
+        return false;
+    }
+
+    unsafe {
+        let mut next: MSG = std::mem::zeroed();
+        let flags = PM_NOREMOVE | PM_QS_INPUT;
+        if PeekMessageW(&mut next, hwnd, WM_KEYFIRST, WM_KEYLAST, flags) == 0 {
+            return false;
+        }
+
+        next.time == GetMessageTime() as u32
+            && next.wParam == VK_MENU as WPARAM
+            && next.lParam & EXTENDED != 0
+            && next.lParam & RELEASED == lparam & RELEASED
+    }
+}
+
+/// Returns the virtual-key code that produces the provided [`crate::Key`].
+///
+/// Keys that are only distinguished by their E0 prefix (such as the two **ENTER** keys) share
+/// the same virtual-key code.
+pub fn virtual_key_of(key: crate::Key) -> Option<u16> {
+    // The virtual-key codes of specific keys (e.g. `VK_LSHIFT` or `VK_NUMPAD9`) are larger than
+    // the generic ones that also produce them (e.g. `VK_SHIFT` or `VK_PRIOR`), which are only
+    // used as a last resort.
+    (1..0xFF).rev().find(|&vkey| {
+        [true, false]
+            .into_iter()
+            .any(|e0| compute_key(vkey, 0, e0) == Some(key))
+    })
+}
+
+/// Computes the [`crate::Key`] associated with the provided virtual-key code.
+///
+/// `make_code` is the scan code of the key, and `e0` indicates whether it has an E0 prefix.
+pub fn compute_key(vkey: u16, make_code: u16, e0: bool) -> Option<crate::Key> {
     // This this for the details:
     //
     //     https://blog.molecular-matters.com/2011/09/05/properly-handling-keyboard-input/
@@ -274,9 +1526,7 @@ fn compute_key(event: &RAWKEYBOARD) -> Option<crate::Key> {
 
     use windows_sys::Win32::UI::Input::KeyboardAndMouse::*;
 
-    let e0 = (event.Flags as u32 & RI_KEY_E0) != 0;
-
-    match event.VKey {
+    match vkey {
         VK_BACK => Some(crate::Key::Backspace),
         VK_TAB => Some(crate::Key::Tab),
         VK_RETURN => {
@@ -423,7 +1673,7 @@ fn compute_key(event: &RAWKEYBOARD) -> Option<crate::Key> {
         VK_LSHIFT => Some(crate::Key::LeftShift),
         VK_RSHIFT => Some(crate::Key::RightShift),
         VK_SHIFT => {
-            if event.MakeCode == 0x36 {
+            if make_code == 0x36 {
                 Some(crate::Key::RightShift)
             } else {
                 Some(crate::Key::LeftShift)
@@ -509,6 +1759,11 @@ fn handle_mouse_event(device: HANDLE, mouse: &RAWMOUSE, state: &mut State) {
         });
     }
 
+    // The other events are produced from window messages.
+    if state.input_backend != crate::InputBackend::RawInput {
+        return;
+    }
+
     let btnflags = unsafe { mouse.Anonymous.Anonymous.usButtonFlags } as u32;
 
     if (btnflags & RI_MOUSE_LEFT_BUTTON_DOWN) != 0 {
@@ -622,9 +1877,9 @@ fn is_high_surrogate(code: u16) -> bool {
     (0xD800..=0xDBFF).contains(&code)
 }
 
-/// Decodes the provided low and high surrogates into an UTF-32 code point.
-fn decode_utf16(low: u16, high: u16) -> Option<char> {
-    std::char::decode_utf16([low, high])
+/// Decodes the provided high and low surrogates into an UTF-32 code point.
+fn decode_utf16(high: u16, low: u16) -> Option<char> {
+    std::char::decode_utf16([high, low])
         .next()
         .and_then(Result::ok)
 }
@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use crate::{imp, Error};
+
+/// A custom task of a [`JumpList`].
+///
+/// Activating a task launches the application again with the provided arguments. To hand the
+/// task over to the instance that is already running, the new instance can call
+/// [`forward_to_running_instance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JumpListTask<'a> {
+    /// The title of the task, displayed in the jump list.
+    pub title: &'a str,
+    /// The command-line arguments the application is launched with when the task is activated.
+    ///
+    /// Arguments that contain spaces must be quoted.
+    pub arguments: &'a str,
+    /// The description of the task, displayed as a tooltip.
+    pub description: &'a str,
+}
+
+/// The list of entries displayed when the taskbar button of the application is right-clicked.
+///
+/// The jump list belongs to the application rather than to one of its windows, and persists
+/// after the application has exited. Calling [`JumpList::apply`] replaces its previous contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JumpList<'a> {
+    /// The application user model ID of the application whose jump list is replaced.
+    ///
+    /// This must match the [`app_id`](crate::WindowAttributes::app_id) of the windows of the
+    /// application, if any.
+    ///
+    /// **Default:** `None`
+    pub app_id: Option<&'a str>,
+    /// The custom tasks of the jump list.
+    ///
+    /// **Default:** `&[]`
+    pub tasks: &'a [JumpListTask<'a>],
+    /// Whether the documents recently opened by the application are displayed.
+    ///
+    /// Documents are added to this list with [`add_recent_document`].
+    ///
+    /// **Default:** `true`
+    pub show_recent: bool,
+}
+
+impl<'a> Default for JumpList<'a> {
+    fn default() -> Self {
+        Self {
+            app_id: None,
+            tasks: &[],
+            show_recent: true,
+        }
+    }
+}
+
+impl<'a> JumpList<'a> {
+    /// Replaces the jump list of the application with this one.
+    #[inline(always)]
+    pub fn apply(&self) -> Result<(), Error> {
+        imp::apply_jump_list(self).map_err(Error::os)
+    }
+}
+
+/// Adds the provided file to the documents recently opened by the application.
+///
+/// The document is displayed in the jump list of the application when
+/// [`JumpList::show_recent`] is set, and activating it launches the application with its path
+/// as the only argument.
+///
+/// # Platform-specific
+///
+/// - **Windows:** the application must be registered as a handler of the type of the file for
+///   it to appear in the jump list.
+#[inline(always)]
+pub fn add_recent_document(path: &Path) {
+    imp::add_recent_document(path)
+}
+
+/// Forwards the command-line arguments of the current process to another running instance of
+/// the application, if any.
+///
+/// Instances are identified by `instance_name`, which must be unique to the application: only
+/// the windows created with the same [`instance_name`](crate::WindowAttributes::instance_name)
+/// receive the arguments, through an [`Event::InstanceActivated`] event. The running instance is
+/// allowed to bring itself to the foreground.
+///
+/// This is typically called on startup, before creating any window, such that activating an
+/// entry of the jump list is handled by the instance that is already running. `true` is
+/// returned if the arguments have been received, in which case the current process should
+/// exit.
+///
+/// [`Event::InstanceActivated`]: crate::Event::InstanceActivated
+#[inline(always)]
+pub fn forward_to_running_instance(instance_name: &str) -> bool {
+    imp::forward_to_running_instance(instance_name)
+}
@@ -0,0 +1,75 @@
+use crate::imp;
+
+/// A keyboard layout installed on the system.
+///
+/// The keyboard layout determines which [`Key`](crate::Key) each physical key produces, and
+/// which characters are entered when typing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyboardLayout(pub(crate) imp::KeyboardLayout);
+
+impl KeyboardLayout {
+    /// Returns the keyboard layout currently active for the calling thread.
+    ///
+    /// Events are processed on the thread that created the window, so this is the layout used to
+    /// interpret its keyboard input.
+    #[inline(always)]
+    pub fn current() -> Self {
+        Self(imp::KeyboardLayout::current())
+    }
+
+    /// Returns the keyboard layouts installed on the system.
+    ///
+    /// These are the layouts the user can switch between.
+    #[inline(always)]
+    pub fn installed() -> Vec<Self> {
+        imp::KeyboardLayout::installed()
+            .into_iter()
+            .map(Self)
+            .collect()
+    }
+
+    /// Returns the identifier of the language of the keyboard layout, as an IETF BCP 47
+    /// language tag (e.g. `"fr-FR"`).
+    ///
+    /// Note that multiple layouts may share the same language, such as the US and US
+    /// International layouts. If the language is unknown, `None` is returned.
+    #[inline(always)]
+    pub fn language(&self) -> Option<String> {
+        self.0.language()
+    }
+
+    /// Returns the identifier of the keyboard layout (e.g. `"00000409"` for the US layout, and
+    /// `"00010409"` for the US Dvorak layout).
+    ///
+    /// Unlike the [`language`](KeyboardLayout::language), it tells apart the layouts of a same
+    /// language. If the layout is unknown, `None` is returned.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** this is the keyboard layout identifier (KLID), eight hexadecimal digits.
+    #[inline(always)]
+    pub fn identifier(&self) -> Option<String> {
+        self.0.identifier()
+    }
+
+    /// Returns the human-readable name of the language of the keyboard layout (e.g.
+    /// `"French (France)"`), localized in the language of the user interface.
+    ///
+    /// If the language is unknown, `None` is returned.
+    #[inline(always)]
+    pub fn display_name(&self) -> Option<String> {
+        self.0.display_name()
+    }
+}
+
+/// Serializes the keyboard layout as its [language](KeyboardLayout::language) tag, or as `None`
+/// when it is unknown.
+#[cfg(feature = "serde")]
+impl serde::Serialize for KeyboardLayout {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.language().serialize(serializer)
+    }
+}
@@ -4,14 +4,36 @@
 #![warn(missing_docs)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
+mod clipboard;
 mod config;
+mod cursor;
+mod dialog;
+mod dpi;
+mod drag;
 mod error;
 mod event;
+mod icon;
+mod jump_list;
+mod keyboard;
+mod monitor;
+mod tray;
 mod window;
 
+pub mod platform;
+
+pub use self::clipboard::*;
 pub use self::config::*;
+pub use self::cursor::*;
+pub use self::dialog::*;
+pub use self::dpi::*;
+pub use self::drag::*;
 pub use self::error::*;
 pub use self::event::*;
+pub use self::icon::*;
+pub use self::jump_list::*;
+pub use self::keyboard::*;
+pub use self::monitor::*;
+pub use self::tray::*;
 pub use self::window::*;
 
 #[cfg_attr(target_os = "windows", path = "imp/windows/mod.rs")]
@@ -0,0 +1,177 @@
+use crate::{imp, PhysicalPosition, PhysicalSize};
+
+/// Returns the primary monitor of the system.
+///
+/// The primary monitor is the one that holds the taskbar, and whose top-left corner is the
+/// origin of the coordinate system of all monitors.
+#[inline(always)]
+pub fn primary_monitor() -> Monitor {
+    Monitor(imp::Monitor::primary())
+}
+
+/// A monitor connected to the system.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Monitor(pub(crate) imp::Monitor);
+
+impl Monitor {
+    /// Returns the human-readable name of the monitor (e.g. `"DELL U2415"`).
+    ///
+    /// The name is usually read from the monitor itself, and is not guaranteed to be unique.
+    /// If the name is unknown or the monitor has been disconnected, `None` is returned.
+    #[inline(always)]
+    pub fn name(&self) -> Option<String> {
+        self.0.name()
+    }
+
+    /// Returns an identifier of the physical monitor that remains the same across reboots.
+    ///
+    /// This is typically stored along with the position of a window, to restore the window on
+    /// the same monitor the next time the application starts. If the monitor has been
+    /// disconnected, `None` is returned.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** this is the device interface path of the monitor, which identifies both
+    ///   the model of the monitor and the connector it is plugged into.
+    #[inline(always)]
+    pub fn id(&self) -> Option<String> {
+        self.0.id()
+    }
+
+    /// Returns the position of the top-left corner of the monitor, in pixels.
+    ///
+    /// The position is expressed relative to the top-left corner of the primary monitor. If
+    /// the monitor has been disconnected, `(0, 0)` is returned.
+    #[inline(always)]
+    pub fn position(&self) -> PhysicalPosition {
+        self.0.position().into()
+    }
+
+    /// Returns the size of the monitor, in pixels.
+    ///
+    /// If the monitor has been disconnected, `(0, 0)` is returned.
+    #[inline(always)]
+    pub fn size(&self) -> PhysicalSize {
+        self.0.size().into()
+    }
+
+    /// Returns the position of the top-left corner of the work area of the monitor, in pixels.
+    ///
+    /// The work area is the part of the monitor that is not covered by the taskbar and other
+    /// docked toolbars. This is the area a maximized window covers. If the monitor has been
+    /// disconnected, `(0, 0)` is returned.
+    #[inline(always)]
+    pub fn work_area_position(&self) -> PhysicalPosition {
+        self.0.work_area_position().into()
+    }
+
+    /// Returns the size of the work area of the monitor, in pixels.
+    ///
+    /// See [`work_area_position`](Monitor::work_area_position) for more information. If the
+    /// monitor has been disconnected, `(0, 0)` is returned.
+    #[inline(always)]
+    pub fn work_area_size(&self) -> PhysicalSize {
+        self.0.work_area_size().into()
+    }
+
+    /// Returns the scale factor of the monitor, which depends on its DPI.
+    ///
+    /// A scale factor of `1.0` corresponds to 96 DPI. This is the scale factor a window placed
+    /// on the monitor would have (see [`Window::scale_factor`]). If the monitor has been
+    /// disconnected, `1.0` is returned.
+    ///
+    /// [`Window::scale_factor`]: crate::Window::scale_factor
+    #[inline(always)]
+    pub fn scale_factor(&self) -> f64 {
+        self.0.scale_factor()
+    }
+
+    /// Returns the number of bits used to represent each color channel of the monitor.
+    ///
+    /// This is the depth of the signal sent to the monitor (e.g. `8` or `10`), which is not
+    /// necessarily the same as the [`bit_depth`](VideoMode::bit_depth) of its video mode. If
+    /// it is unknown or the monitor has been disconnected, `None` is returned.
+    #[inline(always)]
+    pub fn bits_per_color(&self) -> Option<u32> {
+        self.0.bits_per_color()
+    }
+
+    /// Returns whether the monitor supports high dynamic range (HDR) output.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** this is the "advanced color" capability of the display, which also
+    ///   covers wide color gamut displays.
+    #[inline(always)]
+    pub fn is_hdr_supported(&self) -> bool {
+        self.0.is_hdr_supported()
+    }
+
+    /// Returns whether high dynamic range (HDR) output is currently enabled on the monitor.
+    ///
+    /// When it is, renderers should prefer a swapchain format with more than 8 bits per
+    /// channel and a linear or PQ color space to make use of it.
+    #[inline(always)]
+    pub fn is_hdr_enabled(&self) -> bool {
+        self.0.is_hdr_enabled()
+    }
+
+    /// Returns the brightness used for standard dynamic range (SDR) white, in nits.
+    ///
+    /// When HDR output is enabled, SDR content should be scaled such that its white matches
+    /// this level. If it is unknown or the monitor has been disconnected, `None` is returned.
+    #[inline(always)]
+    pub fn sdr_white_level(&self) -> Option<f32> {
+        self.0.sdr_white_level()
+    }
+
+    /// Returns the video mode the monitor is currently using.
+    ///
+    /// If the monitor has been disconnected, `None` is returned.
+    #[inline(always)]
+    pub fn current_video_mode(&self) -> Option<VideoMode> {
+        self.0.current_video_mode().map(VideoMode)
+    }
+
+    /// Returns the video modes supported by the monitor.
+    ///
+    /// The modes are listed in the order reported by the system, without duplicates. If the
+    /// monitor has been disconnected, an empty list is returned.
+    #[inline(always)]
+    pub fn video_modes(&self) -> Vec<VideoMode> {
+        self.0.video_modes().into_iter().map(VideoMode).collect()
+    }
+}
+
+/// A video mode supported by a monitor.
+///
+/// Video modes are used to make a window enter exclusive fullscreen mode with
+/// [`Fullscreen::Exclusive`](crate::Fullscreen::Exclusive).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VideoMode(pub(crate) imp::VideoMode);
+
+impl VideoMode {
+    /// Returns the monitor this video mode applies to.
+    #[inline(always)]
+    pub fn monitor(&self) -> Monitor {
+        Monitor(self.0.monitor().clone())
+    }
+
+    /// Returns the resolution of the video mode, in pixels.
+    #[inline(always)]
+    pub fn size(&self) -> PhysicalSize {
+        self.0.size().into()
+    }
+
+    /// Returns the number of bits used to represent the color of a pixel.
+    #[inline(always)]
+    pub fn bit_depth(&self) -> u16 {
+        self.0.bit_depth()
+    }
+
+    /// Returns the refresh rate of the video mode, in hertz.
+    #[inline(always)]
+    pub fn refresh_rate(&self) -> u32 {
+        self.0.refresh_rate()
+    }
+}
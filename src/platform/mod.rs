@@ -0,0 +1,18 @@
+//! Platform-specific functionality.
+//!
+//! Each module extends the types of the crate with functionality that is only available on one
+//! platform, through extension traits. A module is only available when compiling for its
+//! platform.
+
+#[cfg(target_os = "windows")]
+pub mod windows;
+
+/// Prevents the extension traits from being implemented outside of the crate, such that new
+/// methods can be added to them.
+#[allow(dead_code)]
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::Window {}
+    impl Sealed for crate::Config<'_> {}
+}
@@ -0,0 +1,79 @@
+//! Functionality specific to Windows.
+
+use super::sealed::Sealed;
+use crate::{imp, Config, Error, Window};
+
+/// Windows-specific extensions to [`Window`].
+pub trait WindowExtWindows: Sealed {
+    /// Returns the `CS_*` styles of the class of the window.
+    fn class_style(&self) -> u32;
+
+    /// Sets the `CS_*` styles of the class of the window.
+    ///
+    /// The class is shared by the windows created with the same
+    /// [`class_name`](crate::WindowAttributes::class_name) and
+    /// [class style](ConfigExtWindows::with_class_style), which are affected as well. Windows
+    /// created afterwards with the previous styles get a class of their own. When the window has
+    /// been created by someone else, its class may be shared with other windows too.
+    fn set_class_style(&mut self, style: u32) -> Result<(), Error>;
+
+    /// Sets whether the system draws a drop shadow under the window.
+    ///
+    /// This adds or removes the `CS_DROPSHADOW` style of the class of the window, and takes
+    /// effect the next time the window is shown. The shadow is only drawn when enabled in the
+    /// settings of the system. This is typically used for popups without decorations.
+    fn set_drop_shadow(&mut self, yes: bool) -> Result<(), Error>;
+}
+
+impl WindowExtWindows for Window {
+    #[inline(always)]
+    fn class_style(&self) -> u32 {
+        self.0.class_style()
+    }
+
+    #[inline(always)]
+    fn set_class_style(&mut self, style: u32) -> Result<(), Error> {
+        self.0.set_class_style(style).map_err(Error::os)
+    }
+
+    #[inline(always)]
+    fn set_drop_shadow(&mut self, yes: bool) -> Result<(), Error> {
+        self.0.set_drop_shadow(yes).map_err(Error::os)
+    }
+}
+
+/// Windows-specific extensions to [`Config`].
+pub trait ConfigExtWindows: Sealed {
+    /// Sets the `CS_*` styles the class of the window is registered with.
+    ///
+    /// This replaces the styles set by [`with_drop_shadow`](ConfigExtWindows::with_drop_shadow)
+    /// when called after it. Windows that share a
+    /// [`class_name`](crate::WindowAttributes::class_name) and styles share their class. When a
+    /// class with the same name but different styles is already registered, the class of the window
+    /// is registered under a name derived from its styles, which other applications see instead of
+    /// the requested one.
+    ///
+    /// **Default:** `CS_HREDRAW | CS_VREDRAW`
+    fn with_class_style(self, style: u32) -> Self;
+
+    /// Sets whether the system draws a drop shadow under the window.
+    ///
+    /// See [`WindowExtWindows::set_drop_shadow`].
+    ///
+    /// **Default:** `false`
+    fn with_drop_shadow(self, yes: bool) -> Self;
+}
+
+impl ConfigExtWindows for Config<'_> {
+    #[inline]
+    fn with_class_style(mut self, style: u32) -> Self {
+        self.platform.class_style = style;
+        self
+    }
+
+    #[inline]
+    fn with_drop_shadow(mut self, yes: bool) -> Self {
+        self.platform.class_style = imp::with_drop_shadow(self.platform.class_style, yes);
+        self
+    }
+}
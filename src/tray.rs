@@ -0,0 +1,121 @@
+use crate::{imp, Error, Icon, MouseButton, Window};
+
+/// An icon displayed in the notification area of the taskbar (the "system tray").
+///
+/// Interactions with the icon are reported as [`Event::TrayIcon`] events to the window the
+/// icon was created with. The icon is removed when the [`TrayIcon`] is dropped.
+///
+/// [`Event::TrayIcon`]: crate::Event::TrayIcon
+#[derive(Debug)]
+pub struct TrayIcon(imp::TrayIcon);
+
+impl TrayIcon {
+    /// Adds an icon to the notification area.
+    ///
+    /// `tooltip` is displayed when the cursor hovers the icon. It is truncated to 127 UTF-16
+    /// code units.
+    ///
+    /// The window receives the events of the icon, and does not need to be visible. Background
+    /// utilities typically create a hidden window for this purpose. When the taskbar is
+    /// re-created (for example when Windows Explorer restarts), the icon is added back as long
+    /// as the window is not a child window.
+    pub fn new(window: &Window, icon: Icon, tooltip: &str) -> Result<Self, Error> {
+        imp::TrayIcon::new(window.0.hwnd(), icon.0, tooltip)
+            .map(Self)
+            .map_err(Error::os)
+    }
+
+    /// Returns the identifier of the icon, which is used to identify its events.
+    #[inline(always)]
+    pub fn id(&self) -> TrayIconId {
+        self.0.id()
+    }
+
+    /// Sets the icon displayed in the notification area.
+    #[inline(always)]
+    pub fn set_icon(&mut self, icon: Icon) -> Result<(), Error> {
+        self.0.set_icon(icon.0).map_err(Error::os)
+    }
+
+    /// Attaches a popup menu to the icon.
+    ///
+    /// The menu is shown when the user requests the context menu of the icon, instead of
+    /// sending [`TrayIconEvent::ContextMenu`]. The item selected by the user, if any, is reported
+    /// with [`TrayIconEvent::MenuItemSelected`]. If `None`, the current menu is removed.
+    ///
+    /// The menu is shown while events are being polled, and blocks until it is closed.
+    #[inline(always)]
+    pub fn set_menu(&mut self, items: Option<&[MenuItem]>) -> Result<(), Error> {
+        self.0.set_menu(items).map_err(Error::os)
+    }
+
+    /// Sets the tooltip displayed when the cursor hovers the icon.
+    ///
+    /// See [`TrayIcon::new`] for more information.
+    #[inline(always)]
+    pub fn set_tooltip(&mut self, tooltip: &str) -> Result<(), Error> {
+        self.0.set_tooltip(tooltip).map_err(Error::os)
+    }
+}
+
+/// Identifies a [`TrayIcon`] in the events it produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TrayIconId(pub(crate) u16);
+
+/// An interaction with a [`TrayIcon`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrayIconEvent {
+    /// The icon has been clicked with the provided button.
+    ///
+    /// Selecting the icon with the keyboard is reported as a click of the left button.
+    Clicked(MouseButton),
+    /// The icon has been double-clicked with the provided button.
+    ///
+    /// A [`Clicked`](TrayIconEvent::Clicked) event is sent for the first click.
+    DoubleClicked(MouseButton),
+    /// The user requested the context menu of the icon.
+    ///
+    /// This is sent after the icon has been clicked with the right button, or when the menu key
+    /// is pressed while the icon is selected.
+    ContextMenu,
+    /// An item of the menu attached to the icon has been selected.
+    ///
+    /// This contains the identifier of the item. See [`TrayIcon::set_menu`].
+    MenuItemSelected(u32),
+}
+
+/// An item of a popup menu.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MenuItem {
+    /// An item that can be selected.
+    Item {
+        /// The identifier of the item, reported when it is selected.
+        ///
+        /// Identifiers are chosen by the application and do not need to be unique.
+        id: u32,
+        /// The text of the item.
+        ///
+        /// An `&` character makes the next character the access key of the item, which is
+        /// underlined. Use `&&` to display an `&` character.
+        label: String,
+        /// Whether a checkmark is displayed next to the item.
+        checked: bool,
+        /// Whether the item can be selected.
+        ///
+        /// Disabled items are grayed out.
+        enabled: bool,
+    },
+    /// A horizontal line between two groups of items.
+    Separator,
+    /// An item that opens a nested menu.
+    Submenu {
+        /// The text of the item.
+        ///
+        /// See the `label` of [`MenuItem::Item`] for more information.
+        label: String,
+        /// The items of the nested menu.
+        items: Vec<MenuItem>,
+    },
+}
@@ -1,4 +1,10 @@
-use crate::{imp, Error, Event};
+use std::fmt;
+
+use crate::{
+    imp, ClipboardRenderer, Cursor, CursorGrabMode, CursorIcon, DropEffect, Error, Event, HotkeyId,
+    Icon, Key, KeyboardState, LockKeys, Modifiers, Monitor, PhysicalPosition, PhysicalSize,
+    Position, RawInputDevices, Size, VideoMode,
+};
 
 /// Represents a window.
 ///
@@ -7,23 +13,888 @@ pub struct Window(pub(crate) imp::Window);
 
 impl Window {
     /// Creates a new [`Window`] instance, initiating a connection to the windowing system.
+    ///
+    /// # Errors
+    ///
+    /// The configuration is [validated](crate::Config::validate) first, such that an invalid
+    /// configuration is reported by an error with a [`config_error`](Error::config_error)
+    /// instead of being passed to the system.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** the calling thread is made aware of the DPI of each monitor, such that the
+    ///   window is rendered at the resolution of its monitor and its coordinates are physical
+    ///   pixels. A [`ScaleFactorChanged`] event is sent when the window moves to a monitor with
+    ///   a different DPI. This requires Windows 10, version 1703; older versions keep the DPI
+    ///   awareness declared by the manifest of the application.
+    ///
+    /// [`ScaleFactorChanged`]: crate::Event::ScaleFactorChanged
     pub fn new(config: crate::Config) -> Result<Self, Error> {
+        config.validate()?;
+
         match imp::Window::new(config) {
             Ok(window) => Ok(Self(window)),
-            Err(error) => Err(Error(error)),
+            Err(error) => Err(Error::os(error)),
+        }
+    }
+
+    /// Creates a new [`Window`] instance from a window created by someone else.
+    ///
+    /// The window is subclassed such that it keeps its own behavior, while [`poll_events`]
+    /// reports the events it receives. Dropping the returned [`Window`] removes the subclass but
+    /// does not destroy the window. This allows existing applications to adopt this crate
+    /// progressively.
+    ///
+    /// Like the windows created by this crate, the window is not closed when the user requests
+    /// it. A [`CloseRequested`](crate::Event::CloseRequested) event is sent instead.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `handle` is not a handle to a Win32 window.
+    ///
+    /// # Safety
+    ///
+    /// The window must have been created by the current thread, and must remain valid until the
+    /// returned [`Window`] is dropped.
+    ///
+    /// [`poll_events`]: Window::poll_events
+    #[cfg(feature = "raw-window-handle")]
+    pub unsafe fn from_raw_handle(handle: rwh::RawWindowHandle) -> Result<Self, Error> {
+        match unsafe { imp::Window::from_raw_handle(handle) } {
+            Ok(window) => Ok(Self(window)),
+            Err(error) => Err(Error::os(error)),
         }
     }
 
+    /// Destroys the window.
+    ///
+    /// This is equivalent to dropping the [`Window`]. No more events are received once this
+    /// function has been called.
+    #[inline(always)]
+    pub fn destroy(self) {
+        drop(self);
+    }
+
+    /// Returns the identifier of the window.
+    ///
+    /// Unlike the handle of the window, the identifier is never reused by another window
+    /// created by the process, even once the window has been destroyed.
+    #[inline(always)]
+    pub fn id(&self) -> WindowId {
+        self.0.id()
+    }
+
+    /// Returns whether the window is still open.
+    ///
+    /// A window may be destroyed by the system without being dropped, for example when its
+    /// [owner](crate::Config::owner) or its parent is destroyed. Once this function returns
+    /// `false`, no more events are received and the [`Window`] should be dropped. Its other
+    /// methods do not panic: those that can fail return an error, and the others may do
+    /// nothing or return a default value.
+    #[inline(always)]
+    pub fn is_open(&self) -> bool {
+        self.0.is_open()
+    }
+
+    /// Requests the window to close itself, as if the user had clicked its close button.
+    ///
+    /// This does not close the window. Instead, an [`Event::CloseRequested`] event is received
+    /// during the next call to [`poll_events`], letting the application go through the same
+    /// path (for example to ask for confirmation) regardless of how the request was made.
+    ///
+    /// [`poll_events`]: Window::poll_events
+    #[inline(always)]
+    pub fn request_close(&mut self) {
+        self.0.request_close();
+    }
+
     /// Sets the visibility of the window.
     #[inline(always)]
     pub fn set_visible(&mut self, yes: bool) {
         self.0.set_visible(yes);
     }
 
+    /// Sets the visibility of the window, without activating it.
+    ///
+    /// Unlike [`set_visible`](Window::set_visible), showing the window this way does not take
+    /// the keyboard focus away from the window the user is currently working with. This is
+    /// typically used for popups and notifications.
+    #[inline(always)]
+    pub fn set_visible_without_activation(&mut self, yes: bool) {
+        self.0.set_visible_without_activation(yes);
+    }
+
+    /// Minimizes the window.
+    #[inline(always)]
+    pub fn minimize(&mut self) {
+        self.0.minimize();
+    }
+
+    /// Maximizes the window.
+    ///
+    /// If the window was hidden, it is shown.
+    #[inline(always)]
+    pub fn maximize(&mut self) {
+        self.0.maximize();
+    }
+
+    /// Restores the window to its original size and position if it was minimized or maximized.
+    #[inline(always)]
+    pub fn restore(&mut self) {
+        self.0.restore();
+    }
+
+    /// Returns whether the window is currently minimized.
+    #[inline(always)]
+    pub fn is_minimized(&self) -> bool {
+        self.0.is_minimized()
+    }
+
+    /// Returns whether the window is currently maximized.
+    #[inline(always)]
+    pub fn is_maximized(&self) -> bool {
+        self.0.is_maximized()
+    }
+
+    /// Brings the window to the foreground and gives it the input focus.
+    ///
+    /// If the window is minimized, it is restored first.
+    ///
+    /// # Note
+    ///
+    /// The windowing system may refuse to give the focus to the window, for example when the user
+    /// is actively interacting with another application. In that case, the window is
+    /// highlighted in the taskbar instead, until the user activates it.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** the input of the calling thread is briefly attached to the one of the
+    ///   foreground window, which lets the system accept the request in most cases. No input is
+    ///   simulated.
+    #[inline(always)]
+    pub fn focus(&mut self) {
+        self.0.focus();
+    }
+
+    /// Returns whether the window currently has the input focus.
+    #[inline(always)]
+    pub fn has_focus(&self) -> bool {
+        self.0.has_focus()
+    }
+
+    /// Requests the attention of the user without stealing the focus.
+    ///
+    /// The request stops automatically once the window receives the focus. If `None`, an
+    /// eventual previous request is cancelled.
+    ///
+    /// If the window already has the focus, this function has no effect.
+    #[inline(always)]
+    pub fn request_user_attention(&mut self, ty: Option<UserAttentionType>) {
+        self.0.request_user_attention(ty);
+    }
+
+    /// Registers a system-wide hotkey.
+    ///
+    /// Once registered, pressing the provided key along with the provided modifiers sends an
+    /// [`Event::Hotkey`] event to the window, even when it does not have the focus. The
+    /// combination is no longer delivered to other applications.
+    ///
+    /// This fails if the combination has already been registered by another application. The
+    /// hotkey is unregistered when the window is dropped.
+    #[inline(always)]
+    pub fn register_hotkey(&mut self, modifiers: Modifiers, key: Key) -> Result<HotkeyId, Error> {
+        self.0.register_hotkey(modifiers, key).map_err(Error::os)
+    }
+
+    /// Unregisters a hotkey previously registered with
+    /// [`register_hotkey`](Window::register_hotkey).
+    #[inline(always)]
+    pub fn unregister_hotkey(&mut self, id: HotkeyId) {
+        self.0.unregister_hotkey(id);
+    }
+
+    /// Sets whether the window receives text input.
+    ///
+    /// When disabled, no [`Event::Text`] events are sent and the input method editor of the
+    /// system is turned off, while [`Event::KeyboardKey`] events are still sent. Games typically
+    /// disable text input during gameplay and enable it while a chat box is focused.
+    ///
+    /// System shortcuts, such as Alt+Space to open the window menu, keep working while text input
+    /// is disabled.
+    ///
+    /// Text input is allowed by default.
+    #[inline(always)]
+    pub fn set_text_input_allowed(&mut self, yes: bool) {
+        self.0.set_text_input_allowed(yes);
+    }
+
+    /// Returns whether the provided key is currently pressed.
+    ///
+    /// See [`keyboard_state`](Window::keyboard_state) for more information.
+    #[inline(always)]
+    pub fn is_key_pressed(&self, key: Key) -> bool {
+        self.keyboard_state().is_pressed(key)
+    }
+
+    /// Returns a snapshot of the keys that are currently pressed.
+    ///
+    /// The state is updated when [`Event::KeyboardKey`] events are processed, and is therefore
+    /// only tracked while raw keyboard input is enabled. All keys are considered released when
+    /// the window loses the focus.
+    #[inline(always)]
+    pub fn keyboard_state(&self) -> KeyboardState {
+        self.0.keyboard_state()
+    }
+
+    /// Returns the lock keys that are currently toggled on.
+    ///
+    /// The state is updated as keyboard events are processed. Changes are reported through
+    /// [`Event::LockKeysChanged`] events.
+    #[inline(always)]
+    pub fn lock_keys(&self) -> LockKeys {
+        self.0.lock_keys()
+    }
+
+    /// Sets whether the window captures the mouse.
+    ///
+    /// While the mouse is captured, the window keeps receiving [`Event::CursorMoved`] events
+    /// when the cursor leaves it, which is typically needed for sliders and other drag
+    /// interactions. The capture is usually started when a mouse button is pressed and released
+    /// when it is released.
+    ///
+    /// The system may end the capture at any time, for example when another window is
+    /// activated. A [`Event::MouseCaptureLost`] event is sent whenever a capture started by
+    /// this function ends. The captures the system starts on its own, such as while the user
+    /// moves or resizes the window, are not reported.
+    #[inline(always)]
+    pub fn set_mouse_capture(&mut self, yes: bool) {
+        self.0.set_mouse_capture(yes);
+    }
+
+    /// Returns whether the window currently has the mouse captured.
+    #[inline(always)]
+    pub fn has_mouse_capture(&self) -> bool {
+        self.0.has_mouse_capture()
+    }
+
+    /// Sets whether the window accepts mouse and keyboard input.
+    ///
+    /// A disabled window is still visible, but the user cannot interact with it. This is
+    /// typically used to make a window inert while a modal dialog is showing.
+    #[inline(always)]
+    pub fn set_enabled(&mut self, yes: bool) {
+        self.0.set_enabled(yes);
+    }
+
+    /// Returns whether the window accepts mouse and keyboard input.
+    #[inline(always)]
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_enabled()
+    }
+
+    /// Sets whether the content of the window should be protected from being captured.
+    ///
+    /// When enabled, the window is excluded from screenshots and screen recordings made by
+    /// other applications. On systems that do not support excluding the window entirely, it
+    /// appears as a black rectangle instead.
+    ///
+    /// # Errors
+    ///
+    /// This function fails when the system does not support protecting the window, which is
+    /// the case for child windows, and for windows whose content is not composed by the desktop
+    /// window manager.
+    #[inline(always)]
+    pub fn set_content_protected(&mut self, yes: bool) -> Result<(), Error> {
+        self.0.set_content_protected(yes).map_err(Error::os)
+    }
+
+    /// Starts moving the window with the mouse, as if the user had grabbed its title bar.
+    ///
+    /// The window follows the cursor until the left mouse button is released. This function
+    /// should be called right after the left mouse button has been pressed, and is typically
+    /// used by windows that draw their own title bar.
+    ///
+    /// # Errors
+    ///
+    /// This function fails when the position of the cursor is unavailable, such as while the
+    /// lock screen is displayed.
+    #[inline(always)]
+    pub fn drag_window(&mut self) -> Result<(), Error> {
+        self.0.drag_window().map_err(Error::os)
+    }
+
+    /// Starts resizing the window with the mouse, as if the user had grabbed the provided edge
+    /// of its border.
+    ///
+    /// The edge follows the cursor until the left mouse button is released. Just like
+    /// [`drag_window`], this function should be called right after the left mouse button has
+    /// been pressed, and works even if the window has no decorations.
+    ///
+    /// # Errors
+    ///
+    /// See [`drag_window`].
+    ///
+    /// [`drag_window`]: Window::drag_window
+    #[inline(always)]
+    pub fn drag_resize_window(&mut self, direction: ResizeDirection) -> Result<(), Error> {
+        self.0.drag_resize_window(direction).map_err(Error::os)
+    }
+
+    /// Sets the function used to determine which area of the window is under the cursor.
+    ///
+    /// The function receives a position relative to the client area of the window and returns
+    /// how the windowing system should treat it. This allows windows that draw their own title
+    /// bar to keep the native behavior of the system (dragging, snapping, resizing from the
+    /// borders, etc.).
+    ///
+    /// The function is only called for points within the client area. The decorations of the
+    /// window, if any, keep their usual behavior. If `None`, the whole client area is treated
+    /// as [`HitTestArea::Client`].
+    ///
+    /// # Note
+    ///
+    /// The function is called by the windowing system whenever the cursor moves over the window,
+    /// even outside of [`poll_events`]. It should be fast and must not block.
+    ///
+    /// [`poll_events`]: Window::poll_events
+    #[inline(always)]
+    pub fn set_hit_test(
+        &mut self,
+        hit_test: Option<Box<dyn Send + FnMut(i32, i32) -> HitTestArea>>,
+    ) {
+        self.0.set_hit_test(hit_test);
+    }
+
+    /// Sets the function used to provide the contents of the clipboard offered by the window.
+    ///
+    /// The function is called when another application requests contents that have been
+    /// [offered](crate::Clipboard::offer) through a [`Clipboard`] opened with this window as its
+    /// owner. It receives the requested format, and must provide the contents in that format by
+    /// calling the corresponding setter of the provided [`Clipboard`]. Other formats can be
+    /// provided as well.
+    ///
+    /// # Note
+    ///
+    /// The function is called by the windowing system whenever the contents are requested, even
+    /// outside of [`poll_events`]. The application that requested them waits until the function
+    /// returns.
+    ///
+    /// [`poll_events`]: Window::poll_events
+    /// [`Clipboard`]: crate::Clipboard
+    #[inline(always)]
+    pub fn set_clipboard_renderer(&mut self, renderer: Option<ClipboardRenderer>) {
+        self.0.set_clipboard_renderer(renderer);
+    }
+
+    /// Sets the effect of dropping data on the window.
+    ///
+    /// The effect is reported to the source of the data while it is dragged over the window,
+    /// which reflects it with the cursor, and applied once the data is dropped. It is typically
+    /// chosen when receiving [`Event::DragEntered`] and [`Event::DragMoved`], depending on the
+    /// data and the position of the cursor, and takes effect the next time the cursor moves.
+    ///
+    /// **Default:** [`DropEffect::Copy`]
+    #[inline(always)]
+    pub fn set_drop_effect(&mut self, effect: DropEffect) {
+        self.0.set_drop_effect(effect);
+    }
+
+    /// Sets whether data can be dropped on the window.
+    ///
+    /// See [`accept_drops`](crate::WindowAttributes::accept_drops) for more information.
+    ///
+    /// # Errors
+    ///
+    /// Enabling drag-and-drop fails when it is not available on the thread that created the
+    /// window.
+    #[inline(always)]
+    pub fn set_accept_drops(&mut self, yes: bool) -> Result<(), Error> {
+        self.0.set_accept_drops(yes).map_err(Error::os)
+    }
+
+    /// Shows the system menu of the window at the provided position, relative to the client
+    /// area.
+    ///
+    /// This is the menu that appears when right-clicking the title bar of a decorated window,
+    /// and that allows the user to restore, move, resize, minimize, maximize or close the
+    /// window. Windows that draw their own title bar can use this function to provide the same
+    /// behavior.
+    ///
+    /// This function blocks until the menu is closed.
+    #[inline(always)]
+    pub fn show_window_menu(&mut self, position: impl Into<Position>) {
+        let (x, y) = position.into().to_physical(self.scale_factor()).into();
+        self.0.show_window_menu(x, y);
+    }
+
+    /// Sets the icon of the window.
+    ///
+    /// The icon is displayed in the title bar of the window, as well as in the taskbar and in
+    /// the task switcher unless a different icon has been set with [`set_taskbar_icon`].
+    ///
+    /// [`set_taskbar_icon`]: Window::set_taskbar_icon
+    #[inline(always)]
+    pub fn set_icon(&mut self, icon: Icon) {
+        self.0.set_icon(icon.0);
+    }
+
+    /// Sets the icon displayed in the taskbar and in the task switcher.
+    ///
+    /// This icon is usually larger than the one displayed in the title bar of the window, and
+    /// can be updated at any time (for example to reflect an unread count). If `None`, the icon
+    /// set with [`set_icon`] is used instead.
+    ///
+    /// [`set_icon`]: Window::set_icon
+    #[inline(always)]
+    pub fn set_taskbar_icon(&mut self, icon: Option<Icon>) {
+        self.0.set_taskbar_icon(icon.map(|icon| icon.0));
+    }
+
+    /// Sets a small icon displayed over the taskbar button of the window.
+    ///
+    /// This is typically used to notify the user of a status change, such as an unread count
+    /// in a chat client. `description` is read by accessibility tools instead of the icon. If
+    /// `None`, the current overlay is removed.
+    ///
+    /// The overlay is kept when the taskbar is re-created. Setting it may fail if the taskbar
+    /// button of the window does not exist yet, but it is still applied once it is created.
+    #[inline(always)]
+    pub fn set_overlay_icon(&mut self, icon: Option<Icon>, description: &str) -> Result<(), Error> {
+        self.0
+            .set_overlay_icon(icon.map(|icon| icon.0), description)
+            .map_err(Error::os)
+    }
+
+    /// Sets the cursor displayed when the cursor is over the client area of the window.
+    ///
+    /// By default, the cursor is [`CursorIcon::Arrow`].
+    #[inline(always)]
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.0.set_cursor_icon(icon);
+    }
+
+    /// Sets a custom cursor displayed when the cursor is over the client area of the window.
+    ///
+    /// This replaces any cursor previously set with [`set_cursor_icon`].
+    ///
+    /// [`set_cursor_icon`]: Window::set_cursor_icon
+    #[inline(always)]
+    pub fn set_cursor(&mut self, cursor: Cursor) {
+        self.0.set_cursor(cursor.0);
+    }
+
+    /// Sets whether the cursor is visible while it is over the client area of the window.
+    ///
+    /// A hidden cursor is shown again while the window does not have the focus, or when the
+    /// cursor leaves the client area. This is typically used for first-person camera controls,
+    /// along with raw mouse motion events.
+    #[inline(always)]
+    pub fn set_cursor_visible(&mut self, yes: bool) {
+        self.0.set_cursor_visible(yes);
+    }
+
+    /// Sets whether the window receives raw input events from keyboards and mice.
+    ///
+    /// This is a shorthand for [`set_raw_input_devices`](Window::set_raw_input_devices) with
+    /// either all device classes or none of them.
+    #[inline(always)]
+    pub fn set_raw_input_enabled(&mut self, yes: bool) -> Result<(), Error> {
+        let devices = if yes {
+            RawInputDevices::all()
+        } else {
+            RawInputDevices::empty()
+        };
+
+        self.set_raw_input_devices(devices)
+    }
+
+    /// Sets the device classes from which the window receives raw input events.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** raw input is registered for the whole process. Only the window that
+    ///   registered a device class last receives its events.
+    #[inline(always)]
+    pub fn set_raw_input_devices(&mut self, devices: RawInputDevices) -> Result<(), Error> {
+        self.0.set_raw_input_devices(devices).map_err(Error::os)
+    }
+
+    /// Returns the position of the cursor, relative to the top-left corner of the client area of
+    /// the window.
+    ///
+    /// If the cursor is outside of the client area, `None` is returned. Note that the cursor may
+    /// be over another window that covers the client area.
+    #[inline(always)]
+    pub fn cursor_position(&self) -> Option<PhysicalPosition> {
+        self.0.cursor_position().map(PhysicalPosition::from)
+    }
+
+    /// Moves the cursor to the provided position, relative to the top-left corner of the client
+    /// area of the window.
+    ///
+    /// This is typically used to recenter the cursor after each frame in camera controls. Note
+    /// that the move itself is reported through a [`Event::CursorMoved`] event. Logical
+    /// positions are converted using the [scale factor](Window::scale_factor) of the window.
+    #[inline(always)]
+    pub fn set_cursor_position(&mut self, position: impl Into<Position>) -> Result<(), Error> {
+        let (x, y) = position.into().to_physical(self.scale_factor()).into();
+        self.0.set_cursor_position(x, y).map_err(Error::os)
+    }
+
+    /// Sets the way the cursor is restricted to the window.
+    ///
+    /// The restriction is only in effect while the window has the focus. It is lifted when the
+    /// window loses the focus, and applied again when it regains it.
+    #[inline(always)]
+    pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) -> Result<(), Error> {
+        self.0.set_cursor_grab(mode).map_err(Error::os)
+    }
+
+    /// Sets whether the window is in relative mouse mode.
+    ///
+    /// While the window has the focus, the cursor is hidden and locked in place, no
+    /// [`Event::CursorMoved`] events are sent, and raw [`Event::MouseMoved`] events are
+    /// delivered even if they have been disabled with
+    /// [`set_raw_input_devices`](Window::set_raw_input_devices). This is typically used for
+    /// first-person camera controls.
+    ///
+    /// The cursor is released when the window loses the focus. Leaving relative mouse mode
+    /// restores the previous visibility and grab mode of the cursor.
+    ///
+    /// # Errors
+    ///
+    /// This function fails when the cursor cannot be restricted or when the raw mouse motion
+    /// cannot be received, in which case the window is left in its previous mode.
+    #[inline(always)]
+    pub fn set_relative_mouse_mode(&mut self, yes: bool) -> Result<(), Error> {
+        self.0.set_relative_mouse_mode(yes).map_err(Error::os)
+    }
+
+    /// Sets whether the window should include the system's default decorations.
+    ///
+    /// The window is resized such that its client area keeps the same size and position on the
+    /// screen.
+    #[inline(always)]
+    pub fn set_decorations(&mut self, yes: bool) {
+        self.0.set_decorations(yes);
+    }
+
+    /// Sets the fullscreen mode of the window.
+    ///
+    /// When the window enters fullscreen mode, its placement is saved and its decorations are
+    /// removed. Passing `None` makes the window leave fullscreen mode and restores its previous
+    /// placement and decorations.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the video mode of an [exclusive](Fullscreen::Exclusive)
+    /// fullscreen mode cannot be applied. In that case, the window is no longer in fullscreen
+    /// mode.
+    #[inline(always)]
+    pub fn set_fullscreen(&mut self, fullscreen: Option<Fullscreen>) -> Result<(), Error> {
+        self.0.set_fullscreen(fullscreen).map_err(Error::os)
+    }
+
+    /// Returns the current fullscreen mode of the window, if any.
+    #[inline(always)]
+    pub fn fullscreen(&self) -> Option<Fullscreen> {
+        self.0.fullscreen()
+    }
+
+    /// Sets the theme of the window, which determines how its decorations are drawn.
+    ///
+    /// When `None` is passed, the window follows the preference of the user, including when it
+    /// changes. This is the default.
+    ///
+    /// This has no effect on versions of Windows that do not support dark title bars.
+    #[inline(always)]
+    pub fn set_theme(&mut self, theme: Option<Theme>) {
+        self.0.set_theme(theme);
+    }
+
+    /// Returns the theme currently used by the window.
+    #[inline(always)]
+    pub fn theme(&self) -> Theme {
+        self.0.theme()
+    }
+
+    /// Sets the material drawn by the system behind the window.
+    ///
+    /// Materials other than [`Backdrop::Auto`] and [`Backdrop::None`] are drawn behind the whole
+    /// window, and are only visible through the transparent parts of its content.
+    ///
+    /// # Errors
+    ///
+    /// This function fails on systems that do not support backdrops. They are only supported
+    /// starting with Windows 11, version 22H2.
+    #[inline(always)]
+    pub fn set_backdrop(&mut self, backdrop: Backdrop) -> Result<(), Error> {
+        self.0.set_backdrop(backdrop).map_err(Error::os)
+    }
+
+    /// Sets whether the corners of the window should be rounded.
+    ///
+    /// # Errors
+    ///
+    /// This function fails on systems that do not round the corners of windows. This is only
+    /// done starting with Windows 11.
+    #[inline(always)]
+    pub fn set_corner_preference(&mut self, preference: CornerPreference) -> Result<(), Error> {
+        self.0.set_corner_preference(preference).map_err(Error::os)
+    }
+
+    /// Sets the level of the window, which determines how it is ordered relative to other
+    /// windows.
+    #[inline(always)]
+    pub fn set_level(&mut self, level: WindowLevel) {
+        self.0.set_level(level);
+    }
+
+    /// Sets whether the window is a tool window.
+    ///
+    /// See [`tool_window`](crate::WindowAttributes::tool_window) for more information.
+    #[inline(always)]
+    pub fn set_tool_window(&mut self, yes: bool) {
+        self.0.set_tool_window(yes);
+    }
+
+    /// Sets whether the window is hidden from the taskbar.
+    ///
+    /// See [`skip_taskbar`](crate::WindowAttributes::skip_taskbar) for more information.
+    #[inline(always)]
+    pub fn set_skip_taskbar(&mut self, yes: bool) {
+        self.0.set_skip_taskbar(yes);
+    }
+
+    /// Sets whether the window receives mouse input.
+    ///
+    /// When disabled, mouse input goes through the window and reaches whatever is behind it,
+    /// including windows of other applications.
+    #[inline(always)]
+    pub fn set_cursor_hittest(&mut self, yes: bool) {
+        self.0.set_cursor_hittest(yes);
+    }
+
+    /// Sets the opacity of the whole window, including its decorations.
+    ///
+    /// `0.0` makes the window fully transparent, and `1.0` makes it fully opaque. Values outside
+    /// of this range are clamped.
+    #[inline(always)]
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.0.set_opacity(opacity);
+    }
+
+    /// Returns the monitor the window is on.
+    ///
+    /// When the window spans multiple monitors, the one that has the largest area of
+    /// intersection with the window is returned. This is typically used to center new windows
+    /// on the monitor the user is working on.
+    #[inline(always)]
+    pub fn current_monitor(&self) -> Monitor {
+        Monitor(self.0.current_monitor())
+    }
+
+    /// Returns the refresh rate of the monitor the window is on, in hertz.
+    ///
+    /// If the refresh rate is unknown, `None` is returned.
+    #[inline(always)]
+    pub fn refresh_rate(&self) -> Option<u32> {
+        self.0.refresh_rate()
+    }
+
+    /// Blocks until the next vertical blank of the display.
+    ///
+    /// This is typically used by software renderers and latency-sensitive applications to pace
+    /// their frames without a GPU swapchain: presenting right after this function returns gives
+    /// the compositor a full frame to pick up the new content.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** this waits for the desktop window manager to present its next frame,
+    ///   which happens on the vertical blank of the primary monitor.
+    #[inline(always)]
+    pub fn wait_for_vblank(&self) -> Result<(), Error> {
+        self.0.wait_for_vblank().map_err(Error::os)
+    }
+
+    /// Returns the scale factor of the window, which depends on the DPI of the monitor it is on.
+    ///
+    /// A scale factor of `1.0` corresponds to 96 DPI. The user interface of the application
+    /// should be scaled by this factor to keep the same physical size on every monitor.
+    #[inline(always)]
+    pub fn scale_factor(&self) -> f64 {
+        self.0.scale_factor()
+    }
+
     /// Returns the size of the window's client area.
     #[inline(always)]
-    pub fn client_size(&self) -> (u32, u32) {
-        self.0.client_size()
+    pub fn client_size(&self) -> PhysicalSize {
+        self.0.client_size().into()
+    }
+
+    /// Returns the size of the window, including its decorations.
+    ///
+    /// Unlike [`client_size`], this is the full footprint of the window on the screen.
+    ///
+    /// [`client_size`]: Window::client_size
+    #[inline(always)]
+    pub fn outer_size(&self) -> PhysicalSize {
+        self.0.outer_size().into()
+    }
+
+    /// Requests the window's client area to be resized to the provided size.
+    ///
+    /// The size of the window's decorations is automatically taken into account. Note that the
+    /// windowing system may not honor the request exactly, [`client_size`] can be used to
+    /// query the actual size of the window afterwards. Logical sizes are converted using the
+    /// [scale factor](Window::scale_factor) of the window.
+    ///
+    /// [`client_size`]: Window::client_size
+    #[inline(always)]
+    pub fn request_client_size(&mut self, size: impl Into<Size>) {
+        let (width, height) = size.into().to_physical(self.scale_factor()).into();
+        self.0.request_client_size(width, height);
+    }
+
+    /// Sets the minimum size of the window's client area.
+    ///
+    /// If `None`, the window can be shrunk down to whatever size the system allows. If the
+    /// window is currently smaller than the provided size, it is grown to match it. Logical
+    /// sizes follow the [scale factor](Window::scale_factor) of the window as it changes.
+    #[inline(always)]
+    pub fn set_min_size(&mut self, size: Option<Size>) {
+        self.0.set_min_size(size);
+    }
+
+    /// Sets the maximum size of the window's client area.
+    ///
+    /// If `None`, the window can be grown up to whatever size the system allows. If the window
+    /// is currently larger than the provided size, it is shrunk to match it. Logical sizes
+    /// follow the [scale factor](Window::scale_factor) of the window as it changes.
+    #[inline(always)]
+    pub fn set_max_size(&mut self, size: Option<Size>) {
+        self.0.set_max_size(size);
+    }
+
+    /// Locks the aspect ratio of the window's client area while it is being resized by the user.
+    ///
+    /// The ratio is expressed as `(width, height)`, for example `(16, 9)`. If `None`, the window
+    /// can be resized freely.
+    ///
+    /// The current size of the window is left untouched, the ratio is only enforced when the
+    /// user drags the borders of the window.
+    #[inline(always)]
+    pub fn set_aspect_ratio(&mut self, ratio: Option<(u32, u32)>) {
+        self.0.set_aspect_ratio(ratio);
+    }
+
+    /// Returns the position of the window's client area, in screen coordinates.
+    ///
+    /// This is the position of the top-left corner of the client area, excluding the eventual
+    /// decorations of the window.
+    #[inline(always)]
+    pub fn position(&self) -> PhysicalPosition {
+        self.0.position().into()
+    }
+
+    /// Returns the position of the window, in screen coordinates.
+    ///
+    /// Unlike [`position`], this is the position of the top-left corner of the window including
+    /// its decorations.
+    ///
+    /// [`position`]: Window::position
+    #[inline(always)]
+    pub fn outer_position(&self) -> PhysicalPosition {
+        self.0.outer_position().into()
+    }
+
+    /// Moves the window to the provided position, in screen coordinates.
+    ///
+    /// The position is the one of the top-left corner of the window including its decorations,
+    /// just like [`outer_position`] and the [`position`] field of the [`Config`]. Logical
+    /// positions are converted using the [scale factor](Window::scale_factor) of the window.
+    ///
+    /// [`outer_position`]: Window::outer_position
+    /// [`position`]: crate::WindowAttributes::position
+    /// [`Config`]: crate::Config
+    #[inline(always)]
+    pub fn set_position(&mut self, position: impl Into<Position>) {
+        let (x, y) = position.into().to_physical(self.scale_factor()).into();
+        self.0.set_position(x, y);
+    }
+
+    /// Moves the window to the center of the work area of `monitor`.
+    ///
+    /// The size of the window, including its decorations, is taken into account. As the size of
+    /// the decorations depends on the DPI, the window is centered once it has been moved to
+    /// `monitor`. If the monitor has been disconnected, the window is not moved.
+    #[inline(always)]
+    pub fn center_on(&mut self, monitor: &Monitor) {
+        self.0.center_on(&monitor.0);
+    }
+
+    /// Injects a synthetic keyboard key event.
+    ///
+    /// The event goes through the input pipeline of the system, as if it came from a physical
+    /// keyboard, and is delivered to the window that has the focus. Use [`focus`](Window::focus)
+    /// to make sure it is this window. This is typically used to drive an application from
+    /// end-to-end tests.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows:** the PAUSE key is injected through its virtual-key code, as its E1 prefix
+    ///   cannot be injected. Other keys with an E1 prefix cannot be injected.
+    #[cfg(feature = "automation")]
+    #[inline(always)]
+    pub fn inject_key(&mut self, code: crate::KeyCode, pressed: bool) -> Result<(), Error> {
+        self.0.inject_key(code.0, pressed).map_err(Error::os)
+    }
+
+    /// Injects a synthetic mouse button event.
+    ///
+    /// The event is delivered to the window under the cursor, or to the window that has
+    /// captured the mouse. See [`inject_key`](Window::inject_key) for more information.
+    #[cfg(feature = "automation")]
+    #[inline(always)]
+    pub fn inject_mouse_button(
+        &mut self,
+        button: crate::MouseButton,
+        pressed: bool,
+    ) -> Result<(), Error> {
+        self.0
+            .inject_mouse_button(button, pressed)
+            .map_err(Error::os)
+    }
+
+    /// Injects a synthetic mouse motion that moves the cursor to the provided position,
+    /// relative to the top-left corner of the client area of the window.
+    ///
+    /// See [`inject_key`](Window::inject_key) for more information.
+    #[cfg(feature = "automation")]
+    #[inline(always)]
+    pub fn inject_cursor_position(&mut self, position: impl Into<Position>) -> Result<(), Error> {
+        let (x, y) = position.into().to_physical(self.scale_factor()).into();
+        self.0.inject_cursor_position(x, y).map_err(Error::os)
+    }
+
+    /// Injects a synthetic mouse wheel rotation, expressed in the same unit as
+    /// [`Event::MouseWheel`] events.
+    ///
+    /// See [`inject_key`](Window::inject_key) for more information.
+    #[cfg(feature = "automation")]
+    #[inline(always)]
+    pub fn inject_mouse_wheel(&mut self, dx: f64, dy: f64) -> Result<(), Error> {
+        self.0.inject_mouse_wheel(dx, dy).map_err(Error::os)
+    }
+
+    /// Injects text into the window, producing [`Event::Text`] events.
+    ///
+    /// Unlike the other injection functions, the text is sent directly to the window, whether it
+    /// has the focus or not.
+    #[cfg(feature = "automation")]
+    #[inline(always)]
+    pub fn inject_text(&mut self, text: &str) -> Result<(), Error> {
+        self.0.inject_text(text).map_err(Error::os)
     }
 
     /// Calls the given closure with the new, unprocessed events.
@@ -41,8 +912,45 @@ impl Window {
     pub fn blocking_poll_events(&mut self, handler: impl Send + FnMut(Event)) {
         self.0.blocking_poll_events(handler);
     }
+
+    /// Calls `f`, delivering the events received by the window in the meantime to the provided
+    /// handler.
+    ///
+    /// Modal dialogs, such as [`message_box`](crate::message_box) or [`FileDialog`], block
+    /// until they are closed. The window keeps processing messages during that time, but the
+    /// events it produces are discarded unless the dialog is displayed from within `f`. This
+    /// typically lets the application keep drawing the window while a dialog is open.
+    ///
+    /// [`FileDialog`]: crate::FileDialog
+    #[inline(always)]
+    pub fn run_modal<R>(
+        &mut self,
+        mut handler: impl Send + FnMut(Event),
+        f: impl FnOnce(&Window) -> R,
+    ) -> R {
+        // SAFETY: the guard is dropped before the handler, and the window is borrowed for the
+        // whole function.
+        let _guard = unsafe { self.0.set_modal_handler(&mut handler) };
+        f(self)
+    }
+}
+
+impl fmt::Debug for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Window")
+            .field("id", &self.id())
+            .finish_non_exhaustive()
+    }
 }
 
+/// Identifies a [`Window`].
+///
+/// This is typically used as the key of the data an application associates with each of its
+/// windows. See [`Window::id`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WindowId(pub(crate) u64);
+
 #[cfg(feature = "raw-window-handle")]
 impl rwh::HasWindowHandle for Window {
     #[inline(always)]
@@ -58,3 +966,133 @@ impl rwh::HasDisplayHandle for Window {
         self.0.display_handle()
     }
 }
+
+/// A fullscreen mode of a window.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Fullscreen {
+    /// The window covers a whole monitor, without changing its video mode.
+    ///
+    /// When no monitor is specified, the monitor the window is currently on is used.
+    Borderless(Option<Monitor>),
+    /// The window covers the monitor of the provided video mode, which is switched to that
+    /// mode.
+    ///
+    /// The desktop video mode is restored when the window leaves fullscreen mode, when it is
+    /// dropped, or when the process exits (including abnormally).
+    Exclusive(VideoMode),
+}
+
+/// The theme of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Theme {
+    /// Light colors are used.
+    Light,
+    /// Dark colors are used.
+    Dark,
+}
+
+/// A material drawn by the system behind a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backdrop {
+    /// The system decides which material to draw.
+    #[default]
+    Auto,
+    /// No material is drawn.
+    None,
+    /// The Mica material, which is tinted with the desktop wallpaper. It is meant for long-lived
+    /// windows.
+    Mica,
+    /// The Acrylic material, which blurs what is behind the window. It is meant for transient
+    /// windows, such as menus and popups.
+    Acrylic,
+    /// A variant of the Mica material with a stronger tint, meant for windows with tabs in their
+    /// title bar.
+    MicaAlt,
+}
+
+/// Whether the corners of a window should be rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CornerPreference {
+    /// The system decides whether to round the corners of the window.
+    #[default]
+    Default,
+    /// The corners of the window are never rounded.
+    DoNotRound,
+    /// The corners of the window are rounded when appropriate.
+    Round,
+    /// The corners of the window are rounded with a small radius when appropriate.
+    RoundSmall,
+}
+
+/// The level of a window, which determines how it is ordered relative to other windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowLevel {
+    /// The window is ordered normally among other windows.
+    #[default]
+    Normal,
+    /// The window always appears on top of other windows.
+    AlwaysOnTop,
+    /// The window always appears behind other windows.
+    AlwaysOnBottom,
+}
+
+/// The different ways to request the attention of the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UserAttentionType {
+    /// The request is important and should be noticed quickly.
+    ///
+    /// On Windows, both the caption and the taskbar button of the window flash.
+    Critical,
+    /// The request is purely informational.
+    ///
+    /// On Windows, only the taskbar button of the window flashes.
+    #[default]
+    Informational,
+}
+
+/// An edge or corner of a window, from which it can be resized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeDirection {
+    /// The right edge of the window.
+    East,
+    /// The top edge of the window.
+    North,
+    /// The top-right corner of the window.
+    NorthEast,
+    /// The top-left corner of the window.
+    NorthWest,
+    /// The bottom edge of the window.
+    South,
+    /// The bottom-right corner of the window.
+    SouthEast,
+    /// The bottom-left corner of the window.
+    SouthWest,
+    /// The left edge of the window.
+    West,
+}
+
+/// An area of a window, as seen by the windowing system.
+///
+/// This is returned by the function passed to [`Window::set_hit_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HitTestArea {
+    /// The client area of the window. The application receives the mouse input normally.
+    Client,
+    /// The title bar of the window. It can be used to drag the window around, and double
+    /// clicking it maximizes the window.
+    Caption,
+    /// The border of the window. It can be used to resize the window in the provided direction.
+    ResizeBorder(ResizeDirection),
+    /// The maximize button of the window.
+    ///
+    /// Clicking it toggles the maximized state of the window. On Windows 11, hovering it displays
+    /// the Snap Layouts flyout. The application keeps receiving [`Event::CursorMoved`] events
+    /// while the cursor is over this area, letting it draw the button in its hovered state.
+    ///
+    /// [`Event::CursorMoved`]: crate::Event::CursorMoved
+    MaximizeButton,
+}